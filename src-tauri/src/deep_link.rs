@@ -0,0 +1,141 @@
+use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
+use tauri::Url;
+
+pub const DEEP_LINK_SCHEME: &str = "gdexplorer";
+const MAX_DEEP_LINK_PATHS: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum DeepLinkAction {
+    Upload {
+        destination_folder_id: String,
+        paths: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepLinkUploadRequestedEvent {
+    pub action: DeepLinkAction,
+}
+
+// Parses gdexplorer://upload?destination={folder_id}&path={local_path}[&path=...].
+pub fn parse_deep_link_url(url: &str) -> Result<DeepLinkAction, String> {
+    let parsed = Url::parse(url).map_err(|e| format!("Invalid deep link URL: {e}"))?;
+    if parsed.scheme() != DEEP_LINK_SCHEME {
+        return Err(format!("Unsupported deep link scheme: {}", parsed.scheme()));
+    }
+    match parsed.host_str() {
+        Some("upload") => parse_upload(&parsed),
+        other => Err(format!(
+            "Unsupported deep link action: {}",
+            other.unwrap_or_default()
+        )),
+    }
+}
+
+fn parse_upload(url: &Url) -> Result<DeepLinkAction, String> {
+    let mut destination_folder_id = None;
+    let mut paths = Vec::new();
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "destination" => destination_folder_id = Some(value.into_owned()),
+            "path" => paths.push(
+                normalize_local_path(&value).map_err(|e| format!("Invalid path {value:?}: {e}"))?,
+            ),
+            _ => log::debug!("Ignoring unknown deep link parameter: {key}"),
+        }
+    }
+
+    let destination_folder_id = destination_folder_id
+        .ok_or_else(|| "Deep link is missing the destination parameter".to_string())?;
+    crate::validate_drive_folder_id(&destination_folder_id)?;
+    if paths.is_empty() {
+        return Err("Deep link has no path parameters".to_string());
+    }
+    if paths.len() > MAX_DEEP_LINK_PATHS {
+        return Err(format!(
+            "Deep link has too many paths (max {MAX_DEEP_LINK_PATHS})"
+        ));
+    }
+
+    Ok(DeepLinkAction::Upload {
+        destination_folder_id,
+        paths,
+    })
+}
+
+// Requires an absolute path, drops '.' segments and rejects '..' so a link names exactly
+// the file it appears to.
+fn normalize_local_path(raw: &str) -> Result<String, String> {
+    if raw.chars().any(char::is_control) {
+        return Err("must not contain control characters".to_string());
+    }
+    let path = Path::new(raw);
+    if !path.is_absolute() {
+        return Err("must be an absolute path".to_string());
+    }
+
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => return Err("must not contain '..' segments".to_string()),
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    Ok(normalized.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn parses_upload_links_with_encoded_paths() {
+        let action = parse_deep_link_url(
+            "gdexplorer://upload?destination=0AbC-d_1&path=%2Fhome%2Fme%2Fmy%20file.txt&path=/tmp/./x",
+        )
+        .expect("valid link");
+        let DeepLinkAction::Upload {
+            destination_folder_id,
+            paths,
+        } = action;
+        assert_eq!(destination_folder_id, "0AbC-d_1");
+        assert_eq!(paths, ["/home/me/my file.txt", "/tmp/x"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_malformed_upload_links() {
+        for url in [
+            "https://upload?destination=abc&path=/tmp/a",
+            "gdexplorer://download?destination=abc&path=/tmp/a",
+            "gdexplorer://upload?path=/tmp/a",
+            "gdexplorer://upload?destination=abc",
+            "gdexplorer://upload?destination=a%2Fb&path=/tmp/a",
+            "gdexplorer://upload?destination=abc&path=relative/a",
+            "gdexplorer://upload?destination=abc&path=/tmp/../etc/passwd",
+            "gdexplorer://upload?destination=abc&path=/tmp/a%0Ab",
+        ] {
+            assert!(
+                parse_deep_link_url(url).is_err(),
+                "{url} should be rejected"
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn limits_the_number_of_paths() {
+        let paths = "&path=/tmp/a".repeat(MAX_DEEP_LINK_PATHS + 1);
+        let url = format!("gdexplorer://upload?destination=abc{paths}");
+        assert!(parse_deep_link_url(&url).is_err());
+    }
+}
@@ -0,0 +1,404 @@
+use serde::{Deserialize, Serialize};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    pub max_depth: Option<usize>,
+    pub follow_symlinks: bool,
+    pub include_hidden: bool,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: None,
+            follow_symlinks: false,
+            include_hidden: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScannedFile {
+    pub path: PathBuf,
+    // Path relative to the scan root using '/' separators; the file name for a file root.
+    pub rel_path: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanWarning {
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ScanResult {
+    pub files: Vec<ScannedFile>,
+    pub warnings: Vec<ScanWarning>,
+    // Relative paths of folders with nothing in them, not even other folders.
+    pub empty_dirs: Vec<String>,
+    // Symlinks seen while not following them; they are left out of `files`.
+    pub skipped_symlinks: u32,
+    // Google Docs placeholders among `files`; the uploader decides whether they stay there.
+    pub doc_shortcuts: Vec<DocShortcut>,
+    // Set when `progress` stopped the walk early; the lists then only cover part of the tree.
+    pub partial: bool,
+}
+
+// A .gdoc/.gsheet/... file written by Drive for Desktop: a small JSON pointer to a document
+// that only exists in Drive, not the document itself.
+#[derive(Debug, Clone)]
+pub struct DocShortcut {
+    pub path: PathBuf,
+    pub rel_path: String,
+    pub doc_id: String,
+}
+
+const DOC_SHORTCUT_EXTENSIONS: [&str; 9] = [
+    "gdoc", "gsheet", "gslides", "gdraw", "gform", "gmap", "gsite", "gtable", "gjam",
+];
+
+// Real stubs are a few hundred bytes; anything this large is some other file that happens to
+// share the extension.
+const MAX_DOC_SHORTCUT_BYTES: u64 = 64 * 1024;
+
+// The document id a shortcut points at, or None if the file is not one. Older stubs only carry
+// `url`, newer ones `doc_id` and a `resource_id` of the form "type:id".
+pub fn doc_shortcut_id(path: &Path, size: u64) -> Option<String> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    if !DOC_SHORTCUT_EXTENSIONS.contains(&extension.as_str()) || size > MAX_DOC_SHORTCUT_BYTES {
+        return None;
+    }
+    let contents = std::fs::read(path).ok()?;
+    let value: serde_json::Value = serde_json::from_slice(&contents).ok()?;
+    let field = |name: &str| {
+        value
+            .get(name)
+            .and_then(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+    };
+    let id = field("doc_id")
+        .or_else(|| {
+            field("resource_id")
+                .and_then(|r| r.rsplit_once(':'))
+                .map(|(_, id)| id)
+        })
+        .or_else(|| {
+            field("url")
+                .and_then(|url| url.split_once("/d/"))
+                .and_then(|(_, rest)| rest.split(['/', '?', '#']).next())
+        })?;
+    let valid = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'));
+    valid.then(|| id.to_string())
+}
+
+impl ScanResult {
+    pub fn total_bytes(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+}
+
+pub fn scan(root: &Path, options: &ScanOptions) -> ScanResult {
+    scan_with_progress(root, options, |_, _| ControlFlow::Continue(()))
+}
+
+// Walks `root` (a file or a folder) and returns every regular file sorted by path.
+// Entries that cannot be read are reported as warnings instead of being dropped.
+// `progress` is called with the running file count and byte total after each file; breaking
+// stops the walk and marks the result partial, e.g. to enforce a time budget.
+pub fn scan_with_progress<F>(root: &Path, options: &ScanOptions, mut progress: F) -> ScanResult
+where
+    F: FnMut(usize, u64) -> ControlFlow<()>,
+{
+    let mut result = ScanResult::default();
+    let mut total_bytes = 0_u64;
+    let mut dirs = Vec::new();
+
+    let mut walker = WalkDir::new(root).follow_links(options.follow_symlinks);
+    if let Some(max_depth) = options.max_depth {
+        walker = walker.max_depth(max_depth);
+    }
+
+    let mut iter = walker.into_iter();
+    while let Some(entry) = iter.next() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                let path = e.path().unwrap_or(root).to_string_lossy().to_string();
+                result.warnings.push(ScanWarning {
+                    path,
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+
+        if !options.include_hidden && entry.depth() > 0 && is_hidden(entry.file_name()) {
+            if entry.file_type().is_dir() {
+                iter.skip_current_dir();
+            }
+            continue;
+        }
+
+        // Folders at the depth limit were not looked into, so they may not be empty.
+        let below_limit = options.max_depth.is_none_or(|max| entry.depth() < max);
+        if entry.file_type().is_dir() && entry.depth() > 0 && below_limit {
+            dirs.push(relative_path(root, entry.path()));
+        }
+        if entry.file_type().is_symlink() {
+            result.skipped_symlinks += 1;
+            continue;
+        }
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        // The walker already read the file type from the dirent, so directories never reach
+        // this point; entry.metadata() reuses that where the platform allows and the full stat
+        // is only repeated when it fails.
+        let path = entry.path().to_path_buf();
+        let metadata = match entry.metadata().or_else(|_| std::fs::metadata(&path)) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                result.warnings.push(ScanWarning {
+                    path: path.to_string_lossy().to_string(),
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+
+        let rel_path = relative_path(root, &path);
+        if let Some(doc_id) = doc_shortcut_id(&path, metadata.len()) {
+            result.doc_shortcuts.push(DocShortcut {
+                path: path.clone(),
+                rel_path: rel_path.clone(),
+                doc_id,
+            });
+        }
+        total_bytes = total_bytes.saturating_add(metadata.len());
+        result.files.push(ScannedFile {
+            path,
+            rel_path,
+            size: metadata.len(),
+        });
+        if progress(result.files.len(), total_bytes).is_break() {
+            result.partial = true;
+            break;
+        }
+    }
+
+    result.files.sort_by(|a, b| a.path.cmp(&b.path));
+    result.empty_dirs = find_empty_dirs(dirs, &result.files);
+    result
+}
+
+fn find_empty_dirs(mut dirs: Vec<String>, files: &[ScannedFile]) -> Vec<String> {
+    let mut non_empty = std::collections::HashSet::new();
+    for rel_path in dirs.iter().chain(files.iter().map(|file| &file.rel_path)) {
+        let mut parent = rel_path.as_str();
+        while let Some((ancestor, _)) = parent.rsplit_once('/') {
+            if !non_empty.insert(ancestor.to_string()) {
+                break;
+            }
+            parent = ancestor;
+        }
+    }
+    dirs.retain(|dir| !non_empty.contains(dir));
+    dirs.sort();
+    dirs
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    if root == path {
+        return path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+    }
+    path.strip_prefix(root)
+        .ok()
+        .and_then(|p| p.to_str())
+        .map(|p| p.replace('\\', "/"))
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+fn is_hidden(name: &std::ffi::OsStr) -> bool {
+    name.to_str().is_some_and(|n| n.starts_with('.'))
+}
+
+// Sibling names that are distinct locally but equal once case and Unicode composition are
+// ignored, e.g. "Readme.md" and "README.md". `names` is sorted, so the first is the one a
+// first-wins policy keeps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NameCollision {
+    // Relative path of the containing folder; empty for the scan root.
+    pub parent: String,
+    pub names: Vec<String>,
+}
+
+// Checks every folder and file name along the given relative paths ('/' separated).
+pub fn find_name_collisions<'a>(
+    rel_paths: impl IntoIterator<Item = &'a str>,
+) -> Vec<NameCollision> {
+    use std::collections::{BTreeMap, BTreeSet};
+    use unicode_normalization::UnicodeNormalization;
+
+    let mut groups: BTreeMap<(String, String), BTreeSet<String>> = BTreeMap::new();
+    for rel_path in rel_paths {
+        let mut parent = String::new();
+        for name in rel_path.split('/').filter(|name| !name.is_empty()) {
+            let folded = name.nfc().collect::<String>().to_lowercase();
+            groups
+                .entry((parent.clone(), folded))
+                .or_default()
+                .insert(name.to_string());
+            if !parent.is_empty() {
+                parent.push('/');
+            }
+            parent.push_str(name);
+        }
+    }
+    groups
+        .into_iter()
+        .filter(|(_, names)| names.len() > 1)
+        .map(|((parent, _), names)| NameCollision {
+            parent,
+            names: names.into_iter().collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_with_progress_stops_when_the_callback_breaks() {
+        let root = tempfile::tempdir().unwrap();
+        for i in 0..5 {
+            std::fs::write(root.path().join(format!("{i}.txt")), b"x").unwrap();
+        }
+        let result = scan_with_progress(root.path(), &ScanOptions::default(), |count, _| {
+            if count >= 2 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert!(result.partial);
+        assert_eq!(result.files.len(), 2);
+
+        let full = scan(root.path(), &ScanOptions::default());
+        assert!(!full.partial);
+        assert_eq!(full.files.len(), 5);
+    }
+
+    // Times the enumeration patterns on a generated tree. The old code walked and then called
+    // std::fs::metadata per file; entry.metadata() reuses what the walker already read, which
+    // saves the second stat where the OS returns metadata with the directory listing (Windows).
+    // On Linux both cost one stat per file, so only the Windows numbers should differ much.
+    #[test]
+    #[ignore = "creates 100k files; run with cargo test -- --ignored scan_benchmark"]
+    fn scan_benchmark_100k_files() {
+        let root = tempfile::tempdir().unwrap();
+        for dir in 0..100 {
+            let dir_path = root.path().join(format!("dir{dir:03}"));
+            std::fs::create_dir(&dir_path).unwrap();
+            for file in 0..1000 {
+                std::fs::write(dir_path.join(format!("{file:04}.bin")), b"").unwrap();
+            }
+        }
+        let files = || {
+            WalkDir::new(root.path())
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_type().is_file())
+        };
+        // Warm the directory cache so the first timing is not penalized.
+        assert_eq!(files().count(), 100_000);
+
+        let started = std::time::Instant::now();
+        let restat = files()
+            .filter_map(|entry| std::fs::metadata(entry.path()).ok())
+            .count();
+        let restat_elapsed = started.elapsed();
+
+        let started = std::time::Instant::now();
+        let reused = files().filter_map(|entry| entry.metadata().ok()).count();
+        let reused_elapsed = started.elapsed();
+
+        let started = std::time::Instant::now();
+        let result = scan(root.path(), &ScanOptions::default());
+        let scan_elapsed = started.elapsed();
+
+        assert_eq!(
+            (restat, reused, result.files.len()),
+            (100_000, 100_000, 100_000)
+        );
+        println!(
+            "std::fs::metadata per file: {restat_elapsed:?}, entry.metadata(): {reused_elapsed:?}, \
+             fs_scan::scan end to end: {scan_elapsed:?}"
+        );
+    }
+
+    #[test]
+    fn find_name_collisions_matches_decomposed_names() {
+        let nfc = "R\u{e9}sum\u{e9}.txt";
+        let nfd = "Re\u{301}sume\u{301}.txt";
+        let collisions = find_name_collisions([nfc, nfd, "other.txt"]);
+        assert_eq!(collisions.len(), 1);
+        assert_eq!(collisions[0].parent, "");
+        assert_eq!(collisions[0].names.len(), 2);
+
+        // Decomposed folder names collide too, and are reported once per folder.
+        let collisions = find_name_collisions(["Caf\u{e9}/a.txt", "Cafe\u{301}/b.txt"]);
+        assert_eq!(collisions.len(), 1);
+        assert!(find_name_collisions(["Cafe\u{301}/a.txt", "Cafe\u{301}/b.txt"]).is_empty());
+    }
+
+    #[test]
+    fn doc_shortcut_id_reads_each_stub_format() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let stub = |name: &str, contents: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, contents).expect("write stub");
+            let size = contents.len() as u64;
+            doc_shortcut_id(&path, size)
+        };
+        assert_eq!(
+            stub(
+                "a.gdoc",
+                r#"{"doc_id":"abc_-1","resource_id":"document:other"}"#
+            )
+            .as_deref(),
+            Some("abc_-1")
+        );
+        assert_eq!(
+            stub("b.GSHEET", r#"{"resource_id":"spreadsheet:sheet1"}"#).as_deref(),
+            Some("sheet1")
+        );
+        assert_eq!(
+            stub(
+                "c.gslides",
+                r#"{"url":"https://docs.google.com/presentation/d/deck9/edit?usp=drive_web"}"#
+            )
+            .as_deref(),
+            Some("deck9")
+        );
+        assert!(stub("d.gdoc", r#"{"doc_id":"bad id"}"#).is_none());
+        assert!(stub("e.gdoc", "not json").is_none());
+        assert!(stub("f.json", r#"{"doc_id":"abc"}"#).is_none());
+
+        let path = dir.path().join("a.gdoc");
+        assert!(doc_shortcut_id(&path, MAX_DOC_SHORTCUT_BYTES + 1).is_none());
+    }
+}
@@ -0,0 +1,85 @@
+use serde::Serialize;
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Manager};
+
+// Events with these prefixes are mirrored to stdout once headless mode is on.
+const HEADLESS_EVENT_PREFIXES: [&str; 3] = ["upload:", "schedule:", "recurring:"];
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// Returns the --job value when the app was launched with --headless.
+// `--job -` reads the job definition from stdin.
+pub fn parse_headless_args(args: &[String]) -> Option<Result<String, String>> {
+    if !args.iter().any(|arg| arg == "--headless") {
+        return None;
+    }
+    let job = args
+        .iter()
+        .position(|arg| arg == "--job")
+        .and_then(|idx| args.get(idx + 1))
+        .cloned()
+        .ok_or_else(|| "--headless requires --job <path>".to_string());
+    Some(job)
+}
+
+fn read_job(source: &str) -> Result<crate::StartUploadArgs, String> {
+    let contents = if source == "-" {
+        let mut contents = String::new();
+        std::io::stdin()
+            .read_to_string(&mut contents)
+            .map_err(|e| format!("Failed to read job from stdin: {e}"))?;
+        contents
+    } else {
+        std::fs::read_to_string(source).map_err(|e| format!("Failed to read job file: {e}"))?
+    };
+    serde_json::from_str(&contents).map_err(|e| format!("Invalid job JSON: {e}"))
+}
+
+fn print_event(event: &str, data: impl Serialize) {
+    let data = serde_json::to_value(data).unwrap_or(serde_json::Value::Null);
+    let line = serde_json::json!({ "event": event, "data": data });
+    let mut stdout = std::io::stdout().lock();
+    let _ = writeln!(stdout, "{line}");
+    let _ = stdout.flush();
+}
+
+pub fn forward(event: &str, payload: &impl Serialize) {
+    if ENABLED.load(Ordering::Relaxed)
+        && HEADLESS_EVENT_PREFIXES
+            .iter()
+            .any(|prefix| event.starts_with(prefix))
+    {
+        print_event(event, payload);
+    }
+}
+
+// Runs one job without the UI and exits the process: 0 when every item succeeded,
+// 1 otherwise. Upload events are mirrored to stdout as JSON lines.
+pub fn run(app: &AppHandle, job_source: Result<String, String>) {
+    let job = match job_source.and_then(|source| read_job(&source)) {
+        Ok(job) => job,
+        Err(e) => {
+            print_event("headless:error", &e);
+            std::process::exit(1);
+        }
+    };
+
+    ENABLED.store(true, Ordering::Relaxed);
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<crate::UploadControlState>();
+        let summary =
+            match crate::start_upload_job(&app, &state, job, crate::RunningJob::Cancel).await {
+                Ok(run) => run.await.ok().flatten(),
+                Err(e) => {
+                    print_event("headless:error", &e);
+                    None
+                }
+            };
+        let success = summary.is_some_and(|summary| summary.failed == 0);
+        log::info!("Headless upload finished success={success}");
+        std::process::exit(if success { 0 } else { 1 });
+    });
+}
@@ -0,0 +1,90 @@
+use crate::upload::events::now_ms;
+use serde::{de::DeserializeOwned, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+// Small JSON files in the app data directory, each holding one piece of state. `what` names
+// the state in error messages, e.g. "queue templates".
+
+pub fn state_path(app: &AppHandle, file_name: &str) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    std::fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {e}"))?;
+    Ok(app_data_dir.join(file_name))
+}
+
+// A missing file is the default state. One that cannot be parsed is moved aside rather than
+// overwritten, so the next save does not destroy whatever it still holds.
+pub fn load<T: DeserializeOwned + Default>(path: &Path, what: &str) -> Result<T, String> {
+    let contents = match std::fs::read(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(T::default()),
+        Err(e) => return Err(format!("Failed to read {what}: {e}")),
+    };
+    match serde_json::from_slice(&contents) {
+        Ok(state) => Ok(state),
+        Err(e) => {
+            let mut corrupt_path = path.as_os_str().to_owned();
+            corrupt_path.push(format!(".corrupt-{}", now_ms()));
+            std::fs::rename(path, &corrupt_path)
+                .map_err(|e| format!("Failed to move corrupt {what} aside: {e}"))?;
+            log::warn!("Moved unparseable {what} ({e}) to {corrupt_path:?}");
+            Ok(T::default())
+        }
+    }
+}
+
+pub fn save<T: Serialize>(path: &Path, state: &T, what: &str) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(state)
+        .map_err(|e| format!("Failed to serialize {what}: {e}"))?;
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, contents).map_err(|e| format!("Failed to write {what}: {e}"))?;
+    std::fs::rename(&temp_path, path).map_err(|e| format!("Failed to finalize {what}: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_moves_an_unparseable_file_aside() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("state.json");
+        let missing: Vec<u32> = load(&path, "state").expect("missing file");
+        assert!(missing.is_empty());
+
+        std::fs::write(&path, b"[1, 2").expect("write file");
+        let corrupt: Vec<u32> = load(&path, "state").expect("corrupt file");
+        assert!(corrupt.is_empty());
+        assert!(!path.exists());
+        let kept: Vec<_> = std::fs::read_dir(dir.path())
+            .expect("read dir")
+            .map(|entry| {
+                entry
+                    .expect("entry")
+                    .file_name()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        assert_eq!(kept.len(), 1);
+        assert!(kept[0].starts_with("state.json.corrupt-"), "{kept:?}");
+        assert_eq!(
+            std::fs::read(dir.path().join(&kept[0])).expect("read"),
+            b"[1, 2"
+        );
+    }
+
+    #[test]
+    fn save_round_trips_through_load() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("state.json");
+        save(&path, &vec![1_u32, 2, 3], "state").expect("save");
+        let loaded: Vec<u32> = load(&path, "state").expect("load");
+        assert_eq!(loaded, [1, 2, 3]);
+        assert!(!path.with_extension("tmp").exists());
+    }
+}
@@ -1,23 +1,106 @@
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashSet;
-use std::path::PathBuf;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::menu::{MenuBuilder, MenuItemBuilder, PredefinedMenuItem, SubmenuBuilder};
 use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_deep_link::DeepLinkExt;
 
+mod deep_link;
+mod fs_scan;
+mod headless;
+mod json_state;
+mod queue_templates;
 mod rclone_tools;
+mod recent_destinations;
+mod updates;
 mod upload;
+
+// Sent with every HTTP request the app makes itself, so remote logs identify the build.
+pub const USER_AGENT: &str = concat!("GDExplorer/", env!("CARGO_PKG_VERSION"));
+
 #[derive(Default)]
 struct UploadControlState(tokio::sync::Mutex<Option<UploadControl>>);
 
+// Pending and fired scheduled uploads keyed by schedule id.
+#[derive(Default)]
+struct ScheduledJobsState(tokio::sync::Mutex<HashMap<String, ScheduledJob>>);
+
+struct ScheduledJob {
+    info: ScheduledJobInfo,
+    handle: tokio::task::JoinHandle<()>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScheduledJobInfo {
+    schedule_id: String,
+    run_at_unix: u64,
+    label: Option<String>,
+    // "scheduled" until the timer fires, then "waiting" while another upload holds the slot.
+    // The entry is dropped once its run has started or failed to start.
+    status: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScheduleTriggeredEvent {
+    schedule_id: String,
+}
+
+// Recurring uploads keyed by job id; each entry owns the loop that starts the runs.
+#[derive(Default)]
+struct RecurringJobsState(tokio::sync::Mutex<HashMap<String, RecurringJobHandle>>);
+
+struct RecurringJobHandle {
+    info: RecurringJobInfo,
+    handle: tokio::task::JoinHandle<()>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RecurringJobInfo {
+    job_id: String,
+    interval_hours: f32,
+    max_runs: Option<u32>,
+    runs_completed: u32,
+    next_run_at_unix: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RecurringRunCompletedEvent {
+    job_id: String,
+    run_number: u32,
+    summary: upload::events::Summary,
+}
+
+// Deep link received at launch, held until the frontend is ready to ask for it.
+#[derive(Default)]
+struct DeepLinkState(std::sync::Mutex<Option<deep_link::DeepLinkAction>>);
+
+// Latest saved preferences; running upload jobs subscribe to pick up live-safe changes.
+struct PreferencesState(tokio::sync::watch::Sender<AppPreferences>);
+
+impl Default for PreferencesState {
+    fn default() -> Self {
+        let (tx, _rx) = tokio::sync::watch::channel(AppPreferences::default());
+        Self(tx)
+    }
+}
+
 #[derive(Clone)]
 struct UploadControl {
     cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
     pause_tx: tokio::sync::watch::Sender<bool>,
     paused_items_tx: tokio::sync::watch::Sender<HashSet<String>>,
     canceled_items_tx: tokio::sync::watch::Sender<HashSet<String>>,
+    reload_sa_tx: tokio::sync::watch::Sender<u64>,
+    // Flips to true once the job task has returned.
+    finished_tx: tokio::sync::watch::Sender<bool>,
 }
 
 impl UploadControl {
@@ -25,14 +108,22 @@ impl UploadControl {
         let (pause_tx, _pause_rx) = tokio::sync::watch::channel(false);
         let (paused_items_tx, _paused_items_rx) = tokio::sync::watch::channel(HashSet::new());
         let (canceled_items_tx, _canceled_items_rx) = tokio::sync::watch::channel(HashSet::new());
+        let (reload_sa_tx, _reload_sa_rx) = tokio::sync::watch::channel(0_u64);
+        let (finished_tx, _finished_rx) = tokio::sync::watch::channel(false);
         Self {
             cancel: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
             pause_tx,
             paused_items_tx,
             canceled_items_tx,
+            reload_sa_tx,
+            finished_tx,
         }
     }
 
+    fn is_running(&self) -> bool {
+        !*self.finished_tx.borrow()
+    }
+
     fn cancel(&self) {
         self.cancel
             .store(true, std::sync::atomic::Ordering::Relaxed);
@@ -72,12 +163,17 @@ impl UploadControl {
         let _ = self.canceled_items_tx.send(next);
     }
 
+    fn reload_service_accounts(&self) {
+        self.reload_sa_tx.send_modify(|generation| *generation += 1);
+    }
+
     fn handle(&self) -> upload::scheduler::UploadControlHandle {
         upload::scheduler::UploadControlHandle {
             cancel: self.cancel.clone(),
             pause_rx: self.pause_tx.subscribe(),
             paused_items_rx: self.paused_items_tx.subscribe(),
             canceled_items_rx: self.canceled_items_tx.subscribe(),
+            reload_sa_rx: self.reload_sa_tx.subscribe(),
         }
     }
 }
@@ -107,6 +203,9 @@ struct FileListEntry {
 struct StartUploadArgs {
     queue_items: Vec<upload::scheduler::QueueItemInput>,
     destination_folder_id: String,
+    // Preset the destination was picked from; its rclone remote overrides the global one.
+    #[serde(default)]
+    destination_preset_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -128,7 +227,30 @@ async fn start_upload(
     state: State<'_, UploadControlState>,
     args: StartUploadArgs,
 ) -> Result<(), String> {
-    let app = window.app_handle();
+    start_upload_job(window.app_handle(), &state, args, RunningJob::Cancel).await?;
+    Ok(())
+}
+
+const UPLOAD_ALREADY_RUNNING: &str = "Another upload is already running.";
+
+// What to do with a job that is still running when a new one starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunningJob {
+    // Manual starts replace it, as they always have.
+    Cancel,
+    // Unattended starts fail with UPLOAD_ALREADY_RUNNING instead.
+    Keep,
+}
+
+// Starts the job in the background; the returned handle resolves to the job summary,
+// or None if the job failed before finishing.
+async fn start_upload_job(
+    app: &AppHandle,
+    state: &UploadControlState,
+    args: StartUploadArgs,
+    running: RunningJob,
+) -> Result<tokio::task::JoinHandle<Option<upload::events::Summary>>, String> {
+    upload::orphans::ensure_none_pending(app)?;
     let preferences = load_preferences(app.clone()).await?;
 
     let service_account_folder = preferences
@@ -138,53 +260,438 @@ async fn start_upload(
 
     let max_concurrent = preferences.max_concurrent_uploads;
 
-    let queue_items = args.queue_items;
-    let destination_folder_id = args.destination_folder_id;
+    let mut queue_items = args.queue_items;
+    validate_queue_items(&mut queue_items)?;
 
-    // Cancel any existing upload job (best-effort).
-    {
-        let mut guard = state.0.lock().await;
-        if let Some(existing) = guard.take() {
-            existing.cancel();
-        }
+    let destination_folder_id = args.destination_folder_id;
+    let remote_override = args
+        .destination_preset_id
+        .as_ref()
+        .and_then(|id| preferences.destination_presets.iter().find(|p| &p.id == id))
+        .and_then(|preset| preset.rclone_remote_name.clone());
+    let remote_name = remote_override
+        .clone()
+        .unwrap_or_else(|| preferences.rclone_remote_name.clone());
+    let rclone_path = preferences.rclone_path.clone();
+    let remotes =
+        tokio::task::spawn_blocking(move || rclone_tools::rclone_remote_names(&rclone_path))
+            .await
+            .map_err(|e| format!("Failed to read rclone config: {e}"))??;
+    if !remotes.contains(&remote_name) {
+        return Err(format!(
+            "rclone remote {remote_name:?} is not configured. Set it up in Preferences first."
+        ));
     }
+    // The binary and remote the job started with stay in effect when the saved preferences
+    // change mid-job, so its folder lookups and copies never end up on different remotes.
+    let job_rclone_path = preferences.rclone_path.clone();
+    let job_remote_name = remote_name.clone();
+    let job_rclone_preferences = move |preferences: &AppPreferences| {
+        let mut rclone = rclone_preferences_from(preferences);
+        rclone.rclone_path = job_rclone_path.clone();
+        rclone.remote_name = job_remote_name.clone();
+        rclone
+    };
 
-    // Create a new upload control handle for this run.
+    let preset_name = args
+        .destination_preset_id
+        .as_ref()
+        .and_then(|id| preferences.destination_presets.iter().find(|p| &p.id == id))
+        .map(|preset| preset.name.clone());
+    // Check for a running job and register this run under one lock, so an unattended start
+    // can never slip in front of (or cancel) a job that started in between.
     let control = UploadControl::new();
     let control_handle = control.handle();
+    let finished_tx = control.finished_tx.clone();
     {
         let mut guard = state.0.lock().await;
+        if let Some(existing) = guard.as_ref().filter(|existing| existing.is_running()) {
+            match running {
+                RunningJob::Cancel => existing.cancel(),
+                RunningJob::Keep => return Err(UPLOAD_ALREADY_RUNNING.to_string()),
+            }
+        }
         *guard = Some(control);
     }
 
-    let app_for_task = app.clone();
+    // Only starts that actually got the job slot count as a use of the destination.
+    if let Err(e) = recent_destinations::record(app, &destination_folder_id, preset_name) {
+        log::warn!("Failed to record recent destination: {e}");
+    }
+
+    let (live_prefs_tx, live_prefs_rx) =
+        tokio::sync::watch::channel(job_rclone_preferences(&preferences));
+    let mut saved_prefs_rx = app.state::<PreferencesState>().0.subscribe();
     tokio::spawn(async move {
-        let prefs = upload::rclone::RclonePreferences {
-            rclone_path: preferences.rclone_path,
-            remote_name: preferences.rclone_remote_name,
-            drive_chunk_size_mib: preferences.upload_chunk_size_mib,
-            transfers: preferences.rclone_transfers,
-            checkers: preferences.rclone_checkers,
-        };
+        loop {
+            tokio::select! {
+                changed = saved_prefs_rx.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                    let next = job_rclone_preferences(&saved_prefs_rx.borrow_and_update());
+                    live_prefs_tx.send_if_modified(|current| {
+                        if *current == next {
+                            return false;
+                        }
+                        log::info!("Applying updated rclone preferences to the running upload job");
+                        *current = next;
+                        true
+                    });
+                }
+                _ = live_prefs_tx.closed() => break,
+            }
+        }
+    });
 
-        if let Err(e) = upload::rclone::run_rclone_job(
+    let app_for_task = app.clone();
+    Ok(tokio::spawn(async move {
+        let summary = upload::rclone::run_rclone_job(
             app_for_task,
+            std::sync::Arc::new(upload::runner::SystemRcloneRunner),
             control_handle,
-            prefs,
+            live_prefs_rx,
             max_concurrent,
             service_account_folder,
             queue_items,
             destination_folder_id,
         )
         .await
-        {
-            log::error!("Upload job failed: {e}");
+        .map_err(|e| log::error!("Upload job failed: {e}"))
+        .ok();
+        let _ = finished_tx.send(true);
+        summary
+    }))
+}
+
+// Starts an unattended run once the current job (if any) has finished. The running job is
+// never cancelled; if the user starts another one meanwhile, this keeps waiting behind it.
+async fn start_upload_job_when_idle(
+    app: &AppHandle,
+    state: &UploadControlState,
+    args: StartUploadArgs,
+) -> Result<tokio::task::JoinHandle<Option<upload::events::Summary>>, String> {
+    loop {
+        let finished_rx = state
+            .0
+            .lock()
+            .await
+            .as_ref()
+            .filter(|existing| existing.is_running())
+            .map(|existing| existing.finished_tx.subscribe());
+        if let Some(mut finished_rx) = finished_rx {
+            let _ = finished_rx.wait_for(|finished| *finished).await;
+            continue;
+        }
+        match start_upload_job(app, state, args.clone(), RunningJob::Keep).await {
+            Err(e) if e == UPLOAD_ALREADY_RUNNING => continue,
+            result => return result,
+        }
+    }
+}
+
+fn validate_queue_items(items: &mut [upload::scheduler::QueueItemInput]) -> Result<(), String> {
+    for item in items {
+        if let Some(sa_override_path) = item.sa_override_path.as_ref() {
+            validate_sa_override_path(sa_override_path)?;
+        }
+        if let Some(transfers) = item.transfers {
+            validate_rclone_transfers(transfers)
+                .map_err(|e| format!("Invalid transfers override for {}: {e}", item.path))?;
+        }
+        if item.bwlimit_kib == Some(0) {
+            return Err(format!(
+                "Invalid bandwidth limit for {}: must be at least 1 KiB/s",
+                item.path
+            ));
+        }
+        if let Some(dest_path) = item.dest_path.as_ref() {
+            let normalized = upload::scheduler::normalize_dest_path(dest_path)
+                .map_err(|e| format!("Invalid destination path for {}: {e}", item.path))?;
+            item.dest_path = Some(normalized);
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn schedule_upload(
+    app: AppHandle,
+    state: State<'_, ScheduledJobsState>,
+    mut job: StartUploadArgs,
+    run_at_unix: u64,
+    label: Option<String>,
+) -> Result<String, String> {
+    if let Some(label) = label.as_ref() {
+        validate_string_input(label, 200, "Schedule label")?;
+    }
+    // Catch bad items now rather than when the timer fires unattended.
+    validate_queue_items(&mut job.queue_items)?;
+
+    let now_unix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Failed to read system time: {e}"))?
+        .as_secs();
+    let delay = std::time::Duration::from_secs(run_at_unix.saturating_sub(now_unix));
+    let schedule_id = format!("schedule-{run_at_unix}-{}", upload::events::now_ms());
+
+    // Held until the entry is in place, so a timer that fires at once cannot prune it first.
+    let mut scheduled_jobs = state.0.lock().await;
+    let task_id = schedule_id.clone();
+    let task_app = app.clone();
+    let handle = tokio::spawn(async move {
+        tokio::time::sleep(delay).await;
+        log::info!("Scheduled upload {task_id} triggered");
+        upload::events::emit(
+            &task_app,
+            "schedule:job_triggered",
+            ScheduleTriggeredEvent {
+                schedule_id: task_id.clone(),
+            },
+        );
+
+        let upload_state = task_app.state::<UploadControlState>();
+        let busy = upload_state
+            .0
+            .lock()
+            .await
+            .as_ref()
+            .is_some_and(UploadControl::is_running);
+        if busy {
+            log::info!("Scheduled upload {task_id} is waiting for the running upload to finish");
+            let scheduled = task_app.state::<ScheduledJobsState>();
+            let mut jobs = scheduled.0.lock().await;
+            if let Some(entry) = jobs.get_mut(&task_id) {
+                entry.info.status = "waiting".to_string();
+            }
+        }
+        if let Err(e) = start_upload_job_when_idle(&task_app, &upload_state, job).await {
+            log::error!("Scheduled upload {task_id} failed to start: {e}");
+        }
+        let scheduled = task_app.state::<ScheduledJobsState>();
+        scheduled.0.lock().await.remove(&task_id);
+    });
+
+    log::info!(
+        "Scheduled upload {schedule_id} for {run_at_unix} ({}s from now)",
+        delay.as_secs()
+    );
+    scheduled_jobs.insert(
+        schedule_id.clone(),
+        ScheduledJob {
+            info: ScheduledJobInfo {
+                schedule_id: schedule_id.clone(),
+                run_at_unix,
+                label,
+                status: "scheduled".to_string(),
+            },
+            handle,
+        },
+    );
+    Ok(schedule_id)
+}
+
+#[tauri::command]
+async fn cancel_scheduled_upload(
+    state: State<'_, ScheduledJobsState>,
+    schedule_id: String,
+) -> Result<(), String> {
+    let job = state
+        .0
+        .lock()
+        .await
+        .remove(&schedule_id)
+        .ok_or_else(|| format!("Scheduled upload not found: {schedule_id}"))?;
+    // Once fired the job belongs to the regular upload controls; this only drops the entry.
+    job.handle.abort();
+    Ok(())
+}
+
+#[tauri::command]
+async fn list_scheduled_uploads(
+    state: State<'_, ScheduledJobsState>,
+) -> Result<Vec<ScheduledJobInfo>, String> {
+    let mut jobs = state
+        .0
+        .lock()
+        .await
+        .values()
+        .map(|job| job.info.clone())
+        .collect::<Vec<_>>();
+    jobs.sort_by_key(|job| job.run_at_unix);
+    Ok(jobs)
+}
+
+#[tauri::command]
+async fn start_recurring_upload(
+    app: AppHandle,
+    state: State<'_, RecurringJobsState>,
+    mut job: StartUploadArgs,
+    interval_hours: f32,
+    max_runs: Option<u32>,
+) -> Result<String, String> {
+    if !(0.1..=168.0).contains(&interval_hours) {
+        return Err("Invalid interval: must be between 0.1 and 168 hours".to_string());
+    }
+    if max_runs == Some(0) {
+        return Err("Invalid max runs: must be at least 1".to_string());
+    }
+    validate_queue_items(&mut job.queue_items)?;
+
+    let job_id = format!("recurring-{}", upload::events::now_ms());
+    let interval = std::time::Duration::from_secs_f32(interval_hours * 3600.0);
+
+    let task_id = job_id.clone();
+    let task_app = app.clone();
+    let handle = tokio::spawn(async move {
+        let recurring = task_app.state::<RecurringJobsState>();
+        let mut run_number = 0_u32;
+        while max_runs.is_none_or(|max| run_number < max) {
+            let next_run_at = SystemTime::now() + interval;
+            if let Some(entry) = recurring.0.lock().await.get_mut(&task_id) {
+                entry.info.next_run_at_unix = next_run_at
+                    .duration_since(UNIX_EPOCH)
+                    .ok()
+                    .map(|d| d.as_secs());
+            }
+            tokio::time::sleep(interval).await;
+
+            run_number += 1;
+            log::info!("Recurring upload {task_id} starting run {run_number}");
+            let upload_state = task_app.state::<UploadControlState>();
+            // A run that comes due while another upload is going waits for it to finish.
+            let summary = match start_upload_job_when_idle(&task_app, &upload_state, job.clone())
+                .await
+            {
+                Ok(run) => run.await.ok().flatten(),
+                Err(e) => {
+                    log::error!("Recurring upload {task_id} run {run_number} failed to start: {e}");
+                    None
+                }
+            };
+
+            if let Some(entry) = recurring.0.lock().await.get_mut(&task_id) {
+                entry.info.runs_completed = run_number;
+            }
+            if let Some(summary) = summary {
+                upload::events::emit(
+                    &task_app,
+                    "recurring:run_completed",
+                    RecurringRunCompletedEvent {
+                        job_id: task_id.clone(),
+                        run_number,
+                        summary,
+                    },
+                );
+            }
+        }
+
+        log::info!("Recurring upload {task_id} finished after {run_number} run(s)");
+        let mut jobs = recurring.0.lock().await;
+        if let Some(entry) = jobs.get_mut(&task_id) {
+            entry.info.next_run_at_unix = None;
         }
     });
 
+    log::info!("Recurring upload {job_id} every {interval_hours}h (max runs: {max_runs:?})");
+    state.0.lock().await.insert(
+        job_id.clone(),
+        RecurringJobHandle {
+            info: RecurringJobInfo {
+                job_id: job_id.clone(),
+                interval_hours,
+                max_runs,
+                runs_completed: 0,
+                next_run_at_unix: None,
+            },
+            handle,
+        },
+    );
+    Ok(job_id)
+}
+
+#[tauri::command]
+async fn stop_recurring_upload(
+    state: State<'_, RecurringJobsState>,
+    job_id: String,
+) -> Result<(), String> {
+    let job = state
+        .0
+        .lock()
+        .await
+        .remove(&job_id)
+        .ok_or_else(|| format!("Recurring upload not found: {job_id}"))?;
+    // A run already in progress keeps going under the regular upload controls.
+    job.handle.abort();
     Ok(())
 }
 
+#[tauri::command]
+async fn list_recurring_uploads(
+    state: State<'_, RecurringJobsState>,
+) -> Result<Vec<RecurringJobInfo>, String> {
+    let mut jobs = state
+        .0
+        .lock()
+        .await
+        .values()
+        .map(|job| job.info.clone())
+        .collect::<Vec<_>>();
+    jobs.sort_by(|a, b| a.job_id.cmp(&b.job_id));
+    Ok(jobs)
+}
+
+fn handle_deep_link(app: &AppHandle, url: &str) {
+    let action = match deep_link::parse_deep_link_url(url) {
+        Ok(action) => action,
+        Err(e) => {
+            log::warn!("Ignoring deep link {url}: {e}");
+            return;
+        }
+    };
+    log::info!("Received deep link: {action:?}");
+
+    let state = app.state::<DeepLinkState>();
+    *state.0.lock().unwrap_or_else(|e| e.into_inner()) = Some(action.clone());
+    if let Err(e) = app.emit(
+        "deep_link:upload_requested",
+        deep_link::DeepLinkUploadRequestedEvent { action },
+    ) {
+        log::error!("Failed to emit deep_link:upload_requested event: {e}");
+    }
+}
+
+// Links that arrive before the webview listens are only reachable through this command.
+#[tauri::command]
+async fn take_pending_deep_link(
+    state: State<'_, DeepLinkState>,
+) -> Result<Option<deep_link::DeepLinkAction>, String> {
+    Ok(state.0.lock().unwrap_or_else(|e| e.into_inner()).take())
+}
+
+fn rclone_preferences_from(preferences: &AppPreferences) -> upload::rclone::RclonePreferences {
+    upload::rclone::RclonePreferences {
+        rclone_path: preferences.rclone_path.clone(),
+        remote_name: preferences.rclone_remote_name.clone(),
+        drive_chunk_size_mib: preferences.upload_chunk_size_mib,
+        transfers: preferences.rclone_transfers,
+        checkers: preferences.rclone_checkers,
+        notify_per_item_completion: preferences.notify_per_item_completion,
+        fail_on_unreadable: preferences.fail_on_unreadable,
+        no_traverse: preferences.rclone_no_traverse,
+        auto_no_traverse_threshold_files: preferences.rclone_auto_no_traverse_threshold_files,
+        use_mmap: preferences.rclone_use_mmap,
+        buffer_size_mib: preferences.rclone_buffer_size_mib,
+        folder_upload_strategy: preferences.folder_upload_strategy,
+        stall_timeout_minutes: preferences.stall_timeout_minutes,
+        log_level: preferences.rclone_log_level.clone(),
+        name_collision_policy: preferences.name_collision_policy,
+        sa_selection_strategy: preferences.sa_selection_strategy,
+        doc_shortcut_policy: preferences.doc_shortcut_policy,
+        create_empty_folders: preferences.create_empty_folders,
+    }
+}
+
 #[tauri::command]
 async fn pause_upload(state: State<'_, UploadControlState>, paused: bool) -> Result<(), String> {
     let guard = state.0.lock().await;
@@ -221,6 +728,16 @@ async fn cancel_items(
     Ok(())
 }
 
+#[tauri::command]
+async fn reload_service_accounts(state: State<'_, UploadControlState>) -> Result<(), String> {
+    let guard = state.0.lock().await;
+    let Some(control) = guard.as_ref() else {
+        return Ok(());
+    };
+    control.reload_service_accounts();
+    Ok(())
+}
+
 #[tauri::command]
 async fn cancel_upload(state: State<'_, UploadControlState>) -> Result<(), String> {
     let mut guard = state.0.lock().await;
@@ -230,42 +747,352 @@ async fn cancel_upload(state: State<'_, UploadControlState>) -> Result<(), Strin
     Ok(())
 }
 
+#[tauri::command]
+async fn check_for_updates(app: AppHandle, channel: String) -> Result<updates::UpdateInfo, String> {
+    validate_update_channel(&channel)?;
+    updates::check(&app, &channel).await
+}
+
+// Installs the update found by the last check_for_updates call, emitting
+// update:download_progress while it downloads.
+#[tauri::command]
+async fn download_and_install_update(app: AppHandle) -> Result<(), String> {
+    updates::download_and_install(&app).await
+}
+
+// Returns the taskbar from its error state once the user has seen the failure.
+#[tauri::command]
+fn acknowledge_upload_errors(app: AppHandle) {
+    upload::taskbar::acknowledge_error(&app);
+}
+
+// rclone processes from a crashed session that are still running. The webview asks on mount;
+// orphaned_uploads_detected is only sent when one of them blocks a new job.
+#[tauri::command]
+fn list_orphaned_uploads() -> Vec<upload::orphans::TrackedProcess> {
+    upload::orphans::orphans()
+}
+
+#[tauri::command]
+fn kill_orphaned_uploads() -> u32 {
+    upload::orphans::kill_orphans()
+}
+
+#[tauri::command]
+fn dismiss_orphaned_uploads() {
+    upload::orphans::dismiss_orphans()
+}
+
+// Compares total_bytes with what the configured service accounts can still upload today,
+// with per-account detail.
+#[tauri::command]
+async fn estimate_job_feasibility(
+    app: AppHandle,
+    total_bytes: u64,
+) -> Result<upload::quota::JobFeasibility, String> {
+    let preferences = load_preferences(app).await?;
+    let service_account_folder = preferences
+        .service_account_folder_path
+        .ok_or_else(|| "Service Account folder path is not set in Preferences.".to_string())?;
+    upload::rclone::estimate_job_feasibility(&service_account_folder, total_bytes)
+}
+
+// Last 10 destination folders uploads were started with, most recent first.
+#[tauri::command]
+async fn get_recent_destinations(
+    app: AppHandle,
+) -> Result<Vec<recent_destinations::RecentDestination>, String> {
+    let preferences = load_preferences(app.clone()).await?;
+    recent_destinations::list(&app, &preferences.destination_presets)
+}
+
+#[tauri::command]
+async fn clear_recent_destinations(app: AppHandle) -> Result<(), String> {
+    recent_destinations::clear(&app)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct QueueTemplateRun {
+    // The items that were started, so the frontend can track them by id.
+    queue_items: Vec<upload::scheduler::QueueItemInput>,
+    // One line per saved item that was left out because its path is gone or changed kind.
+    warnings: Vec<String>,
+}
+
+#[tauri::command]
+async fn save_queue_template(
+    app: AppHandle,
+    name: String,
+    mut queue_items: Vec<upload::scheduler::QueueItemInput>,
+    destination_folder_id: String,
+) -> Result<queue_templates::QueueTemplate, String> {
+    queue_templates::validate_name(&name)?;
+    validate_string_input(&destination_folder_id, 200, "Destination folder ID")?;
+    if queue_items.is_empty() {
+        return Err("A queue template needs at least one item".to_string());
+    }
+    validate_queue_items(&mut queue_items)?;
+    queue_templates::save_template(&app, &name, queue_items, destination_folder_id)
+}
+
+#[tauri::command]
+async fn list_queue_templates(
+    app: AppHandle,
+) -> Result<Vec<queue_templates::QueueTemplate>, String> {
+    queue_templates::list(&app)
+}
+
+#[tauri::command]
+async fn delete_queue_template(app: AppHandle, template_id: String) -> Result<(), String> {
+    queue_templates::delete(&app, &template_id)
+}
+
+#[tauri::command]
+async fn run_queue_template(
+    app: AppHandle,
+    state: State<'_, UploadControlState>,
+    template_id: String,
+) -> Result<QueueTemplateRun, String> {
+    let template = queue_templates::get(&app, &template_id)?;
+    let (queue_items, warnings) = queue_templates::restat(template.queue_items);
+    for warning in &warnings {
+        log::warn!("Queue template {:?} item skipped: {warning}", template.name);
+    }
+    if queue_items.is_empty() {
+        return Err(format!(
+            "None of the paths in queue template {:?} exist any more",
+            template.name
+        ));
+    }
+    start_upload_job(
+        &app,
+        &state,
+        StartUploadArgs {
+            queue_items: queue_items.clone(),
+            destination_folder_id: template.destination_folder_id,
+            destination_preset_id: None,
+        },
+        RunningJob::Cancel,
+    )
+    .await?;
+    Ok(QueueTemplateRun {
+        queue_items,
+        warnings,
+    })
+}
+
+#[tauri::command]
+fn get_upload_metrics() -> upload::metrics::MetricsSnapshot {
+    upload::metrics::metrics().snapshot()
+}
+
+// Path of a job's log file, for attaching to bug reports. The file may not exist if the job
+// never started.
+#[tauri::command]
+fn get_job_log_path(app: AppHandle, job_id: String) -> Result<String, String> {
+    upload::joblog::job_log_path(&app, &job_id).map(|path| path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+fn open_logs_folder(app: AppHandle) -> Result<(), String> {
+    use tauri_plugin_opener::OpenerExt;
+    let dir = upload::joblog::logs_dir(&app)?;
+    app.opener()
+        .open_path(dir.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open logs folder: {e}"))
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AppPaths {
+    app_data_dir: String,
+    preferences_path: String,
+    recovery_dir: String,
+    // Per-job upload logs.
+    job_logs_dir: String,
+    // The application log written by the log plugin.
+    app_log_dir: String,
+    rclone_install_dir: String,
+    recent_destinations_path: String,
+    queue_templates_path: String,
+}
+
+fn app_owned_dirs(app: &AppHandle) -> Result<Vec<PathBuf>, String> {
+    let path = app.path();
+    Ok(vec![
+        path.app_data_dir()
+            .map_err(|e| format!("Failed to get app data directory: {e}"))?,
+        path.app_log_dir()
+            .map_err(|e| format!("Failed to get app log directory: {e}"))?,
+    ])
+}
+
+// Where the app keeps its files, for support requests and bug reports.
+#[tauri::command]
+fn get_app_paths(app: AppHandle) -> Result<AppPaths, String> {
+    let to_string = |path: PathBuf| path.to_string_lossy().to_string();
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    Ok(AppPaths {
+        preferences_path: to_string(get_preferences_path(&app)?),
+        recovery_dir: to_string(get_recovery_dir(&app)?),
+        job_logs_dir: to_string(upload::joblog::logs_dir(&app)?),
+        app_log_dir: to_string(
+            app.path()
+                .app_log_dir()
+                .map_err(|e| format!("Failed to get app log directory: {e}"))?,
+        ),
+        rclone_install_dir: to_string(rclone_tools::rclone_install_dir(&app)?),
+        recent_destinations_path: to_string(app_data_dir.join(recent_destinations::STATE_FILE)),
+        queue_templates_path: to_string(app_data_dir.join(queue_templates::STATE_FILE)),
+        app_data_dir: to_string(app_data_dir),
+    })
+}
+
+// Shows a file or folder in Finder/Explorer. Only paths inside the app's own directories are
+// accepted, so the webview cannot use this to probe the rest of the disk.
+#[tauri::command]
+fn reveal_path(app: AppHandle, path: String) -> Result<(), String> {
+    use tauri_plugin_opener::OpenerExt;
+    validate_string_input(&path, 1024, "Path")?;
+    let target = std::fs::canonicalize(&path).map_err(|e| format!("Cannot reveal {path}: {e}"))?;
+    let allowed = app_owned_dirs(&app)?
+        .into_iter()
+        .filter_map(|dir| std::fs::canonicalize(dir).ok())
+        .any(|dir| target.starts_with(dir));
+    if !allowed {
+        return Err(format!(
+            "Cannot reveal {path}: only the app's own folders can be revealed"
+        ));
+    }
+    app.opener()
+        .reveal_item_in_dir(&target)
+        .map_err(|e| format!("Failed to reveal {path}: {e}"))
+}
+
+// Live view of the running job's service account rotation; None when no job is running.
+#[tauri::command]
+async fn get_sa_pool_stats(
+    state: State<'_, upload::rclone::DrivePoolState>,
+) -> Result<Option<upload::rclone::ServiceAccountPoolStats>, String> {
+    Ok(upload::rclone::service_account_pool_stats(&state).await)
+}
+
+// Lets a reloaded webview rebuild its queue from the backend's view of the job. Without a
+// job id, returns the most recently started job.
+#[tauri::command]
+fn get_job_snapshot(job_id: Option<String>) -> Option<upload::snapshot::JobSnapshot> {
+    upload::snapshot::job_snapshot(job_id.as_deref())
+}
+
+#[tauri::command]
+fn reset_upload_metrics() {
+    log::info!("Resetting upload metrics");
+    upload::metrics::metrics().reset();
+}
+
 #[tauri::command]
 async fn list_item_files(path: String, kind: LocalPathKind) -> Result<Vec<FileListEntry>, String> {
-    let mut files = Vec::new();
     let path_buf = PathBuf::from(&path);
+    let scan = fs_scan::scan(&path_buf, &fs_scan::ScanOptions::default());
+
+    if let (LocalPathKind::File, true) = (&kind, scan.files.is_empty()) {
+        if let Some(warning) = scan.warnings.first() {
+            return Err(format!("Failed to stat file: {}", warning.message));
+        }
+    }
+    for warning in &scan.warnings {
+        log::warn!(
+            "Skipping unreadable entry {}: {}",
+            warning.path,
+            warning.message
+        );
+    }
+
+    Ok(scan
+        .files
+        .into_iter()
+        .map(|file| FileListEntry {
+            file_path: file.path.to_string_lossy().to_string(),
+            total_bytes: file.size,
+        })
+        .collect())
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ItemSummary {
+    file_count: u64,
+    total_bytes: u64,
+    largest_file_bytes: u64,
+    has_symlinks: bool,
+    has_hidden: bool,
+    partial: bool,
+    // Entries the walk could not read; they are not in the counts.
+    unreadable_count: u32,
+    elapsed_ms: u64,
+    // Sibling names that differ only by case or Unicode composition, so the user can pick a
+    // name collision policy before uploading.
+    name_collisions: Vec<fs_scan::NameCollision>,
+}
+
+const SUMMARIZE_TIME_BUDGET: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tauri::command]
+async fn summarize_item(path: String, kind: LocalPathKind) -> Result<ItemSummary, String> {
+    tokio::task::spawn_blocking(move || summarize_path(&PathBuf::from(&path), kind))
+        .await
+        .map_err(|e| format!("Failed to summarize item: {e}"))?
+}
+
+fn summarize_path(path: &std::path::Path, kind: LocalPathKind) -> Result<ItemSummary, String> {
+    let started = std::time::Instant::now();
+    let mut summary = ItemSummary::default();
 
     match kind {
         LocalPathKind::File => {
             let metadata =
-                std::fs::metadata(&path_buf).map_err(|e| format!("Failed to stat file: {e}"))?;
-            files.push(FileListEntry {
-                file_path: path_buf.to_string_lossy().to_string(),
-                total_bytes: metadata.len(),
-            });
+                std::fs::metadata(path).map_err(|e| format!("Failed to stat file: {e}"))?;
+            summary.file_count = 1;
+            summary.total_bytes = metadata.len();
+            summary.largest_file_bytes = metadata.len();
+            summary.has_hidden = is_hidden_name(path.file_name());
         }
         LocalPathKind::Folder => {
-            for entry in walkdir::WalkDir::new(&path_buf)
-                .into_iter()
-                .filter_map(Result::ok)
-            {
-                if !entry.file_type().is_file() {
-                    continue;
-                }
-                let file_path = entry.path().to_path_buf();
-                let metadata = std::fs::metadata(&file_path)
-                    .map_err(|e| format!("Failed to stat file: {e}"))?;
-                files.push(FileListEntry {
-                    file_path: file_path.to_string_lossy().to_string(),
-                    total_bytes: metadata.len(),
+            let scan =
+                fs_scan::scan_with_progress(path, &fs_scan::ScanOptions::default(), |_, _| {
+                    if started.elapsed() > SUMMARIZE_TIME_BUDGET {
+                        std::ops::ControlFlow::Break(())
+                    } else {
+                        std::ops::ControlFlow::Continue(())
+                    }
                 });
-            }
+            let rel_paths = || scan.files.iter().map(|file| file.rel_path.as_str());
+            summary.partial = scan.partial;
+            summary.unreadable_count = scan.warnings.len() as u32;
+            summary.file_count = scan.files.len() as u64;
+            summary.total_bytes = scan.total_bytes();
+            summary.largest_file_bytes = scan.files.iter().map(|file| file.size).max().unwrap_or(0);
+            summary.has_symlinks = scan.skipped_symlinks > 0;
+            summary.has_hidden = rel_paths()
+                .chain(scan.empty_dirs.iter().map(String::as_str))
+                .any(|rel_path| rel_path.split('/').any(|name| name.starts_with('.')));
+            summary.name_collisions = fs_scan::find_name_collisions(rel_paths());
         }
     }
 
-    files.sort_by(|a, b| a.file_path.cmp(&b.file_path));
-    Ok(files)
+    summary.elapsed_ms = started.elapsed().as_millis() as u64;
+    Ok(summary)
 }
+
+fn is_hidden_name(name: Option<&std::ffi::OsStr>) -> bool {
+    name.and_then(|n| n.to_str())
+        .is_some_and(|n| n.starts_with('.'))
+}
+
 // Validation functions
 fn validate_filename(filename: &str) -> Result<(), String> {
     // Regex pattern: only alphanumeric, dash, underscore, dot
@@ -304,6 +1131,31 @@ fn validate_theme(theme: &str) -> Result<(), String> {
     }
 }
 
+fn validate_update_channel(channel: &str) -> Result<(), String> {
+    if updates::UPDATE_CHANNELS.contains(&channel) {
+        Ok(())
+    } else {
+        Err("Invalid update channel: must be 'stable' or 'beta'".to_string())
+    }
+}
+
+fn validate_rclone_log_level(level: &str) -> Result<(), String> {
+    match level {
+        "ERROR" | "NOTICE" | "INFO" | "DEBUG" => Ok(()),
+        _ => Err(format!(
+            "Invalid rclone log level: {level}. Must be ERROR, NOTICE, INFO, or DEBUG"
+        )),
+    }
+}
+
+fn validate_job_log_retention_days(value: u32) -> Result<(), String> {
+    if (1..=365).contains(&value) {
+        Ok(())
+    } else {
+        Err("Invalid job log retention: must be between 1 and 365 days".to_string())
+    }
+}
+
 fn validate_max_concurrent_uploads(value: u8) -> Result<(), String> {
     if (1..=10).contains(&value) {
         Ok(())
@@ -330,10 +1182,14 @@ fn validate_rclone_path(path: &str) -> Result<(), String> {
     Ok(())
 }
 
-fn validate_rclone_remote_name(name: &str) -> Result<(), String> {
+pub(crate) fn validate_rclone_remote_name(name: &str) -> Result<(), String> {
     if name.trim().is_empty() {
         return Err("Invalid rclone remote name: must not be empty".to_string());
     }
+    // rclone would read a leading '-' as a flag rather than the remote name.
+    if name.starts_with('-') {
+        return Err("Invalid rclone remote name: must not start with '-'".to_string());
+    }
     validate_string_input(name, 64, "Rclone remote name")?;
     Ok(())
 }
@@ -354,6 +1210,44 @@ fn validate_rclone_checkers(value: u16) -> Result<(), String> {
     }
 }
 
+fn validate_rclone_auto_no_traverse_threshold_files(value: Option<u32>) -> Result<(), String> {
+    match value {
+        Some(value) if value > 1_000_000 => Err(
+            "Invalid rclone auto no-traverse threshold: must be between 0 and 1000000".to_string(),
+        ),
+        _ => Ok(()),
+    }
+}
+
+fn validate_rclone_buffer_size_mib(value: u32) -> Result<(), String> {
+    if value <= 2048 {
+        Ok(())
+    } else {
+        Err("Invalid rclone buffer size: must be between 0 and 2048 MiB".to_string())
+    }
+}
+
+fn validate_stall_timeout_minutes(value: u32) -> Result<(), String> {
+    if value <= 1440 {
+        Ok(())
+    } else {
+        Err("Invalid stall timeout: must be between 0 and 1440 minutes".to_string())
+    }
+}
+
+fn validate_drive_folder_id(id: &str) -> Result<(), String> {
+    let valid = !id.is_empty()
+        && id.len() <= 128
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid Drive folder id: {id:?}"))
+    }
+}
+
 fn validate_service_account_json_path(path: &Option<String>) -> Result<(), String> {
     let Some(path) = path else {
         return Ok(());
@@ -363,9 +1257,22 @@ fn validate_service_account_json_path(path: &Option<String>) -> Result<(), Strin
     Ok(())
 }
 
-fn validate_destination_presets(presets: &[DestinationPreset]) -> Result<(), String> {
-    if presets.len() > 50 {
-        return Err("Too many destination presets (max 50).".to_string());
+const MAX_DESTINATION_PRESETS: usize = 50;
+const MAX_EXTENDED_DESTINATION_PRESETS: usize = 500;
+
+fn max_destination_presets(prefs: &AppPreferences) -> usize {
+    if prefs.extended_presets {
+        MAX_EXTENDED_DESTINATION_PRESETS
+    } else {
+        MAX_DESTINATION_PRESETS
+    }
+}
+
+fn validate_destination_presets(prefs: &AppPreferences) -> Result<(), String> {
+    let presets = &prefs.destination_presets;
+    let max = max_destination_presets(prefs);
+    if presets.len() > max {
+        return Err(format!("Too many destination presets (max {max})."));
     }
     for (i, p) in presets.iter().enumerate() {
         validate_string_input(&p.id, 64, "Destination preset id")?;
@@ -381,10 +1288,130 @@ fn validate_destination_presets(presets: &[DestinationPreset]) -> Result<(), Str
                 "Destination preset URL cannot be empty (index {i})"
             ));
         }
+        if let Some(remote_name) = p.rclone_remote_name.as_ref() {
+            validate_rclone_remote_name(remote_name)?;
+        }
+    }
+    Ok(())
+}
+
+fn validate_default_destination_preset_id(
+    id: &Option<String>,
+    presets: &[DestinationPreset],
+) -> Result<(), String> {
+    let Some(id) = id else {
+        return Ok(());
+    };
+    if presets.iter().any(|p| &p.id == id) {
+        Ok(())
+    } else {
+        Err(format!("Default destination preset {id:?} does not exist"))
+    }
+}
+
+fn validate_sa_override_path(path: &str) -> Result<(), String> {
+    validate_string_input(path, 1024, "Service account override path")?;
+    let metadata = std::fs::metadata(path)
+        .map_err(|e| format!("Invalid service account override {path:?}: {e}"))?;
+    if !metadata.is_file() {
+        return Err(format!(
+            "Invalid service account override {path:?}: not a file"
+        ));
+    }
+    let is_json = std::path::Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    if !is_json {
+        return Err(format!(
+            "Invalid service account override {path:?}: must be a .json file"
+        ));
     }
     Ok(())
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreferenceError {
+    pub field: String,
+    pub message: String,
+}
+
+// Runs every validator and collects all failures instead of stopping at the first one
+fn validate_all_preferences(prefs: &AppPreferences) -> Vec<PreferenceError> {
+    let checks = [
+        ("theme", validate_theme(&prefs.theme)),
+        (
+            "updateChannel",
+            validate_update_channel(&prefs.update_channel),
+        ),
+        (
+            "maxConcurrentUploads",
+            validate_max_concurrent_uploads(prefs.max_concurrent_uploads),
+        ),
+        (
+            "uploadChunkSizeMib",
+            validate_upload_chunk_size_mib(prefs.upload_chunk_size_mib),
+        ),
+        ("rclonePath", validate_rclone_path(&prefs.rclone_path)),
+        (
+            "rcloneRemoteName",
+            validate_rclone_remote_name(&prefs.rclone_remote_name),
+        ),
+        (
+            "rcloneTransfers",
+            validate_rclone_transfers(prefs.rclone_transfers),
+        ),
+        (
+            "rcloneCheckers",
+            validate_rclone_checkers(prefs.rclone_checkers),
+        ),
+        (
+            "rcloneAutoNoTraverseThresholdFiles",
+            validate_rclone_auto_no_traverse_threshold_files(
+                prefs.rclone_auto_no_traverse_threshold_files,
+            ),
+        ),
+        (
+            "rcloneBufferSizeMib",
+            validate_rclone_buffer_size_mib(prefs.rclone_buffer_size_mib),
+        ),
+        (
+            "stallTimeoutMinutes",
+            validate_stall_timeout_minutes(prefs.stall_timeout_minutes),
+        ),
+        (
+            "rcloneLogLevel",
+            validate_rclone_log_level(&prefs.rclone_log_level),
+        ),
+        (
+            "jobLogRetentionDays",
+            validate_job_log_retention_days(prefs.job_log_retention_days),
+        ),
+        (
+            "serviceAccountFolderPath",
+            validate_service_account_json_path(&prefs.service_account_folder_path),
+        ),
+        ("destinationPresets", validate_destination_presets(prefs)),
+        (
+            "defaultDestinationPresetId",
+            validate_default_destination_preset_id(
+                &prefs.default_destination_preset_id,
+                &prefs.destination_presets,
+            ),
+        ),
+    ];
+
+    checks
+        .into_iter()
+        .filter_map(|(field, result)| {
+            result.err().map(|message| PreferenceError {
+                field: field.to_string(),
+                message,
+            })
+        })
+        .collect()
+}
+
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -400,21 +1427,26 @@ fn greet(name: &str) -> String {
 
 // Preferences data structure
 // Only contains settings that should be persisted to disk
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DestinationPreset {
     pub id: String,
     pub name: String,
     pub url: String,
+    // rclone remote for this destination; the global rclone_remote_name when unset.
+    #[serde(default)]
+    pub rclone_remote_name: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(default)]
 pub struct AppPreferences {
     pub theme: String,
     #[serde(default = "default_auto_check_updates")]
     pub auto_check_updates: bool,
+    // Release channel for update checks: "stable" or "beta".
+    pub update_channel: String,
     #[serde(alias = "serviceAccountJsonPath")]
     pub service_account_folder_path: Option<String>,
     pub max_concurrent_uploads: u8,
@@ -428,6 +1460,35 @@ pub struct AppPreferences {
     #[serde(default = "default_rclone_checkers")]
     pub rclone_checkers: u16,
     pub destination_presets: Vec<DestinationPreset>,
+    // Raises the destination preset limit from 50 to 500.
+    pub extended_presets: bool,
+    // Preset whose URL is filled in as the upload destination on launch.
+    pub default_destination_preset_id: Option<String>,
+    pub notify_per_item_completion: bool,
+    pub fail_on_unreadable: bool,
+    // Passes --no-traverse so rclone does not list the destination before copying.
+    // Cheaper for huge destination folders, but rclone no longer sees existing remote
+    // files, which changes sync semantics; leave it off for sync-style uploads.
+    pub rclone_no_traverse: bool,
+    // Enables --no-traverse for items with more files than this, regardless of the flag above.
+    pub rclone_auto_no_traverse_threshold_files: Option<u32>,
+    pub rclone_use_mmap: bool,
+    pub rclone_buffer_size_mib: u32,
+    pub folder_upload_strategy: upload::rclone::FolderUploadStrategy,
+    pub name_collision_policy: upload::rclone::NameCollisionPolicy,
+    pub sa_selection_strategy: upload::rclone::SaSelectionStrategy,
+    // What to do with the .gdoc/.gsheet stubs Drive for Desktop leaves in synced folders.
+    pub doc_shortcut_policy: upload::rclone::DocShortcutPolicy,
+    // Recreates local folders that contain no files on Drive, e.g. placeholders in templates.
+    pub create_empty_folders: bool,
+    // Minutes without any rclone progress before a transfer is restarted; 0 disables.
+    pub stall_timeout_minutes: u32,
+    // rclone's --log-level: ERROR, NOTICE, INFO or DEBUG.
+    pub rclone_log_level: String,
+    // Per-job log files older than this are deleted when the next job starts.
+    pub job_log_retention_days: u32,
+    // Mirrors aggregate upload progress on the taskbar button (Windows) or dock icon (macOS).
+    pub show_taskbar_progress: bool,
 }
 
 impl Default for AppPreferences {
@@ -435,6 +1496,7 @@ impl Default for AppPreferences {
         Self {
             theme: "system".to_string(),
             auto_check_updates: true,
+            update_channel: "stable".to_string(),
             service_account_folder_path: None,
             max_concurrent_uploads: 3,
             upload_chunk_size_mib: 128,
@@ -443,6 +1505,24 @@ impl Default for AppPreferences {
             rclone_transfers: 4,
             rclone_checkers: 8,
             destination_presets: Vec::new(),
+            extended_presets: false,
+            default_destination_preset_id: None,
+            notify_per_item_completion: false,
+            fail_on_unreadable: false,
+            rclone_no_traverse: false,
+            rclone_auto_no_traverse_threshold_files: None,
+            rclone_use_mmap: false,
+            // Matches rclone's own default, so the explicit flag changes nothing out of the box.
+            rclone_buffer_size_mib: 16,
+            folder_upload_strategy: upload::rclone::FolderUploadStrategy::default(),
+            name_collision_policy: upload::rclone::NameCollisionPolicy::default(),
+            sa_selection_strategy: upload::rclone::SaSelectionStrategy::default(),
+            doc_shortcut_policy: upload::rclone::DocShortcutPolicy::default(),
+            create_empty_folders: false,
+            stall_timeout_minutes: 10,
+            rclone_log_level: "INFO".to_string(),
+            job_log_retention_days: 14,
+            show_taskbar_progress: true,
         }
     }
 }
@@ -467,6 +1547,184 @@ fn default_rclone_checkers() -> u16 {
     8
 }
 
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum PreferenceApplyScope {
+    // Picked up by transfers started after the save, including in a running job
+    Live,
+    // Only takes effect when the next upload job starts
+    NextJob,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PreferenceChange {
+    field: String,
+    applies_to: PreferenceApplyScope,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PreferencesChangedEvent {
+    changes: Vec<PreferenceChange>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PreferencesRecoveredEvent {
+    corrupt_path: String,
+    error: String,
+}
+
+fn diff_preferences(old: &AppPreferences, new: &AppPreferences) -> Vec<PreferenceChange> {
+    use PreferenceApplyScope::{Live, NextJob};
+
+    let fields = [
+        ("theme", old.theme != new.theme, Live),
+        (
+            "autoCheckUpdates",
+            old.auto_check_updates != new.auto_check_updates,
+            Live,
+        ),
+        (
+            "updateChannel",
+            old.update_channel != new.update_channel,
+            Live,
+        ),
+        (
+            "serviceAccountFolderPath",
+            old.service_account_folder_path != new.service_account_folder_path,
+            NextJob,
+        ),
+        (
+            "maxConcurrentUploads",
+            old.max_concurrent_uploads != new.max_concurrent_uploads,
+            NextJob,
+        ),
+        (
+            "uploadChunkSizeMib",
+            old.upload_chunk_size_mib != new.upload_chunk_size_mib,
+            Live,
+        ),
+        ("rclonePath", old.rclone_path != new.rclone_path, NextJob),
+        (
+            "rcloneRemoteName",
+            old.rclone_remote_name != new.rclone_remote_name,
+            NextJob,
+        ),
+        (
+            "rcloneTransfers",
+            old.rclone_transfers != new.rclone_transfers,
+            Live,
+        ),
+        (
+            "rcloneCheckers",
+            old.rclone_checkers != new.rclone_checkers,
+            Live,
+        ),
+        (
+            "destinationPresets",
+            old.destination_presets != new.destination_presets,
+            Live,
+        ),
+        (
+            "extendedPresets",
+            old.extended_presets != new.extended_presets,
+            Live,
+        ),
+        (
+            "defaultDestinationPresetId",
+            old.default_destination_preset_id != new.default_destination_preset_id,
+            Live,
+        ),
+        (
+            "notifyPerItemCompletion",
+            old.notify_per_item_completion != new.notify_per_item_completion,
+            Live,
+        ),
+        (
+            "failOnUnreadable",
+            old.fail_on_unreadable != new.fail_on_unreadable,
+            Live,
+        ),
+        (
+            "rcloneNoTraverse",
+            old.rclone_no_traverse != new.rclone_no_traverse,
+            Live,
+        ),
+        (
+            "rcloneAutoNoTraverseThresholdFiles",
+            old.rclone_auto_no_traverse_threshold_files
+                != new.rclone_auto_no_traverse_threshold_files,
+            Live,
+        ),
+        (
+            "rcloneUseMmap",
+            old.rclone_use_mmap != new.rclone_use_mmap,
+            Live,
+        ),
+        (
+            "rcloneBufferSizeMib",
+            old.rclone_buffer_size_mib != new.rclone_buffer_size_mib,
+            Live,
+        ),
+        (
+            "folderUploadStrategy",
+            old.folder_upload_strategy != new.folder_upload_strategy,
+            Live,
+        ),
+        (
+            "nameCollisionPolicy",
+            old.name_collision_policy != new.name_collision_policy,
+            Live,
+        ),
+        (
+            "saSelectionStrategy",
+            old.sa_selection_strategy != new.sa_selection_strategy,
+            Live,
+        ),
+        (
+            "docShortcutPolicy",
+            old.doc_shortcut_policy != new.doc_shortcut_policy,
+            Live,
+        ),
+        (
+            "createEmptyFolders",
+            old.create_empty_folders != new.create_empty_folders,
+            Live,
+        ),
+        (
+            "stallTimeoutMinutes",
+            old.stall_timeout_minutes != new.stall_timeout_minutes,
+            Live,
+        ),
+        (
+            "rcloneLogLevel",
+            old.rclone_log_level != new.rclone_log_level,
+            Live,
+        ),
+        (
+            "jobLogRetentionDays",
+            old.job_log_retention_days != new.job_log_retention_days,
+            Live,
+        ),
+        (
+            "showTaskbarProgress",
+            old.show_taskbar_progress != new.show_taskbar_progress,
+            Live,
+        ),
+    ];
+
+    fields
+        .into_iter()
+        .filter(|(_, changed, _)| *changed)
+        .map(|(field, _, applies_to)| PreferenceChange {
+            field: field.to_string(),
+            applies_to,
+        })
+        .collect()
+}
+
 fn get_preferences_path(app: &AppHandle) -> Result<PathBuf, String> {
     let app_data_dir = app
         .path()
@@ -480,6 +1738,132 @@ fn get_preferences_path(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(app_data_dir.join("preferences.json"))
 }
 
+const PREFERENCES_BACKUP_COUNT: usize = 3;
+
+// Reads a freshly written temp file back before it replaces the live file, so a short write
+// (e.g. a full disk) never gets renamed over good data. The temp file is removed on failure.
+fn verify_written_json<T: serde::de::DeserializeOwned>(temp_path: &Path) -> Result<(), String> {
+    let result = std::fs::read_to_string(temp_path)
+        .map_err(|e| e.to_string())
+        .and_then(|contents| {
+            serde_json::from_str::<T>(&contents)
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        log::error!("Written file {temp_path:?} failed verification: {e}");
+        let _ = std::fs::remove_file(temp_path);
+        return Err(format!("Failed to verify written file: {e}"));
+    }
+    Ok(())
+}
+
+// Replaces `path` through a temp file that is verified first. `what` names the file in errors.
+fn write_verified<T: serde::de::DeserializeOwned>(
+    path: &Path,
+    contents: &[u8],
+    what: &str,
+) -> Result<(), String> {
+    write_verified_with::<T>(path, contents, what, |path, contents| {
+        std::fs::write(path, contents)
+    })
+}
+
+fn write_verified_with<T: serde::de::DeserializeOwned>(
+    path: &Path,
+    contents: &[u8],
+    what: &str,
+    write: impl FnOnce(&Path, &[u8]) -> std::io::Result<()>,
+) -> Result<(), String> {
+    let temp_path = path.with_extension("tmp");
+    write(&temp_path, contents).map_err(|e| {
+        log::error!("Failed to write {what}: {e}");
+        format!("Failed to write {what}: {e}")
+    })?;
+    verify_written_json::<T>(&temp_path)?;
+    std::fs::rename(&temp_path, path).map_err(|e| {
+        log::error!("Failed to finalize {what}: {e}");
+        format!("Failed to finalize {what}: {e}")
+    })
+}
+
+// preferences.json.bak-1 is the most recent backup.
+fn preferences_backup_path(prefs_path: &Path, index: usize) -> PathBuf {
+    let mut name = prefs_path.as_os_str().to_owned();
+    name.push(format!(".bak-{index}"));
+    PathBuf::from(name)
+}
+
+// Shifts the backups down one slot and copies the current file into the first, as long as
+// it still parses; a broken file must not push out a good backup.
+fn rotate_preferences_backups(prefs_path: &Path) {
+    let is_good = std::fs::read(prefs_path)
+        .ok()
+        .is_some_and(|contents| parse_preferences(&contents).is_ok());
+    if !is_good {
+        return;
+    }
+    for index in (1..PREFERENCES_BACKUP_COUNT).rev() {
+        let from = preferences_backup_path(prefs_path, index);
+        if from.exists() {
+            let to = preferences_backup_path(prefs_path, index + 1);
+            if let Err(e) = std::fs::rename(&from, &to) {
+                log::warn!("Failed to rotate preferences backup {from:?}: {e}");
+            }
+        }
+    }
+    if let Err(e) = std::fs::copy(prefs_path, preferences_backup_path(prefs_path, 1)) {
+        log::warn!("Failed to back up preferences: {e}");
+    }
+}
+
+// Keys written by a newer version are dropped with a warning rather than relied on being
+// ignored, so a downgrade keeps working even if parsing ever becomes strict.
+fn drop_unknown_preference_keys(mut raw: Value) -> Value {
+    let Ok(Value::Object(known)) = serde_json::to_value(AppPreferences::default()) else {
+        return raw;
+    };
+    if let Value::Object(fields) = &mut raw {
+        fields.retain(|key, _| {
+            // Accepted through a serde alias.
+            let is_known = known.contains_key(key) || key == "serviceAccountJsonPath";
+            if !is_known {
+                log::warn!("Ignoring unknown preference {key:?}");
+            }
+            is_known
+        });
+    }
+    raw
+}
+
+// Bytes that are not valid UTF-8 (e.g. a write cut off mid-character) fail here like any
+// other malformed JSON, so they count as corruption rather than a read error.
+fn parse_preferences(contents: &[u8]) -> Result<AppPreferences, serde_json::Error> {
+    serde_json::from_slice::<Value>(contents)
+        .and_then(|raw| serde_json::from_value::<AppPreferences>(drop_unknown_preference_keys(raw)))
+}
+
+// Moves an unparseable preferences file aside so the app can start from defaults.
+fn quarantine_corrupt_preferences(app: &AppHandle, prefs_path: &Path, error: String) {
+    let mut corrupt_path = prefs_path.as_os_str().to_owned();
+    corrupt_path.push(format!(".corrupt-{}", upload::events::now_ms()));
+    let corrupt_path = PathBuf::from(corrupt_path);
+    if let Err(e) = std::fs::rename(prefs_path, &corrupt_path) {
+        log::error!("Failed to move corrupt preferences file aside: {e}");
+    } else {
+        log::warn!("Moved corrupt preferences file to {corrupt_path:?}");
+    }
+    if let Err(e) = app.emit(
+        "preferences:recovered_from_corruption",
+        PreferencesRecoveredEvent {
+            corrupt_path: corrupt_path.to_string_lossy().to_string(),
+            error,
+        },
+    ) {
+        log::error!("Failed to emit preferences:recovered_from_corruption event: {e}");
+    }
+}
+
 #[tauri::command]
 async fn load_preferences(app: AppHandle) -> Result<AppPreferences, String> {
     log::debug!("Loading preferences from disk");
@@ -490,32 +1874,57 @@ async fn load_preferences(app: AppHandle) -> Result<AppPreferences, String> {
         return Ok(AppPreferences::default());
     }
 
-    let contents = std::fs::read_to_string(&prefs_path).map_err(|e| {
+    let contents = std::fs::read(&prefs_path).map_err(|e| {
         log::error!("Failed to read preferences file: {e}");
         format!("Failed to read preferences file: {e}")
     })?;
 
-    let preferences: AppPreferences = serde_json::from_str(&contents).map_err(|e| {
-        log::error!("Failed to parse preferences JSON: {e}");
-        format!("Failed to parse preferences: {e}")
-    })?;
+    let preferences: AppPreferences = match parse_preferences(&contents) {
+        Ok(preferences) => preferences,
+        Err(e) => {
+            log::error!("Failed to parse preferences JSON, falling back to defaults: {e}");
+            quarantine_corrupt_preferences(&app, &prefs_path, e.to_string());
+            AppPreferences::default()
+        }
+    };
+
+    app.state::<PreferencesState>()
+        .0
+        .send_if_modified(|current| {
+            if *current == preferences {
+                return false;
+            }
+            *current = preferences.clone();
+            true
+        });
 
     log::info!("Successfully loaded preferences");
     Ok(preferences)
 }
 
 #[tauri::command]
-async fn save_preferences(app: AppHandle, preferences: AppPreferences) -> Result<(), String> {
-    // Validate theme value
-    validate_theme(&preferences.theme)?;
-    validate_max_concurrent_uploads(preferences.max_concurrent_uploads)?;
-    validate_upload_chunk_size_mib(preferences.upload_chunk_size_mib)?;
-    validate_rclone_path(&preferences.rclone_path)?;
-    validate_rclone_remote_name(&preferences.rclone_remote_name)?;
-    validate_rclone_transfers(preferences.rclone_transfers)?;
-    validate_rclone_checkers(preferences.rclone_checkers)?;
-    validate_service_account_json_path(&preferences.service_account_folder_path)?;
-    validate_destination_presets(&preferences.destination_presets)?;
+async fn validate_preferences_cmd(preferences: AppPreferences) -> Vec<PreferenceError> {
+    validate_all_preferences(&preferences)
+}
+
+#[tauri::command]
+async fn save_preferences(app: AppHandle, mut preferences: AppPreferences) -> Result<(), String> {
+    // Past the standard limit, insertion order stops being a useful way to find a preset.
+    if preferences.destination_presets.len() > MAX_DESTINATION_PRESETS {
+        preferences
+            .destination_presets
+            .sort_by_cached_key(|p| p.name.to_lowercase());
+    }
+
+    // Validate every field and report all failures at once
+    let errors = validate_all_preferences(&preferences);
+    if !errors.is_empty() {
+        log::warn!(
+            "Rejected preferences with {} validation error(s)",
+            errors.len()
+        );
+        return Err(serde_json::to_string(&errors).unwrap_or_default());
+    }
 
     log::debug!("Saving preferences to disk: {preferences:?}");
     let prefs_path = get_preferences_path(&app)?;
@@ -525,23 +1934,57 @@ async fn save_preferences(app: AppHandle, preferences: AppPreferences) -> Result
         format!("Failed to serialize preferences: {e}")
     })?;
 
-    // Write to a temporary file first, then rename (atomic operation)
-    let temp_path = prefs_path.with_extension("tmp");
-
-    std::fs::write(&temp_path, json_content).map_err(|e| {
-        log::error!("Failed to write preferences file: {e}");
-        format!("Failed to write preferences file: {e}")
-    })?;
+    rotate_preferences_backups(&prefs_path);
 
-    std::fs::rename(&temp_path, &prefs_path).map_err(|e| {
-        log::error!("Failed to finalize preferences file: {e}");
-        format!("Failed to finalize preferences file: {e}")
-    })?;
+    write_verified::<AppPreferences>(&prefs_path, json_content.as_bytes(), "preferences file")?;
 
     log::info!("Successfully saved preferences to {prefs_path:?}");
+
+    let previous = app
+        .state::<PreferencesState>()
+        .0
+        .send_replace(preferences.clone());
+    let changes = diff_preferences(&previous, &preferences);
+    if !changes.is_empty() {
+        for change in &changes {
+            if let PreferenceApplyScope::NextJob = change.applies_to {
+                log::info!(
+                    "Preference {} changed; it will apply to the next upload job",
+                    change.field
+                );
+            }
+        }
+        if let Err(e) = app.emit("preferences:changed", PreferencesChangedEvent { changes }) {
+            log::error!("Failed to emit preferences:changed event: {e}");
+        }
+    }
+
     Ok(())
 }
 
+// Restores backup `index` (1 is the most recent) through save_preferences, so it is
+// validated and applied like any other save. Returns the restored preferences.
+#[tauri::command]
+async fn restore_preferences_backup(
+    app: AppHandle,
+    index: usize,
+) -> Result<AppPreferences, String> {
+    if !(1..=PREFERENCES_BACKUP_COUNT).contains(&index) {
+        return Err(format!(
+            "Invalid backup index {index}: must be between 1 and {PREFERENCES_BACKUP_COUNT}"
+        ));
+    }
+    let backup_path = preferences_backup_path(&get_preferences_path(&app)?, index);
+    let contents = std::fs::read(&backup_path)
+        .map_err(|e| format!("Failed to read preferences backup {index}: {e}"))?;
+    let preferences = parse_preferences(&contents)
+        .map_err(|e| format!("Failed to parse preferences backup {index}: {e}"))?;
+
+    log::info!("Restoring preferences from backup {index}");
+    save_preferences(app, preferences.clone()).await?;
+    Ok(preferences)
+}
+
 #[tauri::command]
 async fn send_native_notification(
     app: AppHandle,
@@ -579,6 +2022,38 @@ async fn send_native_notification(
     }
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SystemInfo {
+    app_version: String,
+    os_name: String,
+    os_version: String,
+    arch: String,
+    total_memory_bytes: u64,
+    available_memory_bytes: u64,
+    cpu_count: u32,
+}
+
+#[tauri::command]
+async fn get_system_info(app: AppHandle) -> Result<SystemInfo, String> {
+    log::debug!("Collecting system information");
+
+    let system = sysinfo::System::new_all();
+    let os_version = sysinfo::System::os_version()
+        .or_else(sysinfo::System::kernel_version)
+        .unwrap_or_else(|| "unknown".to_string());
+
+    Ok(SystemInfo {
+        app_version: app.package_info().version.to_string(),
+        os_name: std::env::consts::OS.to_string(),
+        os_version,
+        arch: std::env::consts::ARCH.to_string(),
+        total_memory_bytes: system.total_memory(),
+        available_memory_bytes: system.available_memory(),
+        cpu_count: system.cpus().len() as u32,
+    })
+}
+
 // Recovery functions - simple pattern for saving JSON data to disk
 fn get_recovery_dir(app: &AppHandle) -> Result<PathBuf, String> {
     let app_data_dir = app
@@ -595,6 +2070,55 @@ fn get_recovery_dir(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(recovery_dir)
 }
 
+fn recovery_checksum_path(recovery_dir: &Path, filename: &str) -> PathBuf {
+    recovery_dir.join(format!("{filename}.sha256"))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+enum RecoveryContents {
+    Verified(Value),
+    // Written before checksums existed: plain JSON without a sidecar.
+    Unverified(Value),
+    Corrupted,
+    // Not JSON at all, e.g. cut off mid-write.
+    Unparseable(String),
+}
+
+// Recovery files are plain JSON. The SHA-256 of the file's exact bytes sits next to it in
+// `{filename}.sha256`; files written before checksums existed have no sidecar.
+fn read_recovery_contents(contents: &[u8], checksum: Option<&str>) -> RecoveryContents {
+    if let Some(expected) = checksum {
+        if !expected.trim().eq_ignore_ascii_case(&sha256_hex(contents)) {
+            return RecoveryContents::Corrupted;
+        }
+    }
+    match serde_json::from_slice(contents) {
+        Ok(value) if checksum.is_some() => RecoveryContents::Verified(value),
+        Ok(value) => RecoveryContents::Unverified(value),
+        Err(e) => RecoveryContents::Unparseable(e.to_string()),
+    }
+}
+
+fn read_recovery_file(
+    recovery_dir: &Path,
+    filename: &str,
+    file_path: &Path,
+) -> Result<RecoveryContents, String> {
+    let contents = std::fs::read(file_path).map_err(|e| {
+        log::error!("Failed to read recovery file: {e}");
+        format!("Failed to read file: {e}")
+    })?;
+    let checksum = std::fs::read_to_string(recovery_checksum_path(recovery_dir, filename)).ok();
+    Ok(read_recovery_contents(&contents, checksum.as_deref()))
+}
+
 #[tauri::command]
 async fn save_emergency_data(app: AppHandle, filename: String, data: Value) -> Result<(), String> {
     log::info!("Saving emergency data to file: {filename}");
@@ -617,17 +2141,25 @@ async fn save_emergency_data(app: AppHandle, filename: String, data: Value) -> R
         format!("Failed to serialize data: {e}")
     })?;
 
-    // Write to a temporary file first, then rename (atomic operation)
-    let temp_path = file_path.with_extension("tmp");
+    // The old sidecar goes first, so a crash before the new one lands leaves the data
+    // unverified rather than paired with a checksum it cannot match.
+    let checksum_path = recovery_checksum_path(&recovery_dir, &filename);
+    if let Err(e) = std::fs::remove_file(&checksum_path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            log::warn!("Failed to remove old emergency data checksum: {e}");
+        }
+    }
 
-    std::fs::write(&temp_path, json_content).map_err(|e| {
-        log::error!("Failed to write emergency data file: {e}");
-        format!("Failed to write data file: {e}")
-    })?;
+    write_verified::<Value>(&file_path, json_content.as_bytes(), "data file")?;
 
-    std::fs::rename(&temp_path, &file_path).map_err(|e| {
-        log::error!("Failed to finalize emergency data file: {e}");
-        format!("Failed to finalize data file: {e}")
+    let checksum_temp_path = checksum_path.with_extension("sha256.tmp");
+    std::fs::write(&checksum_temp_path, sha256_hex(json_content.as_bytes())).map_err(|e| {
+        log::error!("Failed to write emergency data checksum: {e}");
+        format!("Failed to write checksum file: {e}")
+    })?;
+    std::fs::rename(&checksum_temp_path, &checksum_path).map_err(|e| {
+        log::error!("Failed to finalize emergency data checksum: {e}");
+        format!("Failed to finalize checksum file: {e}")
     })?;
 
     log::info!("Successfully saved emergency data to {file_path:?}");
@@ -649,20 +2181,55 @@ async fn load_emergency_data(app: AppHandle, filename: String) -> Result<Value,
         return Err("File not found".to_string());
     }
 
-    let contents = std::fs::read_to_string(&file_path).map_err(|e| {
-        log::error!("Failed to read recovery file: {e}");
-        format!("Failed to read file: {e}")
-    })?;
-
-    let data: Value = serde_json::from_str(&contents).map_err(|e| {
-        log::error!("Failed to parse recovery JSON: {e}");
-        format!("Failed to parse data: {e}")
-    })?;
+    let data = match read_recovery_file(&recovery_dir, &filename, &file_path) {
+        Ok(RecoveryContents::Verified(data) | RecoveryContents::Unverified(data)) => data,
+        Ok(RecoveryContents::Corrupted) => {
+            log::error!("Recovery file {file_path:?} failed checksum verification");
+            return Err("Recovery file is corrupted (checksum mismatch)".to_string());
+        }
+        Ok(RecoveryContents::Unparseable(e)) => {
+            log::error!("Failed to parse recovery JSON: {e}");
+            return Err(format!("Failed to parse data: {e}"));
+        }
+        Err(e) => return Err(e),
+    };
 
     log::info!("Successfully loaded emergency data");
     Ok(data)
 }
 
+/// Returns `false` when the file fails its checksum or does not parse; files written before
+/// checksums existed count as valid.
+#[tauri::command]
+async fn verify_recovery_file(app: AppHandle, filename: String) -> Result<bool, String> {
+    validate_filename(&filename)?;
+
+    let recovery_dir = get_recovery_dir(&app)?;
+    let file_path = recovery_dir.join(format!("{filename}.json"));
+
+    if !file_path.exists() {
+        log::info!("Recovery file not found: {file_path:?}");
+        return Err("File not found".to_string());
+    }
+
+    match read_recovery_file(&recovery_dir, &filename, &file_path) {
+        Ok(RecoveryContents::Verified(_)) => Ok(true),
+        Ok(RecoveryContents::Unverified(_)) => {
+            log::info!("Recovery file {file_path:?} has no checksum");
+            Ok(true)
+        }
+        Ok(RecoveryContents::Corrupted) => {
+            log::warn!("Recovery file {file_path:?} failed checksum verification");
+            Ok(false)
+        }
+        Ok(RecoveryContents::Unparseable(e)) => {
+            log::warn!("Recovery file {file_path:?} does not parse: {e}");
+            Ok(false)
+        }
+        Err(e) => Err(e),
+    }
+}
+
 #[tauri::command]
 async fn cleanup_old_recovery_files(app: AppHandle) -> Result<u32, String> {
     log::info!("Cleaning up old recovery files");
@@ -729,6 +2296,7 @@ async fn cleanup_old_recovery_files(app: AppHandle) -> Result<u32, String> {
             match std::fs::remove_file(&path) {
                 Ok(_) => {
                     log::info!("Removed old recovery file: {path:?}");
+                    let _ = std::fs::remove_file(path.with_extension("sha256"));
                     removed_count += 1;
                 }
                 Err(e) => {
@@ -742,6 +2310,104 @@ async fn cleanup_old_recovery_files(app: AppHandle) -> Result<u32, String> {
     Ok(removed_count)
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RecoveryFileInfo {
+    filename: String,
+    size_bytes: u64,
+    modified_at_unix: u64,
+    age_days: f64,
+}
+
+#[tauri::command]
+async fn list_recovery_files(app: AppHandle) -> Result<Vec<RecoveryFileInfo>, String> {
+    log::info!("Listing recovery files");
+
+    let recovery_dir = get_recovery_dir(&app)?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Failed to get current time: {e}"))?
+        .as_secs();
+
+    let entries = std::fs::read_dir(&recovery_dir).map_err(|e| {
+        log::error!("Failed to read recovery directory: {e}");
+        format!("Failed to read directory: {e}")
+    })?;
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                log::warn!("Failed to read directory entry: {e}");
+                continue;
+            }
+        };
+
+        let path = entry.path();
+
+        // Only report JSON files
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+
+        let Some(filename) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+
+        let metadata = match std::fs::metadata(&path) {
+            Ok(m) => m,
+            Err(e) => {
+                log::warn!("Failed to get file metadata: {e}");
+                continue;
+            }
+        };
+
+        let modified_at_unix = metadata
+            .modified()
+            .ok()
+            .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        files.push(RecoveryFileInfo {
+            filename: filename.to_string(),
+            size_bytes: metadata.len(),
+            modified_at_unix,
+            age_days: now.saturating_sub(modified_at_unix) as f64 / (24.0 * 60.0 * 60.0),
+        });
+    }
+
+    // Newest first
+    files.sort_by_key(|f| std::cmp::Reverse(f.modified_at_unix));
+    Ok(files)
+}
+
+#[tauri::command]
+async fn delete_recovery_file(app: AppHandle, filename: String) -> Result<(), String> {
+    log::info!("Deleting recovery file: {filename}");
+
+    // Validate filename with proper security checks
+    validate_filename(&filename)?;
+
+    let recovery_dir = get_recovery_dir(&app)?;
+    let file_path = recovery_dir.join(format!("{filename}.json"));
+
+    if !file_path.exists() {
+        log::info!("Recovery file not found: {file_path:?}");
+        return Err("File not found".to_string());
+    }
+
+    std::fs::remove_file(&file_path).map_err(|e| {
+        log::error!("Failed to delete recovery file: {e}");
+        format!("Failed to delete file: {e}")
+    })?;
+    let _ = std::fs::remove_file(recovery_checksum_path(&recovery_dir, &filename));
+
+    log::info!("Deleted recovery file {file_path:?}");
+    Ok(())
+}
+
 #[tauri::command]
 async fn classify_paths(paths: Vec<String>) -> Vec<ClassifiedPath> {
     paths
@@ -825,8 +2491,38 @@ fn create_app_menu(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let headless_job = headless::parse_headless_args(&args);
+    // Headless runs print JSON lines on stdout, so their log output goes to stderr instead.
+    let console_log = if headless_job.is_some() {
+        tauri_plugin_log::TargetKind::Stderr
+    } else {
+        tauri_plugin_log::TargetKind::Stdout
+    };
+
+    let mut builder = tauri::Builder::default();
+    // Must be the first plugin: a second launch (e.g. from a gdexplorer:// link) hands its
+    // arguments to this instance and exits. The deep-link feature forwards the URL to
+    // on_open_url below. Headless runs are independent of any open window.
+    #[cfg(desktop)]
+    if headless_job.is_none() {
+        builder = builder.plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.unminimize();
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }));
+    }
+    builder
+        .plugin(tauri_plugin_deep_link::init())
         .manage(UploadControlState::default())
+        .manage(PreferencesState::default())
+        .manage(ScheduledJobsState::default())
+        .manage(RecurringJobsState::default())
+        .manage(DeepLinkState::default())
+        .manage(upload::rclone::DrivePoolState::default())
+        .manage(updates::PendingUpdateState::default())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_notification::init())
@@ -839,8 +2535,8 @@ pub fn run() {
                     log::LevelFilter::Info
                 })
                 .targets([
-                    // Always log to stdout for development
-                    tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Stdout),
+                    // Always log to the console for development
+                    tauri_plugin_log::Target::new(console_log),
                     // Log to webview console for development
                     tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Webview),
                     // Log to a file in the app log directory
@@ -856,7 +2552,7 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_opener::init())
-        .setup(|app| {
+        .setup(move |app| {
             log::info!("🚀 Application starting up");
             log::debug!(
                 "App handle initialized for package: {}",
@@ -916,6 +2612,49 @@ pub fn run() {
                 }
             });
 
+            upload::snapshot::install(app.handle());
+            upload::joblog::install(app.handle());
+
+            upload::orphans::init(app.handle());
+            upload::quota::init(app.handle());
+
+            if let Some(job_source) = headless_job {
+                headless::run(app.handle(), job_source);
+                return Ok(());
+            }
+            // The window starts hidden so headless runs never flash it on screen.
+            if let Some(window) = app.get_webview_window("main") {
+                window.show()?;
+            }
+
+            // Installers register the scheme too; this covers dev builds and AppImages.
+            #[cfg(any(windows, target_os = "linux"))]
+            if let Err(e) = app.deep_link().register_all() {
+                log::warn!(
+                    "Failed to register the {} URL scheme: {e}",
+                    deep_link::DEEP_LINK_SCHEME
+                );
+            }
+            // Links opened while running (macOS open-url events, or a second launch forwarded
+            // by the single-instance plugin) arrive here; the launch link is read below.
+            let url_app = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    handle_deep_link(&url_app, url.as_str());
+                }
+            });
+            match app.deep_link().get_current() {
+                Ok(urls) => {
+                    for url in urls.unwrap_or_default() {
+                        handle_deep_link(app.handle(), url.as_str());
+                    }
+                }
+                Err(e) => log::warn!("Failed to read the launch deep link: {e}"),
+            }
+
+            upload::taskbar::install(app.handle());
+            updates::schedule_background_check(app.handle());
+
             // Example of different log levels
             log::trace!("This is a trace message (most verbose)");
             log::debug!("This is a debug message (development only)");
@@ -929,20 +2668,290 @@ pub fn run() {
             greet,
             load_preferences,
             save_preferences,
+            restore_preferences_backup,
+            validate_preferences_cmd,
             send_native_notification,
+            get_system_info,
             save_emergency_data,
             load_emergency_data,
+            verify_recovery_file,
             cleanup_old_recovery_files,
+            list_recovery_files,
+            delete_recovery_file,
             classify_paths,
             start_upload,
             pause_upload,
             pause_items,
             cancel_items,
             cancel_upload,
+            schedule_upload,
+            cancel_scheduled_upload,
+            list_scheduled_uploads,
+            start_recurring_upload,
+            stop_recurring_upload,
+            list_recurring_uploads,
+            take_pending_deep_link,
+            reload_service_accounts,
             list_item_files,
+            summarize_item,
+            get_upload_metrics,
+            estimate_job_feasibility,
+            get_recent_destinations,
+            clear_recent_destinations,
+            save_queue_template,
+            list_queue_templates,
+            delete_queue_template,
+            run_queue_template,
+            list_orphaned_uploads,
+            kill_orphaned_uploads,
+            dismiss_orphaned_uploads,
+            acknowledge_upload_errors,
+            check_for_updates,
+            download_and_install_update,
+            get_job_snapshot,
+            get_job_log_path,
+            open_logs_folder,
+            reset_upload_metrics,
+            get_app_paths,
+            get_sa_pool_stats,
+            reveal_path,
             rclone_tools::install_rclone_windows,
-            rclone_tools::configure_rclone_remote
+            rclone_tools::detect_rclone_installation,
+            rclone_tools::configure_rclone_remote,
+            rclone_tools::list_rclone_remotes,
+            rclone_tools::delete_rclone_remote
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                upload::orphans::shutdown();
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid_preferences() -> AppPreferences {
+        AppPreferences {
+            theme: "neon".to_string(),
+            update_channel: "nightly".to_string(),
+            service_account_folder_path: Some("x".repeat(2000)),
+            max_concurrent_uploads: 0,
+            upload_chunk_size_mib: 0,
+            rclone_path: " ".to_string(),
+            rclone_remote_name: String::new(),
+            rclone_transfers: 0,
+            rclone_checkers: 65,
+            destination_presets: vec![DestinationPreset {
+                id: "preset".to_string(),
+                name: String::new(),
+                url: "https://drive.google.com/drive/folders/abc".to_string(),
+                rclone_remote_name: None,
+            }],
+            default_destination_preset_id: Some("missing".to_string()),
+            rclone_auto_no_traverse_threshold_files: Some(2_000_000),
+            rclone_buffer_size_mib: 4096,
+            stall_timeout_minutes: 1441,
+            rclone_log_level: "TRACE".to_string(),
+            job_log_retention_days: 0,
+            ..AppPreferences::default()
+        }
+    }
+
+    #[test]
+    fn validate_all_preferences_accepts_defaults() {
+        assert!(validate_all_preferences(&AppPreferences::default()).is_empty());
+    }
+
+    #[test]
+    fn validate_all_preferences_reports_every_invalid_field() {
+        let errors = validate_all_preferences(&invalid_preferences());
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            [
+                "theme",
+                "updateChannel",
+                "maxConcurrentUploads",
+                "uploadChunkSizeMib",
+                "rclonePath",
+                "rcloneRemoteName",
+                "rcloneTransfers",
+                "rcloneCheckers",
+                "rcloneAutoNoTraverseThresholdFiles",
+                "rcloneBufferSizeMib",
+                "stallTimeoutMinutes",
+                "rcloneLogLevel",
+                "jobLogRetentionDays",
+                "serviceAccountFolderPath",
+                "destinationPresets",
+                "defaultDestinationPresetId",
+            ]
+        );
+        assert!(errors.iter().all(|e| !e.message.is_empty()));
+    }
+
+    #[test]
+    fn validate_all_preferences_combines_unrelated_failures() {
+        let prefs = AppPreferences {
+            theme: "neon".to_string(),
+            rclone_transfers: 100,
+            default_destination_preset_id: Some("gone".to_string()),
+            ..AppPreferences::default()
+        };
+        let errors = validate_all_preferences(&prefs);
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            ["theme", "rcloneTransfers", "defaultDestinationPresetId"]
+        );
+    }
+
+    #[test]
+    fn preference_errors_serialize_as_camel_case_list() {
+        let errors = validate_all_preferences(&AppPreferences {
+            max_concurrent_uploads: 11,
+            ..AppPreferences::default()
+        });
+        let json = serde_json::to_value(&errors).unwrap();
+        assert_eq!(json[0]["field"], "maxConcurrentUploads");
+        assert!(json[0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("between 1 and 10"));
+    }
+
+    #[test]
+    fn individual_validators_check_range_bounds() {
+        assert!(validate_max_concurrent_uploads(1).is_ok());
+        assert!(validate_max_concurrent_uploads(10).is_ok());
+        assert!(validate_max_concurrent_uploads(11).is_err());
+        assert!(validate_upload_chunk_size_mib(1024).is_ok());
+        assert!(validate_upload_chunk_size_mib(1025).is_err());
+        assert!(validate_rclone_checkers(64).is_ok());
+        assert!(validate_rclone_checkers(0).is_err());
+        assert!(validate_stall_timeout_minutes(0).is_ok());
+        assert!(validate_job_log_retention_days(366).is_err());
+        assert!(validate_rclone_log_level("NOTICE").is_ok());
+        assert!(validate_rclone_log_level("notice").is_err());
+    }
+
+    #[test]
+    fn diff_preferences_defers_rclone_binary_and_remote_to_next_job() {
+        let old = AppPreferences::default();
+        let new = AppPreferences {
+            rclone_path: "/opt/rclone".to_string(),
+            rclone_remote_name: "other".to_string(),
+            rclone_transfers: 8,
+            ..AppPreferences::default()
+        };
+        let changes = diff_preferences(&old, &new);
+        let scope = |field: &str| {
+            changes
+                .iter()
+                .find(|change| change.field == field)
+                .map(|change| change.applies_to)
+        };
+        assert!(matches!(
+            scope("rclonePath"),
+            Some(PreferenceApplyScope::NextJob)
+        ));
+        assert!(matches!(
+            scope("rcloneRemoteName"),
+            Some(PreferenceApplyScope::NextJob)
+        ));
+        assert!(matches!(
+            scope("rcloneTransfers"),
+            Some(PreferenceApplyScope::Live)
+        ));
+    }
+
+    #[test]
+    fn recovery_files_are_checked_against_their_sidecar() {
+        let contents = br#"{"queue":[]}"#;
+        let data = serde_json::json!({ "queue": [] });
+        let checksum = sha256_hex(contents);
+        assert_eq!(
+            read_recovery_contents(contents, Some(&checksum)),
+            RecoveryContents::Verified(data.clone())
+        );
+        assert_eq!(
+            read_recovery_contents(contents, Some("deadbeef")),
+            RecoveryContents::Corrupted
+        );
+        assert_eq!(
+            read_recovery_contents(contents, None),
+            RecoveryContents::Unverified(data)
+        );
+        assert!(matches!(
+            read_recovery_contents(b"{\"queue\":", None),
+            RecoveryContents::Unparseable(_)
+        ));
+        // Only the sidecar is trusted; data that happens to carry its own hash is just data.
+        let shaped = br#"{"sha256":"deadbeef","data":{}}"#;
+        assert!(matches!(
+            read_recovery_contents(shaped, None),
+            RecoveryContents::Unverified(_)
+        ));
+    }
+
+    #[test]
+    fn parse_preferences_treats_truncated_utf8_as_corruption() {
+        let preferences = AppPreferences {
+            rclone_remote_name: "ドライブ".to_string(),
+            ..AppPreferences::default()
+        };
+        let bytes = serde_json::to_vec(&preferences).unwrap();
+        assert_eq!(parse_preferences(&bytes).unwrap(), preferences);
+
+        // Cut inside the multi-byte name: the prefix is not valid UTF-8.
+        let cut = bytes
+            .windows("ド".len())
+            .position(|w| w == "ド".as_bytes())
+            .unwrap()
+            + 1;
+        assert!(std::str::from_utf8(&bytes[..cut]).is_err());
+        assert!(parse_preferences(&bytes[..cut]).is_err());
+        assert!(parse_preferences(b"\xff\xfe{}").is_err());
+    }
+
+    #[test]
+    fn a_truncated_write_never_replaces_the_live_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let truncate =
+            |path: &Path, contents: &[u8]| std::fs::write(path, &contents[..contents.len() / 2]);
+
+        // The save paths of save_preferences and save_emergency_data.
+        let prefs_path = dir.path().join("preferences.json");
+        let live = serde_json::to_vec_pretty(&AppPreferences::default()).unwrap();
+        std::fs::write(&prefs_path, &live).unwrap();
+        let next = serde_json::to_vec_pretty(&AppPreferences {
+            rclone_remote_name: "other".to_string(),
+            ..AppPreferences::default()
+        })
+        .unwrap();
+        assert!(write_verified_with::<AppPreferences>(
+            &prefs_path,
+            &next,
+            "preferences file",
+            truncate
+        )
+        .is_err());
+        assert_eq!(std::fs::read(&prefs_path).unwrap(), live);
+        assert!(!prefs_path.with_extension("tmp").exists());
+        write_verified::<AppPreferences>(&prefs_path, &next, "preferences file").unwrap();
+        assert_eq!(std::fs::read(&prefs_path).unwrap(), next);
+
+        let data_path = dir.path().join("queue.json");
+        let live = br#"{"queue":["a.txt"]}"#;
+        std::fs::write(&data_path, live).unwrap();
+        let next =
+            serde_json::to_vec_pretty(&serde_json::json!({ "queue": ["a.txt", "b.txt"] })).unwrap();
+        assert!(write_verified_with::<Value>(&data_path, &next, "data file", truncate).is_err());
+        assert_eq!(std::fs::read(&data_path).unwrap(), live);
+        assert!(!data_path.with_extension("tmp").exists());
+    }
 }
@@ -0,0 +1,143 @@
+use crate::json_state;
+use crate::upload::events::now_ms;
+use crate::upload::scheduler::QueueItemInput;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+pub const STATE_FILE: &str = "queue-templates.json";
+const MAX_QUEUE_TEMPLATES: usize = 50;
+const MAX_TEMPLATE_NAME_LEN: usize = 100;
+
+// Serializes read-modify-write cycles on the state file.
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueTemplate {
+    pub id: String,
+    pub name: String,
+    pub queue_items: Vec<QueueItemInput>,
+    pub destination_folder_id: String,
+    pub saved_unix_ms: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueueTemplatesFile {
+    templates: Vec<QueueTemplate>,
+}
+
+fn state_path(app: &AppHandle) -> Result<PathBuf, String> {
+    json_state::state_path(app, STATE_FILE)
+}
+
+fn load(path: &Path) -> Result<QueueTemplatesFile, String> {
+    json_state::load(path, "queue templates")
+}
+
+fn save(path: &Path, file: &QueueTemplatesFile) -> Result<(), String> {
+    json_state::save(path, file, "queue templates")
+}
+
+// Like validate_filename, but spaces are allowed since the name is only ever shown.
+pub fn validate_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Template name cannot be empty".to_string());
+    }
+    if name.len() > MAX_TEMPLATE_NAME_LEN {
+        return Err(format!(
+            "Template name too long (max {MAX_TEMPLATE_NAME_LEN} characters)"
+        ));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.'))
+    {
+        return Err(
+            "Invalid template name: only alphanumeric characters, spaces, dashes, underscores, and dots allowed"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+// Saving under an existing name replaces that template and keeps its id.
+pub fn save_template(
+    app: &AppHandle,
+    name: &str,
+    queue_items: Vec<QueueItemInput>,
+    destination_folder_id: String,
+) -> Result<QueueTemplate, String> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = state_path(app)?;
+    let mut file = load(&path)?;
+    let name = name.trim().to_string();
+    let existing = file
+        .templates
+        .iter()
+        .position(|template| template.name == name);
+    if existing.is_none() && file.templates.len() >= MAX_QUEUE_TEMPLATES {
+        return Err(format!(
+            "Too many queue templates (max {MAX_QUEUE_TEMPLATES}); delete one first"
+        ));
+    }
+
+    let saved_unix_ms = now_ms();
+    let template = QueueTemplate {
+        id: existing
+            .map(|idx| file.templates[idx].id.clone())
+            .unwrap_or_else(|| format!("template-{saved_unix_ms:x}")),
+        name,
+        queue_items,
+        destination_folder_id,
+        saved_unix_ms,
+    };
+    match existing {
+        Some(idx) => file.templates[idx] = template.clone(),
+        None => file.templates.push(template.clone()),
+    }
+    save(&path, &file)?;
+    Ok(template)
+}
+
+pub fn list(app: &AppHandle) -> Result<Vec<QueueTemplate>, String> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = state_path(app)?;
+    Ok(load(&path)?.templates)
+}
+
+pub fn get(app: &AppHandle, template_id: &str) -> Result<QueueTemplate, String> {
+    list(app)?
+        .into_iter()
+        .find(|template| template.id == template_id)
+        .ok_or_else(|| format!("Queue template not found: {template_id}"))
+}
+
+pub fn delete(app: &AppHandle, template_id: &str) -> Result<(), String> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = state_path(app)?;
+    let mut file = load(&path)?;
+    let before = file.templates.len();
+    file.templates.retain(|template| template.id != template_id);
+    if file.templates.len() == before {
+        return Err(format!("Queue template not found: {template_id}"));
+    }
+    save(&path, &file)
+}
+
+// Splits the saved items into those still on disk as the same kind and a warning for each
+// one that is not.
+pub fn restat(items: Vec<QueueItemInput>) -> (Vec<QueueItemInput>, Vec<String>) {
+    let mut present = Vec::with_capacity(items.len());
+    let mut warnings = Vec::new();
+    for item in items {
+        match std::fs::metadata(&item.path) {
+            Ok(metadata) if metadata.is_dir() == (item.kind == "folder") => present.push(item),
+            Ok(_) => warnings.push(format!("{}: no longer a {}", item.path, item.kind)),
+            Err(e) => warnings.push(format!("{}: {e}", item.path)),
+        }
+    }
+    (present, warnings)
+}
@@ -1,22 +1,58 @@
+use serde::Serialize;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Manager};
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RcloneInstallation {
+    pub found: bool,
+    pub path: Option<String>,
+    // As printed by `rclone version`, e.g. "v1.66.0"; None if the binary did not answer.
+    pub version: Option<String>,
+}
+
+#[tauri::command]
+pub async fn detect_rclone_installation() -> Result<RcloneInstallation, String> {
+    let Some(path) = find_rclone_on_path() else {
+        return Ok(RcloneInstallation {
+            found: false,
+            path: None,
+            version: None,
+        });
+    };
+    let path = path.to_string_lossy().to_string();
+    Ok(RcloneInstallation {
+        found: true,
+        version: check_rclone_version(&path),
+        path: Some(path),
+    })
+}
+
+// Where install_rclone_windows unpacks its download.
+pub fn rclone_install_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("rclone"))
+        .map_err(|e| format!("Failed to resolve app data directory: {e}"))
+}
+
 #[tauri::command]
 pub async fn install_rclone_windows(app: AppHandle) -> Result<String, String> {
     if !cfg!(target_os = "windows") {
         return Err("Rclone installer is only available on Windows.".to_string());
     }
 
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to resolve app data directory: {e}"))?;
-    std::fs::create_dir_all(&app_data_dir)
-        .map_err(|e| format!("Failed to create app data directory: {e}"))?;
+    if let Some(existing) = find_rclone_on_path() {
+        log::info!(
+            "rclone already installed at {}; skipping download",
+            existing.to_string_lossy()
+        );
+        return Ok(existing.to_string_lossy().to_string());
+    }
 
-    let install_dir = app_data_dir.join("rclone");
+    let install_dir = rclone_install_dir(&app)?;
     std::fs::create_dir_all(&install_dir)
         .map_err(|e| format!("Failed to create rclone directory: {e}"))?;
 
@@ -29,7 +65,12 @@ pub async fn install_rclone_windows(app: AppHandle) -> Result<String, String> {
     };
 
     let zip_path = install_dir.join("rclone.zip");
-    let bytes = reqwest::get(url)
+    let bytes = reqwest::Client::builder()
+        .user_agent(crate::USER_AGENT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {e}"))?
+        .get(url)
+        .send()
         .await
         .map_err(|e| format!("Failed to download rclone: {e}"))?
         .bytes()
@@ -86,15 +127,12 @@ pub async fn configure_rclone_remote(
     remote_name: String,
     service_account_folder: String,
 ) -> Result<(), String> {
-    if !cfg!(target_os = "windows") {
-        return Err("Rclone setup is only available on Windows.".to_string());
-    }
-
+    crate::validate_rclone_remote_name(&remote_name)?;
     let service_account_file = pick_service_account_file(&service_account_folder)?
         .to_string_lossy()
         .to_string();
 
-    let status = std::process::Command::new(&rclone_path)
+    let status = rclone_config_command(&rclone_path)
         .args([
             "config",
             "create",
@@ -113,7 +151,7 @@ pub async fn configure_rclone_remote(
         return Ok(());
     }
 
-    let update_status = std::process::Command::new(&rclone_path)
+    let update_status = rclone_config_command(&rclone_path)
         .args([
             "config",
             "update",
@@ -131,9 +169,149 @@ pub async fn configure_rclone_remote(
     Err("Failed to configure rclone remote.".to_string())
 }
 
+// Names of the remotes in rclone's config file, sorted.
+pub fn rclone_remote_names(rclone_path: &str) -> Result<Vec<String>, String> {
+    let output = rclone_config_command(rclone_path)
+        .args(["config", "dump"])
+        .output()
+        .map_err(|e| format!("Failed to run rclone config dump: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "rclone config dump failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let config: serde_json::Map<String, serde_json::Value> = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse rclone config dump: {e}"))?;
+    let mut names: Vec<String> = config.into_iter().map(|(name, _)| name).collect();
+    names.sort();
+    Ok(names)
+}
+
+#[tauri::command]
+pub async fn list_rclone_remotes(rclone_path: String) -> Result<Vec<String>, String> {
+    rclone_remote_names(&rclone_path)
+}
+
+#[tauri::command]
+pub async fn delete_rclone_remote(rclone_path: String, remote_name: String) -> Result<(), String> {
+    crate::validate_rclone_remote_name(&remote_name)?;
+    let status = rclone_config_command(&rclone_path)
+        .args(["config", "delete", &remote_name])
+        .status()
+        .map_err(|e| format!("Failed to run rclone config delete: {e}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("Failed to delete rclone remote {remote_name:?}."))
+    }
+}
+
+fn rclone_binary_name() -> &'static str {
+    if cfg!(windows) {
+        "rclone.exe"
+    } else {
+        "rclone"
+    }
+}
+
+// Where package managers and the official installers put rclone. Apps started from the macOS
+// Finder or the Windows Start menu do not always see the PATH a terminal would.
+fn common_rclone_locations() -> Vec<PathBuf> {
+    let mut locations = Vec::new();
+    if cfg!(windows) {
+        locations.push(PathBuf::from(r"C:\Program Files\rclone\rclone.exe"));
+        locations.push(PathBuf::from(r"C:\ProgramData\chocolatey\bin\rclone.exe"));
+        if let Some(local) = std::env::var_os("LOCALAPPDATA") {
+            locations.push(
+                PathBuf::from(local)
+                    .join("Microsoft")
+                    .join("WinGet")
+                    .join("Links")
+                    .join("rclone.exe"),
+            );
+        }
+        if let Some(profile) = std::env::var_os("USERPROFILE") {
+            locations.push(
+                PathBuf::from(profile)
+                    .join("scoop")
+                    .join("shims")
+                    .join("rclone.exe"),
+            );
+        }
+    } else {
+        locations.push(PathBuf::from("/opt/homebrew/bin/rclone"));
+        locations.push(PathBuf::from("/usr/local/bin/rclone"));
+        locations.push(PathBuf::from("/usr/bin/rclone"));
+        if let Some(home) = std::env::var_os("HOME") {
+            locations.push(
+                PathBuf::from(home)
+                    .join(".local")
+                    .join("bin")
+                    .join("rclone"),
+            );
+        }
+    }
+    locations
+}
+
+// Searches PATH first, then the usual install locations.
+fn find_rclone_on_path() -> Option<PathBuf> {
+    let on_path = std::env::var_os("PATH")
+        .map(|paths| {
+            std::env::split_paths(&paths)
+                .map(|dir| dir.join(rclone_binary_name()))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    on_path
+        .into_iter()
+        .chain(common_rclone_locations())
+        .find(|candidate| candidate.is_file())
+}
+
+// The version tag from the first line of `rclone version`, e.g. "rclone v1.66.0".
+fn check_rclone_version(rclone_path: &str) -> Option<String> {
+    let output = rclone_config_command(rclone_path)
+        .arg("version")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()?
+        .split_whitespace()
+        .nth(1)
+        .map(str::to_string)
+}
+
+// Keeps rclone from flashing a console window on Windows; a plain command elsewhere.
+fn rclone_config_command(rclone_path: &str) -> std::process::Command {
+    #[allow(unused_mut)]
+    let mut command = std::process::Command::new(rclone_path);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    #[cfg(test)]
+    tests::TEST_RCLONE_CONFIG.with_borrow(|config| {
+        if let Some(config) = config {
+            command.env("RCLONE_CONFIG", config);
+        }
+    });
+    command
+}
+
+// Picks the first JSON file by name, so every platform configures the same account
+// regardless of directory listing order.
 fn pick_service_account_file(folder: &str) -> Result<PathBuf, String> {
     let entries = std::fs::read_dir(folder)
         .map_err(|e| format!("Failed to read service account folder: {e}"))?;
+    let mut candidates = Vec::new();
     for entry in entries {
         let entry = entry.map_err(|e| format!("Failed to read folder entry: {e}"))?;
         let path = entry.path();
@@ -143,13 +321,15 @@ fn pick_service_account_file(folder: &str) -> Result<PathBuf, String> {
         let is_json = path
             .extension()
             .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
-        if !is_json {
-            continue;
+        if is_json {
+            candidates.push(path);
         }
-        return Ok(path);
     }
 
-    Err("No service account JSON files found in the selected folder.".to_string())
+    candidates
+        .into_iter()
+        .min()
+        .ok_or_else(|| "No service account JSON files found in the selected folder.".to_string())
 }
 
 fn find_rclone_exe(root: &Path) -> Option<PathBuf> {
@@ -163,3 +343,79 @@ fn find_rclone_exe(root: &Path) -> Option<PathBuf> {
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    thread_local! {
+        // Config file for rclone commands built on this thread, in place of the user's own.
+        pub(super) static TEST_RCLONE_CONFIG: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+    }
+
+    #[test]
+    fn pick_service_account_file_takes_the_first_json_by_name() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        for name in ["b.json", "a.JSON", "0-notes.txt"] {
+            std::fs::write(dir.path().join(name), "{}").expect("write file");
+        }
+        std::fs::create_dir(dir.path().join("0.json")).expect("create dir");
+        let picked = pick_service_account_file(&dir.path().to_string_lossy()).expect("pick");
+        assert_eq!(picked, dir.path().join("a.JSON"));
+
+        let empty = tempfile::tempdir().expect("temp dir");
+        assert!(pick_service_account_file(&empty.path().to_string_lossy()).is_err());
+    }
+
+    // Talks to a real rclone binary, so it only runs on request: `cargo test -- --ignored`.
+    // Commands built on this thread point rclone at a throwaway config through RCLONE_CONFIG.
+    #[tokio::test]
+    #[ignore = "needs rclone installed"]
+    async fn configure_list_and_delete_a_remote() {
+        let rclone = find_rclone_on_path().expect("rclone is not installed");
+        let rclone = rclone.to_string_lossy().to_string();
+        let dir = tempfile::tempdir().expect("temp dir");
+        let sa_folder = dir.path().join("accounts");
+        std::fs::create_dir(&sa_folder).expect("create folder");
+        std::fs::write(sa_folder.join("sa.json"), r#"{"type":"service_account"}"#)
+            .expect("write account");
+        TEST_RCLONE_CONFIG.set(Some(dir.path().join("rclone.conf")));
+
+        let folder = sa_folder.to_string_lossy().to_string();
+        configure_rclone_remote(rclone.clone(), "gdx-test".to_string(), folder.clone())
+            .await
+            .expect("create remote");
+        // A second run updates the existing remote instead of failing.
+        configure_rclone_remote(rclone.clone(), "gdx-test".to_string(), folder)
+            .await
+            .expect("update remote");
+        assert_eq!(
+            list_rclone_remotes(rclone.clone()).await.expect("list"),
+            ["gdx-test"]
+        );
+
+        delete_rclone_remote(rclone.clone(), "gdx-test".to_string())
+            .await
+            .expect("delete remote");
+        assert!(list_rclone_remotes(rclone).await.expect("list").is_empty());
+    }
+
+    #[tokio::test]
+    async fn remote_commands_reject_names_rclone_would_read_as_flags() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let folder = dir.path().to_string_lossy().to_string();
+        // The name is checked before rclone is looked for, so no binary is needed.
+        for name in ["--config=/tmp/x", "-v", "", "  "] {
+            let err = delete_rclone_remote("rclone".to_string(), name.to_string())
+                .await
+                .expect_err("delete should be refused");
+            assert!(err.starts_with("Invalid rclone remote name"), "{err}");
+            let err =
+                configure_rclone_remote("rclone".to_string(), name.to_string(), folder.clone())
+                    .await
+                    .expect_err("configure should be refused");
+            assert!(err.starts_with("Invalid rclone remote name"), "{err}");
+        }
+    }
+}
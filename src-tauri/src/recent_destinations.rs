@@ -0,0 +1,185 @@
+use crate::json_state;
+use crate::upload::events::now_ms;
+use crate::DestinationPreset;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Url};
+
+pub const STATE_FILE: &str = "recent-destinations.json";
+const MAX_RECENT_DESTINATIONS: usize = 10;
+
+// Serializes read-modify-write cycles on the state file.
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentDestination {
+    pub folder_id: String,
+    // Taken from a preset pointing at the folder; None until one is found.
+    pub name: Option<String>,
+    pub last_used_unix_ms: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecentDestinationsFile {
+    // Most recently used first.
+    destinations: Vec<RecentDestination>,
+}
+
+fn state_path(app: &AppHandle) -> Result<PathBuf, String> {
+    json_state::state_path(app, STATE_FILE)
+}
+
+fn load(path: &Path) -> Result<RecentDestinationsFile, String> {
+    json_state::load(path, "recent destinations")
+}
+
+fn save(path: &Path, file: &RecentDestinationsFile) -> Result<(), String> {
+    json_state::save(path, file, "recent destinations")
+}
+
+// Mirrors extractDriveFolderId in src/lib/drive-url.ts, which validates preset URLs.
+fn drive_folder_id(raw: &str) -> Option<String> {
+    let url = Url::parse(raw.trim()).ok()?;
+    if !matches!(url.scheme(), "https" | "http") || url.host_str() != Some("drive.google.com") {
+        return None;
+    }
+    let is_id = |id: &str| {
+        !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    };
+    let segments: Vec<&str> = url.path_segments()?.collect();
+    let folder = match segments.as_slice() {
+        ["drive", "folders", id] | ["drive", "folders", id, ""] => Some(*id),
+        ["drive", "u", account, "folders", id] | ["drive", "u", account, "folders", id, ""]
+            if account.chars().all(|c| c.is_ascii_digit()) && !account.is_empty() =>
+        {
+            Some(*id)
+        }
+        _ => None,
+    };
+    if let Some(id) = folder {
+        return is_id(id).then(|| id.to_string());
+    }
+    if url.path() == "/open" {
+        return url
+            .query_pairs()
+            .find(|(key, _)| key == "id")
+            .map(|(_, id)| id.into_owned())
+            .filter(|id| is_id(id));
+    }
+    None
+}
+
+fn preset_name_for(presets: &[DestinationPreset], folder_id: &str) -> Option<String> {
+    presets
+        .iter()
+        .find(|preset| drive_folder_id(&preset.url).as_deref() == Some(folder_id))
+        .map(|preset| preset.name.clone())
+}
+
+// Moves the folder to the front of the list, keeping a known name if no new one is given.
+pub fn record(app: &AppHandle, folder_id: &str, name: Option<String>) -> Result<(), String> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = state_path(app)?;
+    let mut file = load(&path)?;
+    let previous = file
+        .destinations
+        .iter()
+        .position(|entry| entry.folder_id == folder_id)
+        .map(|idx| file.destinations.remove(idx));
+    file.destinations.insert(
+        0,
+        RecentDestination {
+            folder_id: folder_id.to_string(),
+            name: name.or_else(|| previous.and_then(|entry| entry.name)),
+            last_used_unix_ms: now_ms(),
+        },
+    );
+    file.destinations.truncate(MAX_RECENT_DESTINATIONS);
+    save(&path, &file)
+}
+
+// Names are filled in lazily from the presets; the file is only rewritten when one changed.
+pub fn list(
+    app: &AppHandle,
+    presets: &[DestinationPreset],
+) -> Result<Vec<RecentDestination>, String> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = state_path(app)?;
+    let mut file = load(&path)?;
+    let mut changed = false;
+    for entry in &mut file.destinations {
+        if let Some(name) = preset_name_for(presets, &entry.folder_id) {
+            if entry.name.as_deref() != Some(name.as_str()) {
+                entry.name = Some(name);
+                changed = true;
+            }
+        }
+    }
+    if changed {
+        if let Err(e) = save(&path, &file) {
+            log::warn!("{e}");
+        }
+    }
+    Ok(file.destinations)
+}
+
+pub fn clear(app: &AppHandle) -> Result<(), String> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = state_path(app)?;
+    save(&path, &RecentDestinationsFile::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset(name: &str, url: &str) -> DestinationPreset {
+        DestinationPreset {
+            id: name.to_string(),
+            name: name.to_string(),
+            url: url.to_string(),
+            rclone_remote_name: None,
+        }
+    }
+
+    #[test]
+    fn drive_folder_id_accepts_the_urls_presets_allow() {
+        assert_eq!(
+            drive_folder_id("https://drive.google.com/drive/folders/abc_-1").as_deref(),
+            Some("abc_-1")
+        );
+        assert_eq!(
+            drive_folder_id(" https://drive.google.com/drive/u/1/folders/abc/?usp=sharing")
+                .as_deref(),
+            Some("abc")
+        );
+        assert_eq!(
+            drive_folder_id("https://drive.google.com/open?id=xyz").as_deref(),
+            Some("xyz")
+        );
+        assert!(drive_folder_id("https://drive.google.com/file/d/abc/view").is_none());
+        assert!(drive_folder_id("https://example.com/drive/folders/abc").is_none());
+        assert!(drive_folder_id("not a url").is_none());
+    }
+
+    #[test]
+    fn preset_names_match_whole_folder_ids_only() {
+        let presets = [
+            preset("Longer", "https://drive.google.com/drive/folders/abc123"),
+            preset("Exact", "https://drive.google.com/drive/folders/abc"),
+        ];
+        assert_eq!(preset_name_for(&presets, "abc").as_deref(), Some("Exact"));
+        assert_eq!(
+            preset_name_for(&presets, "abc123").as_deref(),
+            Some("Longer")
+        );
+        assert!(preset_name_for(&presets, "drive").is_none());
+        assert!(preset_name_for(&presets, "123").is_none());
+    }
+}
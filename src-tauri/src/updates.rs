@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, Url};
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+pub const UPDATE_CHANNELS: [&str; 2] = ["stable", "beta"];
+const STABLE_ENDPOINT: &str =
+    "https://github.com/vo1x/GDExplorer/releases/latest/download/latest.json";
+// GitHub's "latest" release skips prereleases, so beta builds publish their manifest under
+// a rolling "beta" tag instead.
+const BETA_ENDPOINT: &str = "https://github.com/vo1x/GDExplorer/releases/download/beta/latest.json";
+const BACKGROUND_CHECK_DELAY: Duration = Duration::from_secs(5 * 60);
+const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(100);
+
+// The update found by the last check, kept so download_and_install_update installs exactly
+// the version the user was shown.
+#[derive(Default)]
+pub struct PendingUpdateState(pub tokio::sync::Mutex<Option<Update>>);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInfo {
+    pub available: bool,
+    pub version: Option<String>,
+    pub notes: Option<String>,
+    pub published_at_unix_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateDownloadProgressEvent {
+    pub downloaded_bytes: u64,
+    pub content_length: Option<u64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateNotifyState {
+    last_notified_version: Option<String>,
+}
+
+impl UpdateInfo {
+    fn from_update(update: Option<&Update>) -> Self {
+        Self {
+            available: update.is_some(),
+            version: update.map(|u| u.version.clone()),
+            notes: update.and_then(|u| u.body.clone()),
+            published_at_unix_ms: update
+                .and_then(|u| u.date)
+                .map(|date| (date.unix_timestamp_nanos() / 1_000_000) as i64),
+        }
+    }
+}
+
+pub async fn check(app: &AppHandle, channel: &str) -> Result<UpdateInfo, String> {
+    let endpoint = match channel {
+        "beta" => BETA_ENDPOINT,
+        _ => STABLE_ENDPOINT,
+    };
+    let endpoint = Url::parse(endpoint).map_err(|e| format!("Invalid update endpoint: {e}"))?;
+    let updater = app
+        .updater_builder()
+        .endpoints(vec![endpoint])
+        .and_then(|builder| builder.build())
+        .map_err(|e| format!("Failed to configure updater: {e}"))?;
+    let update = updater
+        .check()
+        .await
+        .map_err(|e| format!("Update check failed: {e}"))?;
+
+    let info = UpdateInfo::from_update(update.as_ref());
+    log::info!(
+        "Update check channel={} available={} version={:?}",
+        channel,
+        info.available,
+        info.version
+    );
+    *app.state::<PendingUpdateState>().0.lock().await = update;
+    Ok(info)
+}
+
+pub async fn download_and_install(app: &AppHandle) -> Result<(), String> {
+    let update = app
+        .state::<PendingUpdateState>()
+        .0
+        .lock()
+        .await
+        .take()
+        .ok_or_else(|| "No update available; check for updates first".to_string())?;
+
+    log::info!("Downloading update {}", update.version);
+    let mut downloaded_bytes = 0_u64;
+    let mut last_emit: Option<Instant> = None;
+    update
+        .download_and_install(
+            |chunk_length, content_length| {
+                downloaded_bytes += chunk_length as u64;
+                if last_emit.is_some_and(|at| at.elapsed() < PROGRESS_EMIT_INTERVAL) {
+                    return;
+                }
+                last_emit = Some(Instant::now());
+                let _ = app.emit(
+                    "update:download_progress",
+                    UpdateDownloadProgressEvent {
+                        downloaded_bytes,
+                        content_length,
+                    },
+                );
+            },
+            || {
+                let _ = app.emit("update:download_finished", ());
+            },
+        )
+        .await
+        .map_err(|e| format!("Failed to install update: {e}"))
+}
+
+fn notify_state_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    std::fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {e}"))?;
+    Ok(app_data_dir.join("update-state.json"))
+}
+
+fn load_notify_state(app: &AppHandle) -> UpdateNotifyState {
+    notify_state_path(app)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_notify_state(app: &AppHandle, state: &UpdateNotifyState) -> Result<(), String> {
+    let path = notify_state_path(app)?;
+    let contents = serde_json::to_string_pretty(state)
+        .map_err(|e| format!("Failed to serialize update state: {e}"))?;
+    std::fs::write(path, contents).map_err(|e| format!("Failed to write update state: {e}"))
+}
+
+// Checks once, a few minutes after startup, when auto_check_updates is on. Each version is
+// announced with update:available only once, even across restarts.
+pub fn schedule_background_check(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(BACKGROUND_CHECK_DELAY).await;
+        let preferences = match crate::load_preferences(app.clone()).await {
+            Ok(preferences) => preferences,
+            Err(e) => {
+                log::warn!("Skipping background update check: {e}");
+                return;
+            }
+        };
+        if !preferences.auto_check_updates {
+            return;
+        }
+
+        let info = match check(&app, &preferences.update_channel).await {
+            Ok(info) => info,
+            Err(e) => {
+                log::warn!("Background update check failed: {e}");
+                return;
+            }
+        };
+        let Some(version) = info.version.clone() else {
+            return;
+        };
+        let mut state = load_notify_state(&app);
+        if state.last_notified_version.as_deref() == Some(version.as_str()) {
+            log::debug!("Update {version} was already announced");
+            return;
+        }
+
+        let _ = app.emit("update:available", info);
+        state.last_notified_version = Some(version);
+        if let Err(e) = save_notify_state(&app, &state) {
+            log::warn!("{e}");
+        }
+    });
+}
@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// How long an account sits out after Drive rate-limits it.
+pub const RATE_LIMIT_COOLDOWN: Duration = Duration::from_secs(120);
+
+// Keyed by client_email, or the key file path for accounts without one. Shared across jobs so
+// a new job does not walk straight back into an account that was just throttled.
+static COOLDOWNS: Mutex<Option<HashMap<String, Instant>>> = Mutex::new(None);
+
+pub fn key(path: &Path, email: Option<&str>) -> String {
+    email
+        .map(str::to_string)
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+pub fn start(key: String) {
+    let mut cooldowns = COOLDOWNS.lock().unwrap_or_else(|e| e.into_inner());
+    let cooldowns = cooldowns.get_or_insert_with(HashMap::new);
+    let now = Instant::now();
+    cooldowns.retain(|_, until| *until > now);
+    cooldowns.insert(key, now + RATE_LIMIT_COOLDOWN);
+}
+
+// When the account may be used again; None if it is not cooling down.
+pub fn until(key: &str) -> Option<Instant> {
+    let cooldowns = COOLDOWNS.lock().unwrap_or_else(|e| e.into_inner());
+    cooldowns
+        .as_ref()?
+        .get(key)
+        .copied()
+        .filter(|until| *until > Instant::now())
+}
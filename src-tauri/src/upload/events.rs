@@ -1,4 +1,24 @@
+use crate::fs_scan::ScanWarning;
+use crate::upload::quota::JobFeasibility;
 use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+
+// Emits to the webview and, in headless mode, mirrors the event to stdout.
+pub fn emit<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    crate::headless::forward(event, &payload);
+    let _ = app.emit(event, payload);
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobStartedEvent {
+    pub job_id: String,
+    pub total_items: u32,
+    pub timestamp_unix_ms: u64,
+    pub destination_folder_id: String,
+    pub max_concurrent: u8,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -9,6 +29,14 @@ pub struct ItemStatusEvent {
     pub status: String,
     pub message: Option<String>,
     pub sa_email: Option<String>,
+    // Number of files left out because they could not be read; set on completion.
+    pub skipped_count: Option<u32>,
+    // Time since a worker picked the item up, and that time minus pauses; set on done/failed.
+    #[serde(default)]
+    pub wall_time_ms: Option<u64>,
+    #[serde(default)]
+    pub active_time_ms: Option<u64>,
+    pub timestamp_unix_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +46,13 @@ pub struct ProgressEvent {
     pub path: String,
     pub bytes_sent: u64,
     pub total_bytes: u64,
+    pub speed: Option<f64>,
+    // Only known when rclone reports it; see parse_progress_line.
+    #[serde(default)]
+    pub eta_secs: Option<u64>,
+    // True when total_bytes comes from rclone's running estimate rather than local enumeration.
+    pub estimated_total: bool,
+    pub timestamp_unix_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +63,21 @@ pub struct FileProgressEvent {
     pub bytes_sent: u64,
     pub total_bytes: u64,
     pub sa_email: Option<String>,
+    pub speed: Option<f64>,
+    pub eta_secs: Option<u64>,
+    pub timestamp_unix_ms: u64,
+}
+
+// Sent once a second for each uploading item, with the rate measured over the last few seconds
+// rather than rclone's own running average.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpeedEvent {
+    pub item_id: String,
+    pub path: String,
+    pub bytes_per_sec: f64,
+    pub eta_secs: Option<u64>,
+    pub window_secs: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,12 +92,160 @@ pub struct FileListEntry {
 pub struct FileListEvent {
     pub item_id: String,
     pub files: Vec<FileListEntry>,
+    // Precomputed from `files` so consumers don't have to sum the list themselves.
+    pub total_bytes: u64,
+    pub file_count: u32,
+    pub warnings: Vec<ScanWarning>,
+    pub timestamp_unix_ms: u64,
+}
+
+// Folders with no files are only uploaded when the create empty folders preference is on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmptyFoldersCreatedEvent {
+    pub item_id: String,
+    pub count: u32,
+    // Relative to the item's root, '/' separated.
+    pub folder_paths: Vec<String>,
+    pub timestamp_unix_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedFilesEvent {
+    pub item_id: String,
+    pub files: Vec<ScanWarning>,
+    pub timestamp_unix_ms: u64,
+}
+
+// rclone NOTICE and WARNING lines for an item, sent once when it finishes if there were any.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemWarningsEvent {
+    pub item_id: String,
+    pub warnings: Vec<String>,
+    pub timestamp_unix_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SkipReason {
+    // rclone found an identical copy at the destination.
+    ExistsOnRemote,
+    // Symlinks are not followed during the local scan.
+    Symlink,
+    // The item was canceled before it started.
+    UserCanceled,
+    // A Google Docs shortcut stub left out under the doc shortcut policy.
+    DocShortcut,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DocShortcutHandling {
+    Skipped,
+    UploadedRaw,
+    // The document the stub points at was copied into the destination.
+    Copied,
+    // The copy was attempted and failed; the stub was skipped instead.
+    CopyFailed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocShortcutOutcome {
+    pub path: String,
+    pub doc_id: String,
+    pub handling: DocShortcutHandling,
+    pub message: Option<String>,
+}
+
+// How each Google Docs shortcut stub in an item was handled, sent before its final status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocShortcutsEvent {
+    pub item_id: String,
+    pub files: Vec<DocShortcutOutcome>,
+    pub timestamp_unix_ms: u64,
+}
+
+// Files or items left out on purpose. Folder skips are batched, so skipped_count may cover many
+// files and path is then the item's root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedEvent {
+    pub item_id: String,
+    pub path: String,
+    pub reason: SkipReason,
+    pub skipped_count: u32,
+    pub timestamp_unix_ms: u64,
+}
+
+// Files in a folder upload that started but have not sent any bytes yet. Sent whenever the
+// set changes, so an empty list clears earlier reports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StalledFilesEvent {
+    pub item_id: String,
+    pub stalled_paths: Vec<String>,
+    pub timestamp_unix_ms: u64,
+}
+
+// Sent at job start when the queue is larger than the service accounts' remaining daily quota.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaWarningEvent {
+    pub job_id: String,
+    pub feasibility: JobFeasibility,
+    pub timestamp_unix_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceAccountRemovedEvent {
+    pub path: String,
+    pub sa_email: Option<String>,
+    pub remaining: u32,
+    pub timestamp_unix_ms: u64,
+}
+
+// Sent at job start when the local clock disagrees with Google's by more than the tolerance.
+// rclone signs its own token requests, so a skewed clock makes every account fail auth.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClockSkewEvent {
+    pub job_id: String,
+    pub skew_secs: i64,
+    pub timestamp_unix_ms: u64,
+}
+
+// Sent when an account is benched after a rate-limit error; it is skipped until the cooldown
+// ends unless every other account is cooling down too.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceAccountCooldownEvent {
+    pub path: String,
+    pub sa_email: Option<String>,
+    pub remaining_secs: u64,
+    pub timestamp_unix_ms: u64,
+}
+
+// Sent when Google rejects a service account's key; the account sits out the rest of the job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceAccountDisabledEvent {
+    pub path: String,
+    pub sa_email: Option<String>,
+    pub reason: String,
+    pub remaining: u32,
+    pub timestamp_unix_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CompletedEvent {
     pub summary: Summary,
+    pub timestamp_unix_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,4 +254,19 @@ pub struct Summary {
     pub total: u32,
     pub succeeded: u32,
     pub failed: u32,
+    // Files and items reported through upload:skipped.
+    #[serde(default)]
+    pub skipped: u32,
+    // Job duration with and without the time spent paused.
+    #[serde(default)]
+    pub wall_time_ms: u64,
+    #[serde(default)]
+    pub active_time_ms: u64,
+}
+
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
 }
@@ -0,0 +1,339 @@
+use crate::upload::events::{
+    now_ms, ClockSkewEvent, DocShortcutsEvent, ItemStatusEvent, ItemWarningsEvent,
+    ServiceAccountCooldownEvent, ServiceAccountDisabledEvent, ServiceAccountRemovedEvent, Summary,
+};
+use crate::upload::snapshot;
+use serde::de::DeserializeOwned;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Listener, Manager};
+
+const LOGS_DIR: &str = "logs";
+
+// Open job logs by job id, oldest first. A job canceled by a newer start keeps its own entry
+// until it finishes, so its remaining item lines and completion line land in its own file.
+// Only lines that name neither a job nor an item go to the newest one.
+static ACTIVE: Mutex<Vec<(String, Sender<String>)>> = Mutex::new(Vec::new());
+
+pub fn logs_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {e}"))?
+        .join(LOGS_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create logs directory: {e}"))?;
+    Ok(dir)
+}
+
+pub fn job_log_path(app: &AppHandle, job_id: &str) -> Result<PathBuf, String> {
+    let is_job_id = job_id.starts_with("job-")
+        && job_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if !is_job_id {
+        return Err(format!("Invalid job id: {job_id:?}"));
+    }
+    Ok(logs_dir(app)?.join(format!("{job_id}.log")))
+}
+
+fn prune(dir: &Path, retention_days: u32) {
+    let max_age = Duration::from_secs(u64::from(retention_days) * 24 * 60 * 60);
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if !(name.starts_with("job-") && name.ends_with(".log")) {
+            continue;
+        }
+        let expired = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age > max_age);
+        if expired {
+            if let Err(e) = std::fs::remove_file(entry.path()) {
+                log::debug!(target: "rclone", "joblog.prune_failed file={} error={}", name, e);
+            }
+        }
+    }
+}
+
+// Runs on its own thread so a slow disk never stalls upload workers; senders only queue.
+fn write_lines(path: PathBuf, rx: Receiver<String>) {
+    let file = match std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+    {
+        Ok(file) => file,
+        Err(e) => {
+            log::warn!(target: "rclone", "joblog.open_failed path={:?} error={}", path, e);
+            return;
+        }
+    };
+    let mut writer = std::io::BufWriter::new(file);
+    while let Ok(line) = rx.recv() {
+        let _ = writeln!(writer, "{line}");
+        // Flush once the queue is drained so the file is current when a user opens it.
+        while let Ok(line) = rx.try_recv() {
+            let _ = writeln!(writer, "{line}");
+        }
+        let _ = writer.flush();
+    }
+}
+
+fn stamp(line: &str) -> String {
+    format!("{} {}", now_ms(), line)
+}
+
+pub fn write(line: impl AsRef<str>) {
+    let active = ACTIVE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((_, tx)) = active.last() {
+        let _ = tx.send(stamp(line.as_ref()));
+    }
+}
+
+pub fn write_for_job(job_id: &str, line: impl AsRef<str>) {
+    let active = ACTIVE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((_, tx)) = active.iter().find(|(id, _)| id == job_id) {
+        let _ = tx.send(stamp(line.as_ref()));
+    }
+}
+
+pub fn write_for_item(item_id: &str, line: impl AsRef<str>) {
+    match snapshot::job_for_item(item_id) {
+        Some(job_id) => write_for_job(&job_id, line),
+        None => write(line),
+    }
+}
+
+fn register(job_id: &str, tx: Sender<String>) {
+    ACTIVE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push((job_id.to_string(), tx));
+}
+
+pub fn start_job(app: &AppHandle, job_id: &str, destination_folder_id: &str, items: usize) {
+    let result = logs_dir(app).and_then(|dir| {
+        let retention_days = app
+            .state::<crate::PreferencesState>()
+            .0
+            .borrow()
+            .job_log_retention_days;
+        prune(&dir, retention_days);
+        job_log_path(app, job_id)
+    });
+    let path = match result {
+        Ok(path) => path,
+        Err(e) => {
+            log::warn!(target: "rclone", "joblog.unavailable job_id={} error={}", job_id, e);
+            return;
+        }
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let spawned = std::thread::Builder::new()
+        .name("job-log".to_string())
+        .spawn(move || write_lines(path, rx));
+    if let Err(e) = spawned {
+        log::warn!(target: "rclone", "joblog.thread_failed error={}", e);
+        return;
+    }
+    register(job_id, tx);
+    write_for_job(
+        job_id,
+        format!(
+        "job.started job_id={job_id} items={items} destination={destination_folder_id} version={}",
+            app.package_info().version
+        ),
+    );
+}
+
+// Dropping the sender lets the writer thread flush and exit.
+pub fn finish_job(job_id: &str, summary: &Summary) {
+    let mut active = ACTIVE.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(index) = active.iter().position(|(id, _)| id == job_id) else {
+        return;
+    };
+    let (_, tx) = active.remove(index);
+    let _ = tx.send(stamp(&format!(
+        "job.completed job_id={} total={} succeeded={} failed={} skipped={} wall_ms={} active_ms={}",
+        job_id,
+        summary.total,
+        summary.succeeded,
+        summary.failed,
+        summary.skipped,
+        summary.wall_time_ms,
+        summary.active_time_ms
+    )));
+}
+
+fn on_event<T: DeserializeOwned>(payload: &str, handle: impl Fn(T)) {
+    if let Ok(event) = serde_json::from_str::<T>(payload) {
+        handle(event);
+    }
+}
+
+pub fn install(app: &AppHandle) {
+    app.listen_any("upload:item_status", |e| {
+        on_event(e.payload(), |event: ItemStatusEvent| {
+            write_for_item(
+                &event.item_id,
+                format!(
+                    "item.status id={} status={} path={} sa={} message={}",
+                    event.item_id,
+                    event.status,
+                    event.path,
+                    event.sa_email.as_deref().unwrap_or("-"),
+                    event.message.as_deref().unwrap_or("-")
+                ),
+            )
+        })
+    });
+    app.listen_any("upload:sa_removed", |e| {
+        on_event(e.payload(), |event: ServiceAccountRemovedEvent| {
+            write(format!(
+                "sa.removed sa={} remaining={}",
+                event.sa_email.as_deref().unwrap_or("-"),
+                event.remaining
+            ))
+        })
+    });
+    app.listen_any("upload:clock_skew", |e| {
+        on_event(e.payload(), |event: ClockSkewEvent| {
+            write_for_job(
+                &event.job_id,
+                format!("clock.skewed skew_secs={}", event.skew_secs),
+            )
+        })
+    });
+    app.listen_any("upload:sa_cooldown", |e| {
+        on_event(e.payload(), |event: ServiceAccountCooldownEvent| {
+            write(format!(
+                "sa.cooldown sa={} remaining_secs={}",
+                event.sa_email.as_deref().unwrap_or("-"),
+                event.remaining_secs
+            ))
+        })
+    });
+    app.listen_any("upload:item_warnings", |e| {
+        on_event(e.payload(), |event: ItemWarningsEvent| {
+            write_for_item(
+                &event.item_id,
+                format!(
+                    "item.warnings id={} warnings={}",
+                    event.item_id,
+                    event.warnings.join(" | ")
+                ),
+            )
+        })
+    });
+    app.listen_any("upload:doc_shortcuts", |e| {
+        on_event(e.payload(), |event: DocShortcutsEvent| {
+            let files = event
+                .files
+                .iter()
+                .map(|file| format!("{}={:?}", file.path, file.handling))
+                .collect::<Vec<_>>();
+            write_for_item(
+                &event.item_id,
+                format!(
+                    "item.doc_shortcuts id={} files={}",
+                    event.item_id,
+                    files.join(" | ")
+                ),
+            )
+        })
+    });
+    app.listen_any("upload:sa_disabled", |e| {
+        on_event(e.payload(), |event: ServiceAccountDisabledEvent| {
+            write(format!(
+                "sa.disabled sa={} remaining={} reason={}",
+                event.sa_email.as_deref().unwrap_or("-"),
+                event.remaining,
+                event.reason
+            ))
+        })
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary() -> Summary {
+        Summary {
+            total: 1,
+            succeeded: 1,
+            failed: 0,
+            skipped: 0,
+            wall_time_ms: 0,
+            active_time_ms: 0,
+        }
+    }
+
+    fn drain(rx: &Receiver<String>) -> Vec<String> {
+        rx.try_iter().collect()
+    }
+
+    #[test]
+    fn a_late_finish_only_closes_its_own_log() {
+        let (old_tx, old_rx) = mpsc::channel();
+        let (new_tx, new_rx) = mpsc::channel();
+        register("job-old", old_tx);
+        register("job-new", new_tx);
+
+        // The canceled job finishes after the new one started.
+        finish_job("job-old", &summary());
+        let old_lines = drain(&old_rx);
+        assert_eq!(old_lines.len(), 1);
+        assert!(old_lines[0].contains("job.completed job_id=job-old"));
+
+        write_for_job("job-new", "item.status id=a");
+        let new_lines = drain(&new_rx);
+        assert_eq!(new_lines.len(), 1);
+        assert!(new_lines[0].ends_with("item.status id=a"));
+
+        finish_job("job-new", &summary());
+        assert!(drain(&new_rx)[0].contains("job_id=job-new"));
+        // Both senders are gone, so the writer threads would exit.
+        assert!(old_rx.recv().is_err());
+        assert!(new_rx.recv().is_err());
+        // Finishing twice is harmless.
+        finish_job("job-new", &summary());
+    }
+
+    #[test]
+    fn item_lines_follow_the_job_that_owns_the_item() {
+        let item: crate::upload::scheduler::QueueItemInput =
+            serde_json::from_value(serde_json::json!({
+                "id": "joblog-routing-item",
+                "path": "/data/a.bin",
+                "kind": "file",
+            }))
+            .unwrap();
+        let (old_tx, old_rx) = mpsc::channel();
+        let (new_tx, new_rx) = mpsc::channel();
+        snapshot::start_job("job-routing-old", 0, "dest", &[item]);
+        register("job-routing-old", old_tx);
+        snapshot::start_job("job-routing-new", 0, "dest", &[]);
+        register("job-routing-new", new_tx);
+
+        // The canceled job is still finishing its item after the new one started.
+        write_for_item("joblog-routing-item", "item.status id=joblog-routing-item");
+        assert_eq!(drain(&old_rx).len(), 1);
+        assert!(drain(&new_rx).is_empty());
+
+        finish_job("job-routing-old", &summary());
+        finish_job("job-routing-new", &summary());
+        snapshot::finish_job("job-routing-old", &summary());
+        snapshot::finish_job("job-routing-new", &summary());
+    }
+}
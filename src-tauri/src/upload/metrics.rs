@@ -0,0 +1,365 @@
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const LATENCY_BUCKETS_MS: [u64; 10] = [
+    100, 250, 500, 1_000, 2_500, 5_000, 10_000, 30_000, 60_000, 300_000,
+];
+const THROUGHPUT_WINDOW_SECS: usize = 60;
+
+static METRICS: UploadMetrics = UploadMetrics::new();
+
+pub fn metrics() -> &'static UploadMetrics {
+    &METRICS
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum RetryClass {
+    RateLimit,
+    Quota,
+    Forbidden,
+    // Google rejected the service account key (invalid_grant).
+    Auth,
+    // rclone stopped reporting progress and was restarted.
+    Stalled,
+    Other,
+}
+
+impl RetryClass {
+    pub fn classify(message: &str) -> Self {
+        let msg = message.to_ascii_lowercase();
+        if msg.starts_with("transfer stalled") {
+            RetryClass::Stalled
+        } else if msg.contains("invalid_grant") {
+            RetryClass::Auth
+        } else if msg.contains("quotaexceeded") || msg.contains("dailylimitexceeded") {
+            RetryClass::Quota
+        } else if msg.contains("ratelimit")
+            || msg.contains("rate limit")
+            || msg.contains("too many requests")
+            || msg.contains("http 429")
+        {
+            RetryClass::RateLimit
+        } else if msg.contains("http 403") {
+            RetryClass::Forbidden
+        } else {
+            RetryClass::Other
+        }
+    }
+}
+
+struct Histogram {
+    // One slot per bound in LATENCY_BUCKETS_MS plus a final overflow slot.
+    buckets: [AtomicU64; LATENCY_BUCKETS_MS.len() + 1],
+    count: AtomicU64,
+    sum_ms: AtomicU64,
+}
+
+impl Histogram {
+    const fn new() -> Self {
+        Self {
+            buckets: [const { AtomicU64::new(0) }; LATENCY_BUCKETS_MS.len() + 1],
+            count: AtomicU64::new(0),
+            sum_ms: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, duration: Duration) {
+        let ms = duration.as_millis().min(u64::MAX as u128) as u64;
+        let idx = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|bound| ms <= *bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.buckets[idx].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_ms.fetch_add(ms, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> HistogramSnapshot {
+        let buckets = self
+            .buckets
+            .iter()
+            .enumerate()
+            .map(|(idx, count)| HistogramBucket {
+                le_ms: LATENCY_BUCKETS_MS.get(idx).copied(),
+                count: count.load(Ordering::Relaxed),
+            })
+            .collect();
+        HistogramSnapshot {
+            count: self.count.load(Ordering::Relaxed),
+            sum_ms: self.sum_ms.load(Ordering::Relaxed),
+            buckets,
+        }
+    }
+
+    fn reset(&self) {
+        for bucket in &self.buckets {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        self.count.store(0, Ordering::Relaxed);
+        self.sum_ms.store(0, Ordering::Relaxed);
+    }
+}
+
+// Per-second byte counters kept in a fixed ring so recording never allocates or locks.
+struct ThroughputRing {
+    seconds: [AtomicU64; THROUGHPUT_WINDOW_SECS],
+    bytes: [AtomicU64; THROUGHPUT_WINDOW_SECS],
+}
+
+impl ThroughputRing {
+    const fn new() -> Self {
+        Self {
+            seconds: [const { AtomicU64::new(0) }; THROUGHPUT_WINDOW_SECS],
+            bytes: [const { AtomicU64::new(0) }; THROUGHPUT_WINDOW_SECS],
+        }
+    }
+
+    fn record(&self, bytes: u64) {
+        let now = unix_now_secs();
+        let idx = (now % THROUGHPUT_WINDOW_SECS as u64) as usize;
+        let slot_second = self.seconds[idx].load(Ordering::Relaxed);
+        if slot_second != now
+            && self.seconds[idx]
+                .compare_exchange(slot_second, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            self.bytes[idx].store(0, Ordering::Relaxed);
+        }
+        self.bytes[idx].fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> Vec<ThroughputSample> {
+        let now = unix_now_secs();
+        let oldest = now.saturating_sub(THROUGHPUT_WINDOW_SECS as u64 - 1);
+        let mut samples = self
+            .seconds
+            .iter()
+            .zip(self.bytes.iter())
+            .map(|(second, bytes)| ThroughputSample {
+                timestamp_unix: second.load(Ordering::Relaxed),
+                bytes_per_sec: bytes.load(Ordering::Relaxed),
+            })
+            .filter(|sample| sample.timestamp_unix >= oldest && sample.timestamp_unix > 0)
+            .collect::<Vec<_>>();
+        samples.sort_by_key(|sample| sample.timestamp_unix);
+        samples
+    }
+
+    fn reset(&self) {
+        for (second, bytes) in self.seconds.iter().zip(self.bytes.iter()) {
+            second.store(0, Ordering::Relaxed);
+            bytes.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+pub struct UploadMetrics {
+    bytes_uploaded: AtomicU64,
+    rclone_processes_spawned: AtomicU64,
+    rclone_processes_active: AtomicU64,
+    rclone_processes_failed: AtomicU64,
+    retries_rate_limit: AtomicU64,
+    retries_quota: AtomicU64,
+    retries_forbidden: AtomicU64,
+    retries_auth: AtomicU64,
+    retries_stalled: AtomicU64,
+    retries_other: AtomicU64,
+    transfer_latency: Histogram,
+    throughput: ThroughputRing,
+    // Bytes and unpaused time of finished jobs, for the average upload speed.
+    finished_job_bytes: AtomicU64,
+    finished_job_active_ms: AtomicU64,
+}
+
+impl UploadMetrics {
+    const fn new() -> Self {
+        Self {
+            bytes_uploaded: AtomicU64::new(0),
+            rclone_processes_spawned: AtomicU64::new(0),
+            rclone_processes_active: AtomicU64::new(0),
+            rclone_processes_failed: AtomicU64::new(0),
+            retries_rate_limit: AtomicU64::new(0),
+            retries_quota: AtomicU64::new(0),
+            retries_forbidden: AtomicU64::new(0),
+            retries_auth: AtomicU64::new(0),
+            retries_stalled: AtomicU64::new(0),
+            retries_other: AtomicU64::new(0),
+            transfer_latency: Histogram::new(),
+            throughput: ThroughputRing::new(),
+            finished_job_bytes: AtomicU64::new(0),
+            finished_job_active_ms: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record_bytes(&self, bytes: u64) {
+        if bytes == 0 {
+            return;
+        }
+        self.bytes_uploaded.fetch_add(bytes, Ordering::Relaxed);
+        self.throughput.record(bytes);
+    }
+
+    pub fn record_process_spawned(&self) {
+        self.rclone_processes_spawned
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn start_transfer(&'static self) -> TransferGuard {
+        self.record_process_spawned();
+        self.rclone_processes_active.fetch_add(1, Ordering::Relaxed);
+        TransferGuard {
+            metrics: self,
+            started: Instant::now(),
+            success: false,
+        }
+    }
+
+    fn record_transfer_finished(&self, duration: Duration, success: bool) {
+        let _ = self.rclone_processes_active.fetch_update(
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+            |active| Some(active.saturating_sub(1)),
+        );
+        if !success {
+            self.rclone_processes_failed.fetch_add(1, Ordering::Relaxed);
+        }
+        self.transfer_latency.record(duration);
+    }
+
+    pub fn record_job_finished(&self, bytes: u64, active_time_ms: u64) {
+        self.finished_job_bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.finished_job_active_ms
+            .fetch_add(active_time_ms, Ordering::Relaxed);
+    }
+
+    pub fn record_retry(&self, class: RetryClass) {
+        let counter = match class {
+            RetryClass::RateLimit => &self.retries_rate_limit,
+            RetryClass::Quota => &self.retries_quota,
+            RetryClass::Forbidden => &self.retries_forbidden,
+            RetryClass::Auth => &self.retries_auth,
+            RetryClass::Stalled => &self.retries_stalled,
+            RetryClass::Other => &self.retries_other,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            captured_at_unix: unix_now_secs(),
+            bytes_uploaded: self.bytes_uploaded.load(Ordering::Relaxed),
+            rclone_processes_spawned: self.rclone_processes_spawned.load(Ordering::Relaxed),
+            rclone_processes_active: self.rclone_processes_active.load(Ordering::Relaxed),
+            rclone_processes_failed: self.rclone_processes_failed.load(Ordering::Relaxed),
+            retries: RetryCounts {
+                rate_limit: self.retries_rate_limit.load(Ordering::Relaxed),
+                quota: self.retries_quota.load(Ordering::Relaxed),
+                forbidden: self.retries_forbidden.load(Ordering::Relaxed),
+                auth: self.retries_auth.load(Ordering::Relaxed),
+                stalled: self.retries_stalled.load(Ordering::Relaxed),
+                other: self.retries_other.load(Ordering::Relaxed),
+            },
+            transfer_latency: self.transfer_latency.snapshot(),
+            throughput: self.throughput.snapshot(),
+            average_bytes_per_sec: {
+                let bytes = self.finished_job_bytes.load(Ordering::Relaxed);
+                let active_ms = self.finished_job_active_ms.load(Ordering::Relaxed);
+                (active_ms > 0).then(|| bytes.saturating_mul(1000) / active_ms)
+            },
+        }
+    }
+
+    // Active process count is a live gauge, so it survives a reset.
+    pub fn reset(&self) {
+        self.bytes_uploaded.store(0, Ordering::Relaxed);
+        self.finished_job_bytes.store(0, Ordering::Relaxed);
+        self.finished_job_active_ms.store(0, Ordering::Relaxed);
+        self.rclone_processes_spawned.store(0, Ordering::Relaxed);
+        self.rclone_processes_failed.store(0, Ordering::Relaxed);
+        self.retries_rate_limit.store(0, Ordering::Relaxed);
+        self.retries_quota.store(0, Ordering::Relaxed);
+        self.retries_forbidden.store(0, Ordering::Relaxed);
+        self.retries_auth.store(0, Ordering::Relaxed);
+        self.retries_stalled.store(0, Ordering::Relaxed);
+        self.retries_other.store(0, Ordering::Relaxed);
+        self.transfer_latency.reset();
+        self.throughput.reset();
+    }
+}
+
+// Records the transfer as finished when dropped, so early returns are still counted.
+pub struct TransferGuard {
+    metrics: &'static UploadMetrics,
+    started: Instant,
+    success: bool,
+}
+
+impl TransferGuard {
+    pub fn succeed(&mut self) {
+        self.success = true;
+    }
+}
+
+impl Drop for TransferGuard {
+    fn drop(&mut self) {
+        self.metrics
+            .record_transfer_finished(self.started.elapsed(), self.success);
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsSnapshot {
+    pub captured_at_unix: u64,
+    pub bytes_uploaded: u64,
+    pub rclone_processes_spawned: u64,
+    pub rclone_processes_active: u64,
+    pub rclone_processes_failed: u64,
+    pub retries: RetryCounts,
+    pub transfer_latency: HistogramSnapshot,
+    pub throughput: Vec<ThroughputSample>,
+    // Over finished jobs, counting only the time they were not paused.
+    pub average_bytes_per_sec: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryCounts {
+    pub rate_limit: u64,
+    pub quota: u64,
+    pub forbidden: u64,
+    pub auth: u64,
+    pub stalled: u64,
+    pub other: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistogramSnapshot {
+    pub count: u64,
+    pub sum_ms: u64,
+    pub buckets: Vec<HistogramBucket>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistogramBucket {
+    /// Upper bound of the bucket in milliseconds; `None` for the overflow bucket.
+    pub le_ms: Option<u64>,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThroughputSample {
+    pub timestamp_unix: u64,
+    pub bytes_per_sec: u64,
+}
+
+fn unix_now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -1,3 +1,15 @@
+pub mod cooldown;
 pub mod events;
+pub mod joblog;
+pub mod metrics;
+pub mod notify;
+pub mod orphans;
+pub mod quota;
 pub mod rclone;
+pub mod runner;
 pub mod scheduler;
+pub mod snapshot;
+pub mod speed;
+pub mod taskbar;
+pub mod timing;
+pub mod warnings;
@@ -0,0 +1,59 @@
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::sync::mpsc;
+
+const BATCH_WINDOW: Duration = Duration::from_secs(2);
+const BATCH_THRESHOLD: usize = 5;
+
+// Collects item completions for a short window so a burst of finished items
+// produces one summary notification instead of a storm.
+pub struct NotificationBatcher {
+    tx: mpsc::UnboundedSender<String>,
+}
+
+impl NotificationBatcher {
+    pub fn new(app: AppHandle) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(run_batcher(app, rx));
+        Self { tx }
+    }
+
+    pub fn item_completed(&self, name: String) {
+        let _ = self.tx.send(name);
+    }
+}
+
+async fn run_batcher(app: AppHandle, mut rx: mpsc::UnboundedReceiver<String>) {
+    while let Some(first) = rx.recv().await {
+        let mut names = vec![first];
+        let window = tokio::time::sleep(BATCH_WINDOW);
+        tokio::pin!(window);
+
+        loop {
+            tokio::select! {
+                _ = &mut window => break,
+                next = rx.recv() => match next {
+                    Some(name) => names.push(name),
+                    None => break,
+                },
+            }
+        }
+
+        if names.len() > BATCH_THRESHOLD {
+            send(&app, format!("{} files uploaded", names.len())).await;
+        } else {
+            for name in names {
+                send(&app, format!("'{name}' uploaded successfully")).await;
+            }
+        }
+    }
+}
+
+async fn send(app: &AppHandle, body: String) {
+    if let Err(e) =
+        crate::send_native_notification(app.clone(), "Upload Complete".to_string(), Some(body))
+            .await
+    {
+        log::warn!(target: "rclone", "notify.failed error={}", e);
+    }
+}
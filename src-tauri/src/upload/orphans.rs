@@ -0,0 +1,248 @@
+use crate::json_state;
+use crate::upload::events::now_ms;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+use tauri::{AppHandle, Emitter};
+
+const SESSION_FILE: &str = "rclone-session.json";
+
+// An rclone process spawned by this app. The fingerprint identifies its exact command line,
+// so a recycled PID belonging to some other process is never mistaken for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackedProcess {
+    pub pid: u32,
+    pub item_id: String,
+    pub fingerprint: String,
+    pub started_unix_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedUploadsDetectedEvent {
+    pub processes: Vec<TrackedProcess>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionFile {
+    processes: Vec<TrackedProcess>,
+}
+
+struct Session {
+    path: Option<PathBuf>,
+    running: Vec<TrackedProcess>,
+    // Still-running processes left behind by a previous session.
+    orphans: Vec<TrackedProcess>,
+}
+
+static SESSION: Mutex<Session> = Mutex::new(Session {
+    path: None,
+    running: Vec::new(),
+    orphans: Vec::new(),
+});
+
+// Removes the process from the session file once its rclone run is over.
+pub struct ProcessGuard {
+    pid: u32,
+}
+
+impl Drop for ProcessGuard {
+    fn drop(&mut self) {
+        let mut session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+        session.running.retain(|process| process.pid != self.pid);
+        persist(&session);
+    }
+}
+
+// FNV-1a over the arguments; stable across builds, unlike std's hasher.
+fn fingerprint<'a>(args: impl IntoIterator<Item = &'a str>) -> String {
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    for arg in args {
+        for byte in arg.bytes().chain(std::iter::once(0)) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    format!("{hash:016x}")
+}
+
+fn persist(session: &Session) {
+    let Some(path) = session.path.as_ref() else {
+        return;
+    };
+    let file = SessionFile {
+        processes: session
+            .running
+            .iter()
+            .chain(&session.orphans)
+            .cloned()
+            .collect(),
+    };
+    if let Err(e) = json_state::save(path, &file, "rclone session") {
+        log::warn!(target: "rclone", "session.write_failed error={}", e);
+    }
+}
+
+pub fn track(pid: u32, item_id: &str, args: &[String]) -> ProcessGuard {
+    let mut session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+    session.running.push(TrackedProcess {
+        pid,
+        item_id: item_id.to_string(),
+        fingerprint: fingerprint(args.iter().map(String::as_str)),
+        started_unix_ms: now_ms(),
+    });
+    persist(&session);
+    ProcessGuard { pid }
+}
+
+fn is_same_process(system: &mut System, process: &TrackedProcess) -> bool {
+    let pid = Pid::from_u32(process.pid);
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        true,
+        ProcessRefreshKind::nothing().with_cmd(UpdateKind::Always),
+    );
+    system.process(pid).is_some_and(|running| {
+        let args = running
+            .cmd()
+            .iter()
+            .skip(1)
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        fingerprint(args.iter().map(String::as_str)) == process.fingerprint
+    })
+}
+
+// Reads the previous session's file and keeps its rclone processes that are still running.
+// The webview is not listening yet, so it asks for them through list_orphaned_uploads.
+pub fn init(app: &AppHandle) {
+    let loaded = json_state::state_path(app, SESSION_FILE)
+        .and_then(|path| Ok((json_state::load(&path, "rclone session")?, path)));
+    let (previous, path): (SessionFile, PathBuf) = match loaded {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            log::warn!(target: "rclone", "session.unavailable error={}", e);
+            return;
+        }
+    };
+
+    let mut system = System::new();
+    let orphans: Vec<TrackedProcess> = previous
+        .processes
+        .into_iter()
+        .filter(|process| is_same_process(&mut system, process))
+        .collect();
+    if !orphans.is_empty() {
+        log::warn!(
+            target: "rclone",
+            "session.orphans_detected count={}",
+            orphans.len()
+        );
+    }
+
+    let mut session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+    session.path = Some(path);
+    session.orphans = orphans;
+    persist(&session);
+}
+
+// Orphans that are still running; ones that exited on their own are forgotten.
+pub fn orphans() -> Vec<TrackedProcess> {
+    let mut session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+    if session.orphans.is_empty() {
+        return Vec::new();
+    }
+    let mut system = System::new();
+    let before = session.orphans.len();
+    session
+        .orphans
+        .retain(|process| is_same_process(&mut system, process));
+    if session.orphans.len() != before {
+        persist(&session);
+    }
+    session.orphans.clone()
+}
+
+// Refuses to start a job while orphans could still be uploading the same files, and asks the
+// webview to offer killing them.
+pub fn ensure_none_pending(app: &AppHandle) -> Result<(), String> {
+    let processes = orphans();
+    if processes.is_empty() {
+        return Ok(());
+    }
+    let count = processes.len();
+    let _ = app.emit(
+        "orphaned_uploads_detected",
+        OrphanedUploadsDetectedEvent { processes },
+    );
+    Err(format!(
+        "{count} rclone upload(s) from a previous session are still running; stop or keep them before starting a new upload"
+    ))
+}
+
+// Leaves the orphans running but stops tracking them, so new jobs may start.
+pub fn dismiss_orphans() {
+    let mut session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+    for process in std::mem::take(&mut session.orphans) {
+        log::info!(
+            target: "rclone",
+            "session.orphan_kept pid={} id={}",
+            process.pid,
+            process.item_id
+        );
+    }
+    persist(&session);
+}
+
+// Kills the detected orphans, re-checking each command line first. Returns how many were
+// terminated.
+pub fn kill_orphans() -> u32 {
+    let mut session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+    let mut system = System::new();
+    let mut killed = 0;
+    for process in std::mem::take(&mut session.orphans) {
+        if !is_same_process(&mut system, &process) {
+            continue;
+        }
+        match system.process(Pid::from_u32(process.pid)) {
+            Some(running) if running.kill() => {
+                log::info!(
+                    target: "rclone",
+                    "session.orphan_killed pid={} id={}",
+                    process.pid,
+                    process.item_id
+                );
+                killed += 1;
+            }
+            _ => log::warn!(
+                target: "rclone",
+                "session.orphan_kill_failed pid={} id={}",
+                process.pid,
+                process.item_id
+            ),
+        }
+    }
+    persist(&session);
+    killed
+}
+
+// On a clean exit nothing is left to recover: our own rclone children are stopped rather
+// than left running unobserved, and the session file is removed.
+pub fn shutdown() {
+    let mut session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+    let mut system = System::new();
+    for process in std::mem::take(&mut session.running) {
+        if is_same_process(&mut system, &process) {
+            if let Some(running) = system.process(Pid::from_u32(process.pid)) {
+                running.kill();
+            }
+        }
+    }
+    // Guards dropped after this point must not write the file back.
+    if let Some(path) = session.path.take() {
+        let _ = std::fs::remove_file(path);
+    }
+}
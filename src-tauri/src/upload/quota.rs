@@ -0,0 +1,239 @@
+use crate::json_state;
+use crate::upload::events::now_ms;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+const LEDGER_FILE: &str = "sa-quota.json";
+// Drive accepts at most 750 GB of uploads per account per day.
+pub const DAILY_UPLOAD_LIMIT_BYTES: u64 = 750 * 1000 * 1000 * 1000;
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+// Progress is recorded many times a second; the file only needs to survive a restart.
+const PERSIST_INTERVAL: Duration = Duration::from_secs(30);
+// Outcome counts halve every six hours, so yesterday's throttling barely counts today.
+const HEALTH_HALF_LIFE_MS: f64 = 6.0 * 60.0 * 60.0 * 1000.0;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LedgerFile {
+    // Days since the Unix epoch (UTC); Drive's quota window resets at midnight UTC.
+    day: u64,
+    used_bytes: BTreeMap<String, u64>,
+    // Decayed transfer outcomes per account; kept across the daily roll-over.
+    #[serde(default)]
+    health: BTreeMap<String, AccountHealth>,
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountHealth {
+    successes: f64,
+    failures: f64,
+    updated_unix_ms: u64,
+}
+
+impl AccountHealth {
+    fn decayed(self, now: u64) -> Self {
+        let elapsed = now.saturating_sub(self.updated_unix_ms) as f64;
+        let factor = 0.5_f64.powf(elapsed / HEALTH_HALF_LIFE_MS);
+        AccountHealth {
+            successes: self.successes * factor,
+            failures: self.failures * factor,
+            updated_unix_ms: now,
+        }
+    }
+}
+
+struct Ledger {
+    path: Option<PathBuf>,
+    file: LedgerFile,
+    last_persist: Option<Instant>,
+}
+
+static LEDGER: Mutex<Ledger> = Mutex::new(Ledger {
+    path: None,
+    file: LedgerFile {
+        day: 0,
+        used_bytes: BTreeMap::new(),
+        health: BTreeMap::new(),
+    },
+    last_persist: None,
+});
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountCapacity {
+    pub path: String,
+    pub email: Option<String>,
+    pub used_bytes: u64,
+    pub remaining_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobFeasibility {
+    pub feasible: bool,
+    pub total_bytes: u64,
+    pub remaining_bytes: u64,
+    pub accounts_with_capacity: u32,
+    // Accounts the job would use up, fullest-remaining first; beyond the pool when infeasible.
+    pub projected_accounts_needed: u32,
+    pub accounts: Vec<AccountCapacity>,
+}
+
+fn today() -> u64 {
+    now_ms() / DAY_MS
+}
+
+fn roll_over(ledger: &mut Ledger) {
+    let day = today();
+    if ledger.file.day != day {
+        ledger.file.day = day;
+        ledger.file.used_bytes.clear();
+    }
+}
+
+fn persist(ledger: &mut Ledger) {
+    let Some(path) = ledger.path.as_ref() else {
+        return;
+    };
+    ledger.last_persist = Some(Instant::now());
+    if let Err(e) = json_state::save(path, &ledger.file, "quota ledger") {
+        log::warn!(target: "rclone", "quota.write_failed error={}", e);
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    // An unparseable ledger is moved aside rather than silently replaced.
+    let loaded = json_state::state_path(app, LEDGER_FILE)
+        .and_then(|path| Ok((json_state::load(&path, "quota ledger")?, path)));
+    let (file, path): (LedgerFile, PathBuf) = match loaded {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            log::warn!(target: "rclone", "quota.unavailable error={}", e);
+            return;
+        }
+    };
+
+    let mut ledger = LEDGER.lock().unwrap_or_else(|e| e.into_inner());
+    ledger.path = Some(path);
+    ledger.file = file;
+    roll_over(&mut ledger);
+}
+
+// Counts bytes sent with the given account towards today's quota.
+pub fn record(email: Option<&str>, bytes: u64) {
+    let Some(email) = email else {
+        return;
+    };
+    if bytes == 0 {
+        return;
+    }
+    let mut ledger = LEDGER.lock().unwrap_or_else(|e| e.into_inner());
+    roll_over(&mut ledger);
+    *ledger.file.used_bytes.entry(email.to_string()).or_default() += bytes;
+    if ledger
+        .last_persist
+        .is_none_or(|at| at.elapsed() >= PERSIST_INTERVAL)
+    {
+        persist(&mut ledger);
+    }
+}
+
+// Counts a finished transfer attempt for or against the account.
+pub fn record_outcome(email: Option<&str>, success: bool) {
+    let Some(email) = email else {
+        return;
+    };
+    let now = now_ms();
+    let mut ledger = LEDGER.lock().unwrap_or_else(|e| e.into_inner());
+    let entry = ledger.file.health.entry(email.to_string()).or_default();
+    let mut health = entry.decayed(now);
+    if success {
+        health.successes += 1.0;
+    } else {
+        health.failures += 1.0;
+    }
+    *entry = health;
+    if ledger
+        .last_persist
+        .is_none_or(|at| at.elapsed() >= PERSIST_INTERVAL)
+    {
+        persist(&mut ledger);
+    }
+}
+
+// Share of recent attempts that failed, between 0 and 1. The extra success in the denominator
+// keeps a single failure on an otherwise unused account from reading as a 100% failure rate.
+pub fn failure_rate(email: Option<&str>) -> f64 {
+    let Some(email) = email else {
+        return 0.0;
+    };
+    let ledger = LEDGER.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(health) = ledger.file.health.get(email) else {
+        return 0.0;
+    };
+    let health = health.decayed(now_ms());
+    health.failures / (health.successes + health.failures + 1.0)
+}
+
+pub fn flush() {
+    let mut ledger = LEDGER.lock().unwrap_or_else(|e| e.into_inner());
+    persist(&mut ledger);
+}
+
+// Accounts without a client_email cannot be tracked and are assumed to have a full day left.
+pub fn estimate<'a>(
+    accounts: impl IntoIterator<Item = (String, Option<&'a str>)>,
+    total_bytes: u64,
+) -> JobFeasibility {
+    let mut ledger = LEDGER.lock().unwrap_or_else(|e| e.into_inner());
+    roll_over(&mut ledger);
+    let accounts: Vec<AccountCapacity> = accounts
+        .into_iter()
+        .map(|(path, email)| {
+            let used_bytes = email
+                .and_then(|email| ledger.file.used_bytes.get(email))
+                .copied()
+                .unwrap_or(0);
+            AccountCapacity {
+                path,
+                email: email.map(str::to_string),
+                used_bytes,
+                remaining_bytes: DAILY_UPLOAD_LIMIT_BYTES.saturating_sub(used_bytes),
+            }
+        })
+        .collect();
+    drop(ledger);
+
+    let remaining_bytes = accounts.iter().map(|a| a.remaining_bytes).sum::<u64>();
+    let accounts_with_capacity = accounts.iter().filter(|a| a.remaining_bytes > 0).count() as u32;
+
+    let mut remaining: Vec<u64> = accounts.iter().map(|a| a.remaining_bytes).collect();
+    remaining.sort_unstable_by(|a, b| b.cmp(a));
+    let mut covered = 0_u64;
+    let mut projected_accounts_needed = 0_u32;
+    for capacity in remaining.into_iter().filter(|c| *c > 0) {
+        if covered >= total_bytes {
+            break;
+        }
+        covered += capacity;
+        projected_accounts_needed += 1;
+    }
+    if covered < total_bytes {
+        let shortfall = total_bytes - covered;
+        projected_accounts_needed += shortfall.div_ceil(DAILY_UPLOAD_LIMIT_BYTES) as u32;
+    }
+
+    JobFeasibility {
+        feasible: total_bytes <= remaining_bytes,
+        total_bytes,
+        remaining_bytes,
+        accounts_with_capacity,
+        projected_accounts_needed,
+        accounts,
+    }
+}
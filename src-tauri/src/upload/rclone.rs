@@ -1,29 +1,152 @@
+use crate::fs_scan::{self, DocShortcut, ScanOptions, ScanResult, ScannedFile};
+use crate::upload::cooldown;
 use crate::upload::events::{
-    CompletedEvent, FileListEntry, FileListEvent, FileProgressEvent, ItemStatusEvent,
-    ProgressEvent, Summary,
+    self, now_ms, ClockSkewEvent, CompletedEvent, DocShortcutHandling, DocShortcutOutcome,
+    DocShortcutsEvent, EmptyFoldersCreatedEvent, FileListEntry, FileListEvent, FileProgressEvent,
+    ItemStatusEvent, ItemWarningsEvent, JobStartedEvent, ProgressEvent, QuotaWarningEvent,
+    ServiceAccountCooldownEvent, ServiceAccountDisabledEvent, ServiceAccountRemovedEvent,
+    SkipReason, SkippedEvent, SkippedFilesEvent, StalledFilesEvent, Summary,
 };
-use crate::upload::scheduler::{wait_if_paused, QueueItemInput, UploadControlHandle};
+use crate::upload::joblog;
+use crate::upload::metrics::{metrics, RetryClass};
+use crate::upload::notify::NotificationBatcher;
+use crate::upload::orphans;
+use crate::upload::quota::{self, JobFeasibility};
+use crate::upload::runner::{self, RcloneChild, RcloneProcess, RcloneRunner, RcloneStream};
+use crate::upload::scheduler::{wait_if_paused, QueueItemInput, UploadControlHandle, UploadMode};
+use crate::upload::snapshot;
+use crate::upload::speed;
+use crate::upload::timing::{self, PauseClock};
+use crate::upload::warnings;
 use regex::Regex;
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
+use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::Stdio;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
-use tauri::{AppHandle, Emitter};
-use tokio::io::AsyncReadExt;
-use tokio::process::Command;
-use tokio::sync::{mpsc, watch, Mutex, Semaphore};
-use walkdir::WalkDir;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::{mpsc, oneshot, watch, Mutex, Semaphore};
+use unicode_normalization::UnicodeNormalization;
+
+// Below this average file size, per-process startup and SA token costs outweigh the
+// benefit of rotating service accounts per file.
+const SINGLE_PROCESS_MAX_AVG_FILE_BYTES: u64 = 8 * 1024 * 1024;
+
+// How a folder item is handed to rclone. Per-file runs one process per file and retries a
+// failed file with the next service account. Single-process copies the whole folder in one
+// run with --transfers and retries the whole run; rclone then skips files already uploaded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FolderUploadStrategy {
+    PerFile,
+    SingleProcess,
+    #[default]
+    Auto,
+}
 
-#[derive(Clone, Debug)]
+// What to do with sibling names that differ only by case or Unicode composition. Drive keeps
+// both as distinct files, but they clash once synced to a case-insensitive filesystem.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NameCollisionPolicy {
+    // Upload every file under its own name.
+    #[default]
+    KeepBoth,
+    // Fail the item before anything is uploaded.
+    Fail,
+    // Upload only the first name in sorted order and skip the others.
+    FirstWins,
+}
+
+// Which service account the next transfer is handed. Weighted works like least-recently-used
+// but lets accounts that failed recently wait proportionally longer for their next turn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SaSelectionStrategy {
+    // Walks the pool in folder order.
+    RoundRobin,
+    #[default]
+    LeastRecentlyUsed,
+    Weighted,
+}
+
+// How Google Docs shortcut stubs (see fs_scan::DocShortcut) found in an item are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DocShortcutPolicy {
+    // Leave them out and warn; the destination would only get a dead pointer.
+    #[default]
+    Skip,
+    // Upload the stub file itself like any other file.
+    UploadRaw,
+    // Copy the document the stub points at server-side; skipped as above if that fails,
+    // usually because no service account can read the document.
+    CopyDocument,
+}
+
+impl FolderUploadStrategy {
+    fn use_single_process(self, entries: &[ScannedFile]) -> bool {
+        match self {
+            FolderUploadStrategy::PerFile => false,
+            FolderUploadStrategy::SingleProcess => true,
+            FolderUploadStrategy::Auto => {
+                let total: u64 = entries.iter().map(|entry| entry.size).sum();
+                let avg = total / (entries.len().max(1) as u64);
+                avg < SINGLE_PROCESS_MAX_AVG_FILE_BYTES
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct RclonePreferences {
     pub rclone_path: String,
     pub remote_name: String,
     pub drive_chunk_size_mib: u32,
     pub transfers: u16,
     pub checkers: u16,
+    pub notify_per_item_completion: bool,
+    pub fail_on_unreadable: bool,
+    pub no_traverse: bool,
+    pub auto_no_traverse_threshold_files: Option<u32>,
+    pub use_mmap: bool,
+    pub buffer_size_mib: u32,
+    pub folder_upload_strategy: FolderUploadStrategy,
+    pub stall_timeout_minutes: u32,
+    pub log_level: String,
+    pub name_collision_policy: NameCollisionPolicy,
+    pub sa_selection_strategy: SaSelectionStrategy,
+    pub doc_shortcut_policy: DocShortcutPolicy,
+    pub create_empty_folders: bool,
+}
+
+impl RclonePreferences {
+    // Progress is parsed from rclone's stats lines, which are only printed when the log level
+    // lets them through, so copy commands never go quieter than NOTICE.
+    fn copy_log_level(&self) -> &str {
+        match self.log_level.as_str() {
+            "ERROR" => "NOTICE",
+            level => level,
+        }
+    }
+
+    fn stats_log_level(&self) -> &str {
+        match self.copy_log_level() {
+            "NOTICE" => "NOTICE",
+            _ => "INFO",
+        }
+    }
+
+    // The threshold turns --no-traverse on for large items even when the flag is off.
+    fn no_traverse_for(&self, item_file_count: usize) -> bool {
+        self.no_traverse
+            || self
+                .auto_no_traverse_threshold_files
+                .is_some_and(|threshold| item_file_count > threshold as usize)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -33,11 +156,92 @@ struct ServiceAccountFile {
     last_used: u64,
 }
 
-#[derive(Clone, Debug)]
-struct FolderFileEntry {
-    path: PathBuf,
-    rel_path: String,
-    size: u64,
+#[derive(Debug, Default)]
+struct ServiceAccountPool {
+    accounts: Vec<ServiceAccountFile>,
+    // Rejected by Google earlier in the job; reloads from the folder leave them out.
+    disabled: HashSet<PathBuf>,
+}
+
+// The running job's service account pool and selection counter, for get_sa_pool_stats.
+#[derive(Default)]
+pub struct DrivePoolState(Mutex<Option<ActivePool>>);
+
+impl DrivePoolState {
+    // A job that ends after its successor started leaves the successor's pool in place.
+    async fn clear(&self, job_id: &str) {
+        let mut active = self.0.lock().await;
+        if active
+            .as_ref()
+            .is_some_and(|active| active.job_id == job_id)
+        {
+            *active = None;
+        }
+    }
+}
+
+struct ActivePool {
+    job_id: String,
+    pool: Arc<Mutex<ServiceAccountPool>>,
+    tick: Arc<AtomicU64>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceAccountPoolStats {
+    pub total_accounts: usize,
+    // How many times an account has been handed out in this job.
+    pub selections: u64,
+    pub accounts: Vec<ServiceAccountStats>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceAccountStats {
+    pub path: String,
+    pub email: Option<String>,
+    // Selection number of the account's most recent use; 0 if not used yet.
+    pub last_used: u64,
+    pub cooldown_remaining_secs: Option<u64>,
+    pub failure_rate: f64,
+}
+
+// None when no job is running.
+pub async fn service_account_pool_stats(state: &DrivePoolState) -> Option<ServiceAccountPoolStats> {
+    let (pool, tick) = state
+        .0
+        .lock()
+        .await
+        .as_ref()
+        .map(|active| (active.pool.clone(), active.tick.clone()))?;
+    let guard = pool.lock().await;
+    let accounts: Vec<ServiceAccountStats> = guard
+        .accounts
+        .iter()
+        .map(|entry| ServiceAccountStats {
+            path: entry.path.to_string_lossy().to_string(),
+            email: entry.email.clone(),
+            last_used: entry.last_used,
+            cooldown_remaining_secs: cooldown::until(&cooldown::key(
+                &entry.path,
+                entry.email.as_deref(),
+            ))
+            .map(|until| until.saturating_duration_since(Instant::now()).as_secs()),
+            failure_rate: quota::failure_rate(entry.email.as_deref()),
+        })
+        .collect();
+    Some(ServiceAccountPoolStats {
+        total_accounts: accounts.len(),
+        selections: tick.load(Ordering::Relaxed),
+        accounts,
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FileOutcome {
+    Uploaded,
+    // rclone checked the destination and found nothing to transfer
+    Skipped,
 }
 
 #[derive(Debug)]
@@ -45,6 +249,8 @@ struct FolderProgressTracker {
     total_bytes: u64,
     current_bytes: u64,
     by_file: HashMap<String, u64>,
+    // When each unfinished file was first seen in this attempt; cleared once it completes.
+    file_started_at: HashMap<String, Instant>,
 }
 
 impl FolderProgressTracker {
@@ -53,10 +259,41 @@ impl FolderProgressTracker {
             total_bytes,
             current_bytes: 0,
             by_file: HashMap::new(),
+            file_started_at: HashMap::new(),
         }
     }
 
+    // Restarts the stall clock for a file that is being (re)tried.
+    fn start(&mut self, file_key: &str) -> (u64, u64) {
+        self.file_started_at
+            .insert(file_key.to_string(), Instant::now());
+        self.update(file_key, 0)
+    }
+
+    fn finish(&mut self, file_key: &str, bytes: u64) -> (u64, u64) {
+        self.file_started_at.remove(file_key);
+        self.update(file_key, bytes)
+    }
+
+    // Files that started but have not sent a single byte within the threshold.
+    fn stalled_files(&self, stall_threshold: Duration) -> Vec<String> {
+        let mut stalled: Vec<String> = self
+            .file_started_at
+            .iter()
+            .filter(|(key, started_at)| {
+                self.by_file.get(*key).copied().unwrap_or(0) == 0
+                    && started_at.elapsed() > stall_threshold
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        stalled.sort();
+        stalled
+    }
+
     fn update(&mut self, file_key: &str, bytes: u64) -> (u64, u64) {
+        self.file_started_at
+            .entry(file_key.to_string())
+            .or_insert_with(Instant::now);
         let prev = self
             .by_file
             .insert(file_key.to_string(), bytes)
@@ -70,35 +307,78 @@ impl FolderProgressTracker {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn run_rclone_job(
     app: AppHandle,
+    runner: Arc<dyn RcloneRunner>,
     control: UploadControlHandle,
-    prefs: RclonePreferences,
+    prefs_rx: watch::Receiver<RclonePreferences>,
     max_concurrent: u8,
     service_account_folder: String,
     queue: Vec<QueueItemInput>,
     destination_folder_id: String,
-) -> Result<(), String> {
+) -> Result<Summary, String> {
     log::debug!(
         target: "rclone",
         "queue.received items={} max_concurrent={}",
         queue.len(),
         max_concurrent
     );
-    let sa_files = load_service_account_files(&service_account_folder)?;
-    if sa_files.is_empty() {
-        return Err(
-            "No valid service account JSON files found in the selected folder.".to_string(),
+    let (sa_files, sa_warnings) = load_service_account_files(&service_account_folder)?;
+    log_service_account_warnings(&sa_warnings);
+
+    #[cfg(windows)]
+    if prefs_rx.borrow().use_mmap {
+        log::warn!(
+            target: "rclone",
+            "queue.use_mmap enabled on Windows; memory-mapped buffers behave differently than on Unix"
         );
     }
 
-    let sa_pool = Arc::new(Mutex::new(sa_files));
+    let started_ms = now_ms();
+    let job_id = format!("job-{started_ms:x}");
+    log::info!(target: "rclone", "job.started job_id={} items={}", job_id, queue.len());
+    snapshot::start_job(&job_id, started_ms, &destination_folder_id, &queue);
+    joblog::start_job(&app, &job_id, &destination_folder_id, queue.len());
+    events::emit(
+        &app,
+        "upload:job_started",
+        JobStartedEvent {
+            job_id: job_id.clone(),
+            total_items: queue.len() as u32,
+            timestamp_unix_ms: started_ms,
+            destination_folder_id: destination_folder_id.clone(),
+            max_concurrent,
+        },
+    );
+
+    let scans = scan_queue_and_check_quota(&app, &job_id, &control, &sa_files, &queue);
+    warn_if_clock_skewed(&app, &job_id);
+    speed::spawn(&app, &job_id, control.clone());
+    let job_clock = Arc::new(std::sync::Mutex::new(PauseClock::start()));
+    let job_pause_task = timing::track_job_pause(job_clock.clone(), control.pause_rx.clone());
+
+    let sa_pool = Arc::new(Mutex::new(ServiceAccountPool {
+        accounts: sa_files,
+        disabled: HashSet::new(),
+    }));
     let sa_tick = Arc::new(AtomicU64::new(0));
+    *app.state::<DrivePoolState>().0.lock().await = Some(ActivePool {
+        job_id: job_id.clone(),
+        pool: sa_pool.clone(),
+        tick: sa_tick.clone(),
+    });
+    let reload_task = tokio::spawn(watch_service_account_reloads(
+        control.reload_sa_rx.clone(),
+        sa_pool.clone(),
+        service_account_folder.clone(),
+    ));
 
     let concurrency = max_concurrent.clamp(1, 10) as usize;
     let (tx, rx) = mpsc::channel::<QueueItemInput>(concurrency.saturating_mul(2).max(8));
     let rx = Arc::new(Mutex::new(rx));
 
+    let notifier = Arc::new(NotificationBatcher::new(app.clone()));
     let succeeded = Arc::new(std::sync::atomic::AtomicUsize::new(0));
     let failed = Arc::new(std::sync::atomic::AtomicUsize::new(0));
 
@@ -110,15 +390,20 @@ pub async fn run_rclone_job(
             item.kind,
             item.path
         );
-        let _ = app.emit(
+        events::emit(
+            &app,
             "upload:item_status",
             ItemStatusEvent {
                 item_id: item.id.clone(),
                 path: item.path.clone(),
                 kind: item.kind.clone(),
-                status: "preparing".to_string(),
+                status: "queued".to_string(),
                 message: None,
                 sa_email: None,
+                skipped_count: None,
+                wall_time_ms: None,
+                active_time_ms: None,
+                timestamp_unix_ms: now_ms(),
             },
         );
     }
@@ -126,14 +411,17 @@ pub async fn run_rclone_job(
     let mut worker_handles = Vec::with_capacity(concurrency);
     for _ in 0..concurrency {
         let app = app.clone();
+        let runner = runner.clone();
         let control = control.clone();
         let rx = rx.clone();
-        let prefs = prefs.clone();
+        let prefs_rx = prefs_rx.clone();
         let destination_folder_id = destination_folder_id.clone();
         let sa_pool = sa_pool.clone();
         let sa_tick = sa_tick.clone();
+        let scans = scans.clone();
         let succeeded = succeeded.clone();
         let failed = failed.clone();
+        let notifier = notifier.clone();
 
         worker_handles.push(tokio::spawn(async move {
             loop {
@@ -146,21 +434,54 @@ pub async fn run_rclone_job(
                 };
                 let Some(item) = item else { break };
 
+                events::emit(
+                    &app,
+                    "upload:item_status",
+                    ItemStatusEvent {
+                        item_id: item.id.clone(),
+                        path: item.path.clone(),
+                        kind: item.kind.clone(),
+                        status: "preparing".to_string(),
+                        message: None,
+                        sa_email: None,
+                        skipped_count: None,
+                        wall_time_ms: None,
+                        active_time_ms: None,
+                        timestamp_unix_ms: now_ms(),
+                    },
+                );
+                timing::start_item(&item.id);
                 let result = run_rclone_for_item(
                     &app,
+                    &runner,
                     &control,
-                    &prefs,
+                    &prefs_rx,
                     max_concurrent,
                     &sa_pool,
                     &sa_tick,
+                    &scans,
                     &destination_folder_id,
                     &item,
                 )
                 .await;
+                let elapsed = timing::finish_item(&item.id);
+                let item_warnings = warnings::take(&item.id);
+                if !item_warnings.is_empty() {
+                    events::emit(
+                        &app,
+                        "upload:item_warnings",
+                        ItemWarningsEvent {
+                            item_id: item.id.clone(),
+                            warnings: item_warnings,
+                            timestamp_unix_ms: now_ms(),
+                        },
+                    );
+                }
 
                 if let Err(err) = result {
                     failed.fetch_add(1, Ordering::Relaxed);
-                    let _ = app.emit(
+                    events::emit(
+                        &app,
                         "upload:item_status",
                         ItemStatusEvent {
                             item_id: item.id.clone(),
@@ -169,16 +490,29 @@ pub async fn run_rclone_job(
                             status: "failed".to_string(),
                             message: Some(err),
                             sa_email: None,
+                            skipped_count: None,
+                            wall_time_ms: elapsed.map(|e| e.wall_time_ms),
+                            active_time_ms: elapsed.map(|e| e.active_time_ms),
+                            timestamp_unix_ms: now_ms(),
                         },
                     );
                 } else {
                     succeeded.fetch_add(1, Ordering::Relaxed);
+                    let notify_enabled = prefs_rx.borrow().notify_per_item_completion;
+                    if item.notify_on_item_complete || notify_enabled {
+                        let name = Path::new(&item.path)
+                            .file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_else(|| item.path.clone());
+                        notifier.item_completed(name);
+                    }
                 }
             }
         }));
     }
 
     let total_items = queue.len() as u32;
+    let mut enqueue_error = None;
     for item in queue {
         if control.is_canceled() {
             break;
@@ -190,9 +524,10 @@ pub async fn run_rclone_job(
             item.kind,
             item.path
         );
-        tx.send(item)
-            .await
-            .map_err(|e| format!("Failed to enqueue upload task: {e}"))?;
+        if let Err(e) = tx.send(item).await {
+            enqueue_error = Some(format!("Failed to enqueue upload task: {e}"));
+            break;
+        }
     }
 
     drop(tx);
@@ -200,68 +535,334 @@ pub async fn run_rclone_job(
     for handle in worker_handles {
         let _ = handle.await;
     }
+    reload_task.abort();
+    job_pause_task.abort();
+    app.state::<DrivePoolState>().clear(&job_id).await;
+    if let Some(err) = enqueue_error {
+        return Err(err);
+    }
 
     let succeeded = succeeded.load(Ordering::Relaxed) as u32;
     let failed = failed.load(Ordering::Relaxed) as u32;
+    let elapsed = job_clock
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .elapsed();
+    let job_bytes = snapshot::job_snapshot(Some(&job_id)).map_or(0, |job| {
+        job.items
+            .iter()
+            .map(|item| item.bytes_sent.min(item.total_bytes))
+            .sum::<u64>()
+    });
+    metrics().record_job_finished(job_bytes, elapsed.active_time_ms);
+    log::info!(
+        target: "rclone",
+        "job.timing job_id={} wall_ms={} active_ms={} bytes={} avg_bytes_per_sec={:?}",
+        job_id,
+        elapsed.wall_time_ms,
+        elapsed.active_time_ms,
+        job_bytes,
+        elapsed.average_bytes_per_sec(job_bytes).map(|speed| speed as u64)
+    );
 
-    let _ = app.emit(
+    let summary = Summary {
+        total: total_items,
+        succeeded,
+        failed,
+        skipped: snapshot::skipped_total(&job_id),
+        wall_time_ms: elapsed.wall_time_ms,
+        active_time_ms: elapsed.active_time_ms,
+    };
+    snapshot::finish_job(&job_id, &summary);
+    joblog::finish_job(&job_id, &summary);
+    quota::flush();
+    events::emit(
+        &app,
         "upload:completed",
         CompletedEvent {
-            summary: Summary {
-                total: total_items,
-                succeeded,
-                failed,
-            },
+            summary: summary.clone(),
+            timestamp_unix_ms: now_ms(),
         },
     );
 
-    Ok(())
+    Ok(summary)
+}
+
+fn service_account_capacity(accounts: &[ServiceAccountFile], total_bytes: u64) -> JobFeasibility {
+    quota::estimate(
+        accounts
+            .iter()
+            .map(|sa| (sa.path.to_string_lossy().to_string(), sa.email.as_deref())),
+        total_bytes,
+    )
+}
+
+pub fn estimate_job_feasibility(
+    service_account_folder: &str,
+    total_bytes: u64,
+) -> Result<JobFeasibility, String> {
+    let (sa_files, sa_warnings) = load_service_account_files(service_account_folder)?;
+    log_service_account_warnings(&sa_warnings);
+    Ok(service_account_capacity(&sa_files, total_bytes))
+}
+
+// Scans waiting for the worker that uploads each item, keyed by item id.
+type PendingScans = std::sync::Mutex<HashMap<String, oneshot::Receiver<Option<ScanResult>>>>;
+
+// Scans the queue once, in the order workers take items, handing each scan to its worker and
+// sizing the queue on the way. Warns when the queue exceeds what the pool can still upload
+// today; the job starts regardless, as it would otherwise only find out hours in.
+fn scan_queue_and_check_quota(
+    app: &AppHandle,
+    job_id: &str,
+    control: &UploadControlHandle,
+    sa_files: &[ServiceAccountFile],
+    queue: &[QueueItemInput],
+) -> Arc<PendingScans> {
+    let mut senders = Vec::with_capacity(queue.len());
+    let mut receivers = HashMap::with_capacity(queue.len());
+    for item in queue {
+        let (tx, rx) = oneshot::channel();
+        senders.push((item.clone(), tx));
+        receivers.insert(item.id.clone(), rx);
+    }
+
+    let app = app.clone();
+    let job_id = job_id.to_string();
+    let control = control.clone();
+    let sa_files = sa_files.to_vec();
+    tokio::spawn(async move {
+        let queued_bytes = tokio::task::spawn_blocking(move || {
+            let mut queued_bytes = 0_u64;
+            for (item, tx) in senders {
+                if control.is_canceled() {
+                    return None;
+                }
+                let scan = scan_item(&item);
+                queued_bytes += scan.as_ref().map_or(0, ScanResult::total_bytes);
+                // The worker may already have given up on the item; the size still counts.
+                let _ = tx.send(scan);
+            }
+            Some(queued_bytes)
+        })
+        .await
+        .ok()
+        .flatten();
+        let Some(queued_bytes) = queued_bytes else {
+            return;
+        };
+
+        let feasibility = service_account_capacity(&sa_files, queued_bytes);
+        if feasibility.feasible {
+            return;
+        }
+        log::warn!(
+            target: "rclone",
+            "job.quota_insufficient job_id={} queued_bytes={} remaining_bytes={}",
+            job_id,
+            queued_bytes,
+            feasibility.remaining_bytes
+        );
+        events::emit(
+            &app,
+            "upload:quota_warning",
+            QuotaWarningEvent {
+                job_id,
+                feasibility,
+                timestamp_unix_ms: now_ms(),
+            },
+        );
+    });
+    Arc::new(std::sync::Mutex::new(receivers))
+}
+
+// The scan queued for the item at job start, or a fresh one when there is none (a retry, or
+// the scan task is gone).
+async fn take_item_scan(scans: &PendingScans, item: &QueueItemInput) -> Option<ScanResult> {
+    let pending = scans
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&item.id);
+    match pending {
+        Some(rx) => match rx.await {
+            Ok(scan) => scan,
+            Err(_) => scan_item(item),
+        },
+        None => scan_item(item),
+    }
+}
+
+// Runs in the background so an unreachable Google endpoint never delays the job.
+fn warn_if_clock_skewed(app: &AppHandle, job_id: &str) {
+    let app = app.clone();
+    let job_id = job_id.to_string();
+    tokio::spawn(async move {
+        let Some(skew_secs) = detect_clock_skew_secs().await else {
+            return;
+        };
+        log::warn!(
+            target: "rclone",
+            "job.clock_skewed job_id={} skew_secs={}",
+            job_id,
+            skew_secs
+        );
+        events::emit(
+            &app,
+            "upload:clock_skew",
+            ClockSkewEvent {
+                job_id,
+                skew_secs,
+                timestamp_unix_ms: now_ms(),
+            },
+        );
+    });
 }
 
+const NO_SERVICE_ACCOUNTS_ERROR: &str =
+    "No service accounts available: all service account files were removed from the folder.";
 const MAX_SA_ATTEMPTS: usize = 5;
 const RETRY_BACKOFF_MS: u64 = 1200;
 
 #[allow(clippy::too_many_arguments)]
 async fn run_rclone_for_item(
     app: &AppHandle,
+    runner: &Arc<dyn RcloneRunner>,
     control: &UploadControlHandle,
-    prefs: &RclonePreferences,
+    prefs_rx: &watch::Receiver<RclonePreferences>,
     max_concurrent: u8,
-    sa_pool: &Arc<Mutex<Vec<ServiceAccountFile>>>,
+    sa_pool: &Arc<Mutex<ServiceAccountPool>>,
     sa_tick: &Arc<AtomicU64>,
+    scans: &PendingScans,
     destination_folder_id: &str,
     item: &QueueItemInput,
 ) -> Result<(), String> {
     if is_item_canceled(control, &item.id) {
+        // Lets the item's queued scan be freed instead of waiting for the job to end.
+        scans
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&item.id);
+        emit_skipped(app, item, SkipReason::UserCanceled, 1);
         return Err("Upload canceled".to_string());
     }
-    let folder_entries = collect_folder_file_entries(item);
-    if let Some(entries) = folder_entries.as_ref() {
-        let file_list = entries
-            .iter()
-            .map(|entry| FileListEntry {
-                file_path: entry.path.to_string_lossy().to_string(),
-                total_bytes: entry.size,
-            })
-            .collect::<Vec<_>>();
-        if !file_list.is_empty() {
-            let _ = app.emit(
+    if item.transfers.is_some() || item.bwlimit_kib.is_some() {
+        log::info!(
+            target: "rclone",
+            "upload.overrides id={} transfers={} bwlimit_kib={:?}",
+            item.id,
+            item.transfers.unwrap_or(prefs_rx.borrow().transfers),
+            item.bwlimit_kib
+        );
+    }
+    if let Some(override_path) = item.sa_override_path.as_ref() {
+        log::info!(
+            target: "rclone",
+            "upload.sa_override id={} sa={}",
+            item.id,
+            redact_path(override_path)
+        );
+    }
+    let mut scan = take_item_scan(scans, item).await;
+    let mut forced_per_file = false;
+    if let Some(scan) = scan.as_mut().filter(|_| item.kind == "folder") {
+        let policy = prefs_rx.borrow().name_collision_policy;
+        forced_per_file = apply_name_collision_policy(app, item, scan, policy)?;
+    }
+    let shortcut_policy = prefs_rx.borrow().doc_shortcut_policy;
+    let doc_shortcuts = scan
+        .as_mut()
+        .map(|scan| take_doc_shortcuts(scan, shortcut_policy))
+        .unwrap_or_default();
+    // Like skipped collisions, left-out stubs need each file copied on its own.
+    if item.kind == "folder"
+        && !doc_shortcuts.is_empty()
+        && shortcut_policy != DocShortcutPolicy::UploadRaw
+    {
+        forced_per_file = true;
+    }
+    let mut known_total: Option<u64> = None;
+    let mut unreadable_count = 0_u32;
+    if let Some(scan) = scan.as_ref() {
+        if scan.skipped_symlinks > 0 {
+            emit_skipped(app, item, SkipReason::Symlink, scan.skipped_symlinks);
+        }
+        for warning in &scan.warnings {
+            log::warn!(
+                target: "rclone",
+                "scan.unreadable id={} path={} error={}",
+                item.id,
+                warning.path,
+                warning.message
+            );
+        }
+        if !scan.warnings.is_empty() {
+            unreadable_count = scan.warnings.len() as u32;
+            events::emit(
+                app,
+                "upload:skipped_files",
+                SkippedFilesEvent {
+                    item_id: item.id.clone(),
+                    files: scan.warnings.clone(),
+                    timestamp_unix_ms: now_ms(),
+                },
+            );
+            if prefs_rx.borrow().fail_on_unreadable {
+                return Err(format!(
+                    "{unreadable_count} file(s) could not be read and fail on unreadable is enabled"
+                ));
+            }
+        }
+        if !scan.files.is_empty() {
+            known_total = Some(scan.total_bytes());
+        }
+        if !scan.files.is_empty() || !scan.warnings.is_empty() {
+            let file_list = scan
+                .files
+                .iter()
+                .map(|entry| FileListEntry {
+                    file_path: entry.path.to_string_lossy().to_string(),
+                    total_bytes: entry.size,
+                })
+                .collect::<Vec<_>>();
+            let total_bytes = file_list.iter().map(|entry| entry.total_bytes).sum();
+            let file_count = file_list.len() as u32;
+            events::emit(
+                app,
                 "upload:file_list",
                 FileListEvent {
                     item_id: item.id.clone(),
                     files: file_list,
+                    total_bytes,
+                    file_count,
+                    warnings: scan.warnings.clone(),
+                    timestamp_unix_ms: now_ms(),
                 },
             );
         }
-    } else if let Some(file_list) = collect_file_list(item) {
-        let _ = app.emit(
-            "upload:file_list",
-            FileListEvent {
-                item_id: item.id.clone(),
-                files: file_list,
-            },
-        );
     }
+    let item_file_count = scan.as_ref().map_or(0, |scan| scan.files.len());
+    let empty_dirs = scan
+        .as_ref()
+        .filter(|_| item.kind == "folder")
+        .map(|scan| scan.empty_dirs.clone())
+        .unwrap_or_default();
+    let folder_entries = scan
+        .filter(|scan| item.kind == "folder" && (!scan.files.is_empty() || forced_per_file))
+        .map(|scan| scan.files)
+        .filter(|entries| {
+            let strategy = prefs_rx.borrow().folder_upload_strategy;
+            // Skipped collisions are only left out when each file is copied on its own.
+            let single_process = !forced_per_file && strategy.use_single_process(entries);
+            log::debug!(
+                target: "rclone",
+                "upload.strategy id={} strategy={:?} files={} single_process={}",
+                item.id,
+                strategy,
+                entries.len(),
+                single_process
+            );
+            !single_process
+        });
 
     let should_pause =
         *control.pause_rx.borrow() || control.paused_items_rx.borrow().contains(&item.id);
@@ -274,7 +875,8 @@ async fn run_rclone_for_item(
         item.path,
         should_pause
     );
-    let _ = app.emit(
+    events::emit(
+        app,
         "upload:item_status",
         ItemStatusEvent {
             item_id: item.id.clone(),
@@ -283,29 +885,82 @@ async fn run_rclone_for_item(
             status: initial_status.to_string(),
             message: None,
             sa_email: None,
+            skipped_count: None,
+            wall_time_ms: None,
+            active_time_ms: None,
+            timestamp_unix_ms: now_ms(),
         },
     );
 
+    timing::set_item_paused(&item.id, should_pause);
     wait_if_paused(control, &item.id).await?;
+    timing::set_item_paused(&item.id, false);
+
+    if !doc_shortcuts.is_empty() {
+        let prefs = prefs_rx.borrow().clone();
+        let outcomes = handle_doc_shortcuts(
+            app,
+            runner.as_ref(),
+            control,
+            &prefs,
+            sa_pool,
+            sa_tick,
+            destination_folder_id,
+            item,
+            &doc_shortcuts,
+            shortcut_policy,
+        )
+        .await?;
+        // A file item that is itself a shortcut has nothing left for rclone copy to do.
+        if item.kind == "file" && shortcut_policy != DocShortcutPolicy::UploadRaw {
+            let message = match outcomes.first().map(|outcome| outcome.handling) {
+                Some(DocShortcutHandling::Copied) => "Copied the linked Google document",
+                _ => "Skipped: Google Docs shortcut",
+            };
+            let elapsed = timing::item_elapsed(&item.id);
+            events::emit(
+                app,
+                "upload:item_status",
+                ItemStatusEvent {
+                    item_id: item.id.clone(),
+                    path: item.path.clone(),
+                    kind: item.kind.clone(),
+                    status: "done".to_string(),
+                    message: Some(message.to_string()),
+                    sa_email: None,
+                    skipped_count: None,
+                    wall_time_ms: elapsed.map(|e| e.wall_time_ms),
+                    active_time_ms: elapsed.map(|e| e.active_time_ms),
+                    timestamp_unix_ms: now_ms(),
+                },
+            );
+            return Ok(());
+        }
+    }
 
     if let Some(entries) = folder_entries {
         return run_rclone_for_folder_entries(
             app,
+            runner,
             control,
-            prefs,
+            prefs_rx,
             max_concurrent,
             sa_pool,
             sa_tick,
             destination_folder_id,
             item,
             entries,
+            empty_dirs,
+            unreadable_count,
         )
         .await;
     }
 
+    // Preferences saved while the job runs apply to transfers started after this point.
+    let prefs = prefs_rx.borrow().clone();
     let max_attempts = {
         let guard = sa_pool.lock().await;
-        guard.len().clamp(1, MAX_SA_ATTEMPTS)
+        guard.accounts.len().clamp(1, MAX_SA_ATTEMPTS)
     };
     let mut attempts = 0_usize;
     let mut tried: HashSet<PathBuf> = HashSet::new();
@@ -315,25 +970,54 @@ async fn run_rclone_for_item(
             return Err("Upload canceled".to_string());
         }
         attempts += 1;
-        let (sa_path, sa_email) =
-            select_service_account_excluding(sa_pool, sa_tick, &tried).await?;
+        let (sa_path, sa_email) = select_service_account_for_item(
+            app,
+            item,
+            sa_pool,
+            sa_tick,
+            &tried,
+            prefs.sa_selection_strategy,
+        )
+        .await?;
         tried.insert(sa_path.clone());
 
         let result = run_rclone_command(
             app,
+            runner.as_ref(),
             control,
-            prefs,
+            &prefs,
             &sa_path,
-            sa_email,
+            sa_email.clone(),
             destination_folder_id,
             item,
+            known_total,
+            item_file_count,
+            unreadable_count,
         )
         .await;
 
         match result {
-            Ok(()) => return Ok(()),
+            Ok(()) => {
+                quota::record_outcome(sa_email.as_deref(), true);
+                // --create-empty-src-dirs made rclone create these alongside the files.
+                if prefs.create_empty_folders && !empty_dirs.is_empty() {
+                    emit_empty_folders_created(app, item, empty_dirs);
+                }
+                return Ok(());
+            }
             Err(err) => {
-                let retryable = is_retryable_error(&err);
+                let auth_failure = is_sa_auth_failure(&err);
+                if auth_failure {
+                    handle_sa_auth_failure(app, item, sa_pool, &sa_path, &err).await?;
+                }
+                if matches!(RetryClass::classify(&err), RetryClass::RateLimit) {
+                    start_sa_cooldown(app, &sa_path, sa_email.as_deref());
+                }
+                let retryable = auth_failure || is_retryable_error(&err);
+                // Only failures that another account might avoid count against this one.
+                if retryable {
+                    quota::record_outcome(sa_email.as_deref(), false);
+                }
                 log::warn!(
                     target: "rclone",
                     "upload.attempt_failed id={} attempt={}/{} retryable={} error={}",
@@ -346,6 +1030,7 @@ async fn run_rclone_for_item(
                 if !retryable || attempts >= max_attempts {
                     return Err(err);
                 }
+                metrics().record_retry(RetryClass::classify(&err));
                 tokio::time::sleep(Duration::from_millis(
                     RETRY_BACKOFF_MS.saturating_mul(attempts as u64),
                 ))
@@ -355,35 +1040,84 @@ async fn run_rclone_for_item(
     }
 }
 
+const STALLED_FILES_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const STALLED_FILE_THRESHOLD: Duration = Duration::from_secs(30);
+
 #[allow(clippy::too_many_arguments)]
 async fn run_rclone_for_folder_entries(
     app: &AppHandle,
+    runner: &Arc<dyn RcloneRunner>,
     control: &UploadControlHandle,
-    prefs: &RclonePreferences,
+    prefs_rx: &watch::Receiver<RclonePreferences>,
     max_concurrent: u8,
-    sa_pool: &Arc<Mutex<Vec<ServiceAccountFile>>>,
+    sa_pool: &Arc<Mutex<ServiceAccountPool>>,
     sa_tick: &Arc<AtomicU64>,
     destination_folder_id: &str,
     item: &QueueItemInput,
-    entries: Vec<FolderFileEntry>,
+    entries: Vec<ScannedFile>,
+    empty_dirs: Vec<String>,
+    unreadable_count: u32,
 ) -> Result<(), String> {
+    // Reached when every file was left out, e.g. a folder holding only doc shortcuts.
     if entries.is_empty() {
+        let elapsed = timing::item_elapsed(&item.id);
+        events::emit(
+            app,
+            "upload:item_status",
+            ItemStatusEvent {
+                item_id: item.id.clone(),
+                path: item.path.clone(),
+                kind: item.kind.clone(),
+                status: "done".to_string(),
+                message: None,
+                sa_email: None,
+                skipped_count: (unreadable_count > 0).then_some(unreadable_count),
+                wall_time_ms: elapsed.map(|e| e.wall_time_ms),
+                active_time_ms: elapsed.map(|e| e.active_time_ms),
+                timestamp_unix_ms: now_ms(),
+            },
+        );
         return Ok(());
     }
 
     let total_bytes: u64 = entries.iter().map(|entry| entry.size).sum();
     if total_bytes > 0 {
-        emit_progress(app, item, 0, total_bytes).await;
+        emit_progress(app, item, 0, total_bytes, None, None, false).await;
     }
 
     let dest_base = resolve_folder_dest_base(item);
     let (dest_root_id, dest_prefix) = if !dest_base.is_empty() {
-        let (sa_path, _sa_email) =
-            select_service_account_excluding(sa_pool, sa_tick, &HashSet::new()).await?;
-        let base_id =
-            get_or_create_folder_id(prefs, &sa_path, destination_folder_id, &dest_base).await?;
+        let prefs = prefs_rx.borrow().clone();
+        let (sa_path, _sa_email) = select_service_account_for_item(
+            app,
+            item,
+            sa_pool,
+            sa_tick,
+            &HashSet::new(),
+            prefs.sa_selection_strategy,
+        )
+        .await?;
+        let base_id = get_or_create_folder_id(
+            runner.as_ref(),
+            control,
+            &item.id,
+            &prefs,
+            &sa_path,
+            destination_folder_id,
+            &dest_base,
+        )
+        .await?;
         let folder_dirs = build_rel_folder_dir_list(&entries);
-        ensure_remote_dirs(control, prefs, &sa_path, &base_id, &item.id, &folder_dirs).await?;
+        ensure_remote_dirs(
+            runner.as_ref(),
+            control,
+            &prefs,
+            &sa_path,
+            &base_id,
+            &item.id,
+            &folder_dirs,
+        )
+        .await?;
         (base_id, String::new())
     } else {
         (destination_folder_id.to_string(), dest_base.clone())
@@ -393,9 +1127,12 @@ async fn run_rclone_for_folder_entries(
     let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
     let progress_tracker = Arc::new(Mutex::new(FolderProgressTracker::new(total_bytes)));
     let last_sa_email = Arc::new(Mutex::new(None::<String>));
+    let skipped_files = Arc::new(AtomicU64::new(0));
+    let skip_batch = Arc::new(Mutex::new(SkipBatch::new(SkipReason::ExistsOnRemote)));
+    let item_file_count = entries.len();
     let mut tasks = tokio::task::JoinSet::new();
 
-    for entry in entries {
+    for unit in plan_folder_units(entries) {
         if control.is_canceled() {
             return Err("Upload canceled".to_string());
         }
@@ -410,22 +1147,24 @@ async fn run_rclone_for_folder_entries(
             .map_err(|_| "Upload canceled".to_string())?;
 
         let app = app.clone();
+        let runner = runner.clone();
         let control = control.clone();
-        let prefs = prefs.clone();
+        let prefs = prefs_rx.borrow().clone();
         let sa_pool = sa_pool.clone();
         let sa_tick = sa_tick.clone();
         let destination_folder_id = dest_root_id.clone();
         let item = item.clone();
         let progress_tracker = progress_tracker.clone();
         let last_sa_email = last_sa_email.clone();
+        let skipped_files = skipped_files.clone();
+        let skip_batch = skip_batch.clone();
         let dest_base = dest_prefix.clone();
 
         tasks.spawn(async move {
             let _permit = permit;
-            let dest_dir = build_folder_dest_dir(&dest_base, &entry.rel_path);
             let max_attempts = {
                 let guard = sa_pool.lock().await;
-                guard.len().clamp(1, MAX_SA_ATTEMPTS)
+                guard.accounts.len().clamp(1, MAX_SA_ATTEMPTS)
             };
             let mut attempts = 0_usize;
             let mut tried: HashSet<PathBuf> = HashSet::new();
@@ -436,26 +1175,64 @@ async fn run_rclone_for_folder_entries(
                 }
                 attempts += 1;
                 let (sa_path, sa_email) =
-                    select_service_account_excluding(&sa_pool, &sa_tick, &tried).await?;
+                    select_service_account_for_item(
+                        &app,
+                        &item,
+                        &sa_pool,
+                        &sa_tick,
+                        &tried,
+                        prefs.sa_selection_strategy,
+                    )
+                    .await?;
                 tried.insert(sa_path.clone());
 
-                let result = run_rclone_for_file(
-                    &app,
-                    &control,
-                    &prefs,
-                    &sa_path,
-                    sa_email.clone(),
-                    &destination_folder_id,
-                    &item,
-                    &entry.path,
-                    entry.size,
-                    &dest_dir,
-                    progress_tracker.clone(),
-                )
-                .await;
+                let result = match &unit {
+                    FolderUnit::File(entry) => {
+                        let dest_dir = build_folder_dest_dir(&dest_base, &entry.rel_path);
+                        run_rclone_for_file(
+                            &app,
+                            runner.as_ref(),
+                            &control,
+                            &prefs,
+                            &sa_path,
+                            sa_email.clone(),
+                            &destination_folder_id,
+                            &item,
+                            &entry.path,
+                            entry.size,
+                            &dest_dir,
+                            item_file_count,
+                            progress_tracker.clone(),
+                        )
+                        .await
+                        .map(|outcome| u64::from(outcome == FileOutcome::Skipped))
+                    }
+                    FolderUnit::Batch(batch) => {
+                        run_rclone_for_batch(
+                            &app,
+                            runner.as_ref(),
+                            &control,
+                            &prefs,
+                            &sa_path,
+                            sa_email.clone(),
+                            &destination_folder_id,
+                            &item,
+                            &dest_base,
+                            batch,
+                            item_file_count,
+                            progress_tracker.clone(),
+                        )
+                        .await
+                    }
+                };
 
                 match result {
-                    Ok(()) => {
+                    Ok(skipped) => {
+                        quota::record_outcome(sa_email.as_deref(), true);
+                        skipped_files.fetch_add(skipped, Ordering::Relaxed);
+                        if skipped > 0 {
+                            skip_batch.lock().await.add(&app, &item, skipped as u32);
+                        }
                         if let Some(sa_email) = sa_email {
                             let mut guard = last_sa_email.lock().await;
                             *guard = Some(sa_email);
@@ -463,24 +1240,35 @@ async fn run_rclone_for_folder_entries(
                         return Ok(());
                     }
                     Err(err) => {
-                        let retryable = is_retryable_error(&err);
+                        let auth_failure = is_sa_auth_failure(&err);
+                        if auth_failure {
+                            handle_sa_auth_failure(&app, &item, &sa_pool, &sa_path, &err)
+                                .await
+                                .map_err(|err| {
+                                    format!("Failed to upload {}: {}", unit.describe(), err)
+                                })?;
+                        }
+                        if matches!(RetryClass::classify(&err), RetryClass::RateLimit) {
+                            start_sa_cooldown(&app, &sa_path, sa_email.as_deref());
+                        }
+                        let retryable = auth_failure || is_retryable_error(&err);
+                        if retryable {
+                            quota::record_outcome(sa_email.as_deref(), false);
+                        }
                         log::warn!(
                             target: "rclone",
                             "upload.attempt_failed id={} file={} attempt={}/{} retryable={} error={}",
                             item.id,
-                            entry.path.to_string_lossy(),
+                            unit.describe(),
                             attempts,
                             max_attempts,
                             retryable,
                             err
                         );
                         if !retryable || attempts >= max_attempts {
-                            return Err(format!(
-                                "Failed to upload {}: {}",
-                                entry.path.to_string_lossy(),
-                                err
-                            ));
+                            return Err(format!("Failed to upload {}: {}", unit.describe(), err));
                         }
+                        metrics().record_retry(RetryClass::classify(&err));
                         tokio::time::sleep(Duration::from_millis(
                             RETRY_BACKOFF_MS.saturating_mul(attempts as u64),
                         ))
@@ -492,7 +1280,41 @@ async fn run_rclone_for_folder_entries(
     }
 
     let mut first_error: Option<String> = None;
-    while let Some(result) = tasks.join_next().await {
+    let mut stalled_check = tokio::time::interval(STALLED_FILES_CHECK_INTERVAL);
+    let mut reported_stalled: Vec<String> = Vec::new();
+    loop {
+        let result = tokio::select! {
+            result = tasks.join_next() => result,
+            _ = stalled_check.tick() => {
+                let paused = *control.pause_rx.borrow()
+                    || control.paused_items_rx.borrow().contains(&item.id);
+                let stalled = if paused {
+                    Vec::new()
+                } else {
+                    progress_tracker.lock().await.stalled_files(STALLED_FILE_THRESHOLD)
+                };
+                if stalled != reported_stalled {
+                    log::debug!(
+                        target: "rclone",
+                        "upload.stalled_files id={} count={}",
+                        item.id,
+                        stalled.len()
+                    );
+                    events::emit(
+                        app,
+                        "upload:stalled_files",
+                        StalledFilesEvent {
+                            item_id: item.id.clone(),
+                            stalled_paths: stalled.clone(),
+                            timestamp_unix_ms: now_ms(),
+                        },
+                    );
+                    reported_stalled = stalled;
+                }
+                continue;
+            }
+        };
+        let Some(result) = result else { break };
         match result {
             Ok(Ok(())) => {}
             Ok(Err(err)) => {
@@ -508,190 +1330,567 @@ async fn run_rclone_for_folder_entries(
         }
     }
 
+    skip_batch.lock().await.flush(app, item);
     if let Some(err) = first_error {
         return Err(err);
     }
 
+    // Per-file copies only create the folders their files land in.
+    let prefs = prefs_rx.borrow().clone();
+    if prefs.create_empty_folders && !empty_dirs.is_empty() {
+        let (sa_path, _sa_email) = select_service_account_for_item(
+            app,
+            item,
+            sa_pool,
+            sa_tick,
+            &HashSet::new(),
+            prefs.sa_selection_strategy,
+        )
+        .await?;
+        let dirs = empty_dirs
+            .iter()
+            .map(|dir| join_remote_path(&dest_prefix, dir))
+            .collect::<Vec<_>>();
+        ensure_remote_dirs(
+            runner.as_ref(),
+            control,
+            &prefs,
+            &sa_path,
+            &dest_root_id,
+            &item.id,
+            &dirs,
+        )
+        .await?;
+        emit_empty_folders_created(app, item, empty_dirs);
+    }
+
     let sa_email = last_sa_email.lock().await.clone();
-    let _ = app.emit(
+    let skipped = skipped_files.load(Ordering::Relaxed);
+    if skipped > 0 {
+        log::info!(
+            target: "rclone",
+            "upload.skipped id={} files={}",
+            item.id,
+            skipped
+        );
+    }
+    let elapsed = timing::item_elapsed(&item.id);
+    events::emit(
+        app,
         "upload:item_status",
         ItemStatusEvent {
             item_id: item.id.clone(),
             path: item.path.clone(),
             kind: item.kind.clone(),
             status: "done".to_string(),
-            message: None,
+            message: (skipped > 0).then(|| format!("Skipped {skipped} unchanged file(s)")),
             sa_email,
+            skipped_count: (unreadable_count > 0).then_some(unreadable_count),
+            wall_time_ms: elapsed.map(|e| e.wall_time_ms),
+            active_time_ms: elapsed.map(|e| e.active_time_ms),
+            timestamp_unix_ms: now_ms(),
         },
     );
 
     Ok(())
 }
 
-#[allow(clippy::too_many_arguments)]
-async fn run_rclone_command(
-    app: &AppHandle,
-    control: &UploadControlHandle,
-    prefs: &RclonePreferences,
-    sa_path: &Path,
-    sa_email: Option<String>,
-    destination_folder_id: &str,
-    item: &QueueItemInput,
-) -> Result<(), String> {
-    if control.is_canceled() {
-        return Err("Upload canceled".to_string());
-    }
-    if is_item_canceled(control, &item.id) {
-        return Err("Upload canceled".to_string());
-    }
-
-    log::debug!(
-        target: "rclone",
-        "upload.sa id={} sa={}",
-        item.id,
-        sa_path.to_string_lossy()
-    );
-    let _ = app.emit(
-        "upload:item_status",
-        ItemStatusEvent {
-            item_id: item.id.clone(),
-            path: item.path.clone(),
-            kind: item.kind.clone(),
-            status: "uploading".to_string(),
-            message: None,
-            sa_email: sa_email.clone(),
-        },
-    );
+// Caller-specific handling of the lines a running rclone process prints. Everything the
+// three kinds of copy share lives in drive_rclone_process.
+trait RcloneLineHandler {
+    async fn handle_line(&mut self, line: &str);
+}
 
-    let args = build_rclone_args(prefs, destination_folder_id, item, sa_path);
+// How an rclone process ended, plus what the shared loop collected from its output.
+struct RcloneExit {
+    status: std::process::ExitStatus,
+    errors: RcloneErrors,
+    last_counts: Option<(u64, u64)>,
+}
 
-    #[cfg(windows)]
-    let mut command = {
-        use std::os::windows::process::CommandExt;
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
-        let mut std_command = std::process::Command::new(&prefs.rclone_path);
-        std_command
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .creation_flags(CREATE_NO_WINDOW);
-        Command::from(std_command)
-    };
-    #[cfg(not(windows))]
-    let mut command = {
-        let mut command = Command::new(&prefs.rclone_path);
-        command
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-        command
-    };
+impl RcloneExit {
+    fn failure_message(self) -> String {
+        let status = self.status;
+        self.errors
+            .combined()
+            .unwrap_or_else(|| format!("Rclone failed with status: {status}"))
+    }
+}
 
+// Runs one rclone process to completion: tracks it for orphan cleanup, follows pause and
+// resume, aborts it when it stalls, collects its errors and hands every line to `handler`.
+#[allow(clippy::too_many_arguments)]
+async fn drive_rclone_process<H: RcloneLineHandler>(
+    app: &AppHandle,
+    runner: &dyn RcloneRunner,
+    control: &UploadControlHandle,
+    prefs: &RclonePreferences,
+    sa_path: &Path,
+    sa_email: Option<&str>,
+    item: &QueueItemInput,
+    args: &[String],
+    batch_files: Option<usize>,
+    handler: &mut H,
+) -> Result<RcloneExit, String> {
+    let files = batch_files
+        .map(|count| format!(" files={count}"))
+        .unwrap_or_default();
     log::debug!(
         target: "rclone",
-        "upload.exec id={} cmd={} args={:?}",
+        "upload.exec id={}{files} cmd={} args={:?}",
         item.id,
         prefs.rclone_path,
-        args
+        redact_args(args)
+    );
+    joblog::write_for_item(
+        &item.id,
+        format!(
+            "rclone.exec id={}{files} args={:?}",
+            item.id,
+            redact_args(args)
+        ),
     );
-    let mut child = command
-        .spawn()
-        .map_err(|e| format!("Failed to start rclone: {e}"))?;
+    let RcloneProcess {
+        pid,
+        stdout,
+        stderr,
+        mut child,
+    } = runner.spawn(&prefs.rclone_path, args)?;
+    let mut transfer_metrics = metrics().start_transfer();
 
-    let pid = child
-        .id()
-        .ok_or_else(|| "Failed to get rclone process id".to_string())?;
+    let _session_entry = orphans::track(pid, &item.id, args);
 
+    let rc = RcEndpoint::default();
     let (done_tx, done_rx) = watch::channel(false);
     let pause_task = tokio::spawn(monitor_pause_state(
         app.clone(),
         control.clone(),
         item.clone(),
         pid,
+        rc.clone(),
         done_rx,
     ));
 
-    let stdout = child
-        .stdout
-        .take()
-        .ok_or_else(|| "Missing stdout".to_string())?;
-    let stderr = child
-        .stderr
-        .take()
-        .ok_or_else(|| "Missing stderr".to_string())?;
+    let pumped = pump_rclone_output(
+        control,
+        &item.id,
+        sa_path,
+        StallWatch::new(prefs.stall_timeout_minutes),
+        &rc,
+        (stdout, stderr),
+        child.as_mut(),
+        handler,
+    )
+    .await;
+    let _ = done_tx.send(true);
+    let exit = match pumped? {
+        Pumped::Exited(exit) => exit,
+        Pumped::Stalled => {
+            let sa_email = sa_email.map(str::to_string);
+            return Err(abort_stalled(app, item, child.as_mut(), sa_email).await);
+        }
+    };
+    let _ = pause_task.await;
+
+    if control.is_canceled() {
+        return Err("Upload canceled".to_string());
+    }
+    if exit.status.success() {
+        transfer_metrics.succeed();
+    }
+    Ok(exit)
+}
+
+enum Pumped {
+    Exited(RcloneExit),
+    // The process is still running; the caller decides how to stop it.
+    Stalled,
+}
 
+// Reads both output streams of a spawned rclone process until it exits. Stops early when
+// the item is canceled or the stall watch fires.
+#[allow(clippy::too_many_arguments)]
+async fn pump_rclone_output<H: RcloneLineHandler>(
+    control: &UploadControlHandle,
+    item_id: &str,
+    sa_path: &Path,
+    mut stall: StallWatch,
+    rc: &RcEndpoint,
+    (stdout, stderr): (RcloneStream, RcloneStream),
+    child: &mut dyn RcloneChild,
+    handler: &mut H,
+) -> Result<Pumped, String> {
     let (line_tx, mut line_rx) = mpsc::channel::<String>(256);
     let stdout_task = tokio::spawn(read_rclone_stream(stdout, line_tx.clone()));
     let stderr_task = tokio::spawn(read_rclone_stream(stderr, line_tx.clone()));
+    let stats_tx = line_tx.downgrade();
     drop(line_tx);
 
-    let progress_re = progress_regex();
-    let mut last_bytes = 0_u64;
-    let mut last_total = 0_u64;
-    let mut last_file_progress: HashMap<String, (u64, u64)> = HashMap::new();
-    let mut last_error: Option<String> = None;
+    let mut errors = RcloneErrors::default();
+    let mut last_counts: Option<(u64, u64)> = None;
 
-    while let Some(line) = line_rx.recv().await {
+    let mut stall_check = tokio::time::interval(STALL_CHECK_INTERVAL);
+    loop {
+        let line = tokio::select! {
+            line = line_rx.recv() => line,
+            _ = stall_check.tick() => {
+                if stall.is_stalled(control, item_id) {
+                    return Ok(Pumped::Stalled);
+                }
+                continue;
+            }
+        };
+        let Some(line) = line else { break };
         log::debug!(target: "rclone", "{}", line);
-        if is_item_canceled(control, &item.id) {
+        stall.observe(&line);
+        rc.observe(&line, &stats_tx);
+        if is_item_canceled(control, item_id) {
             return Err("Upload canceled".to_string());
         }
-        if let Some(msg) = extract_error_message(&line) {
-            last_error = Some(msg);
+        collect_log_line(item_id, &line, sa_path, &mut errors);
+        if let Some(counts) = parse_json_transfer_counts(&line) {
+            last_counts = Some(counts);
         }
-        if let Some(entries) = parse_json_file_progress(&line) {
-            for (file_path, bytes, total) in entries {
-                let should_emit = match last_file_progress.get(&file_path) {
+        handler.handle_line(&line).await;
+    }
+
+    let _ = stdout_task.await;
+    let _ = stderr_task.await;
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| format!("Failed to wait for rclone: {e}"))?;
+    Ok(Pumped::Exited(RcloneExit {
+        status,
+        errors,
+        last_counts,
+    }))
+}
+
+// Overall progress from either a JSON or a plain-text stats line, as
+// (bytes, total, speed, eta_secs).
+fn parse_transfer_progress(
+    regex: &Regex,
+    line: &str,
+    path: Option<&str>,
+    known_total: Option<u64>,
+) -> Option<(u64, u64, Option<f64>, Option<u64>)> {
+    parse_json_progress(line, path, known_total)
+        .map(|(bytes, total, speed)| (bytes, total, speed, None))
+        .or_else(|| {
+            parse_progress_line(regex, line).map(|p| {
+                (
+                    p.sent,
+                    clamp_total(p.total, known_total),
+                    p.speed,
+                    p.eta_secs,
+                )
+            })
+        })
+}
+
+// A whole item in one process: per-file progress for folders and the item's overall bytes.
+struct ItemLineHandler<'a> {
+    app: &'a AppHandle,
+    item: &'a QueueItemInput,
+    sa_email: Option<&'a str>,
+    known_total: Option<u64>,
+    progress_re: Regex,
+    last_bytes: u64,
+    last_total: u64,
+    last_file_progress: HashMap<String, (u64, u64)>,
+}
+
+impl RcloneLineHandler for ItemLineHandler<'_> {
+    async fn handle_line(&mut self, line: &str) {
+        if let Some(entries) = parse_json_file_progress(line) {
+            for (file_path, bytes, total, speed, eta_secs) in entries {
+                let should_emit = match self.last_file_progress.get(&file_path) {
                     Some((last_bytes, last_total)) => *last_bytes != bytes || *last_total != total,
                     None => true,
                 };
                 if should_emit {
-                    last_file_progress.insert(file_path.clone(), (bytes, total));
-                    emit_file_progress(app, item, &file_path, bytes, total, sa_email.clone()).await;
+                    self.last_file_progress
+                        .insert(file_path.clone(), (bytes, total));
+                    emit_file_progress(
+                        self.app,
+                        self.item,
+                        &file_path,
+                        bytes,
+                        total,
+                        self.sa_email.map(str::to_string),
+                        speed,
+                        eta_secs,
+                    )
+                    .await;
                 }
             }
         }
-        if let Some((bytes, total)) = parse_json_progress(&line, &item.path)
-            .or_else(|| parse_progress_line(&progress_re, &line))
-        {
-            if bytes != last_bytes || total != last_total {
-                last_bytes = bytes;
-                last_total = total;
-                emit_progress(app, item, bytes, total).await;
-            }
+        let Some((bytes, total, speed, eta_secs)) = parse_transfer_progress(
+            &self.progress_re,
+            line,
+            single_file_path(self.item),
+            self.known_total,
+        ) else {
+            return;
+        };
+        if bytes != self.last_bytes || total != self.last_total {
+            metrics().record_bytes(bytes.saturating_sub(self.last_bytes));
+            quota::record(self.sa_email, bytes.saturating_sub(self.last_bytes));
+            self.last_bytes = bytes;
+            self.last_total = total;
+            emit_progress(
+                self.app,
+                self.item,
+                bytes,
+                total,
+                speed,
+                eta_secs,
+                self.known_total.is_none(),
+            )
+            .await;
         }
     }
+}
 
-    let _ = stdout_task.await;
-    let _ = stderr_task.await;
+// One file of a per-file folder upload, reported both on its own and through the
+// folder's aggregate progress.
+struct FileLineHandler<'a> {
+    app: &'a AppHandle,
+    item: &'a QueueItemInput,
+    sa_email: Option<&'a str>,
+    file_path: &'a str,
+    file_size: u64,
+    progress_tracker: &'a Mutex<FolderProgressTracker>,
+    progress_re: Regex,
+    last_bytes: u64,
+    last_total: u64,
+}
 
-    let _ = done_tx.send(true);
-    let _ = pause_task.await;
+impl RcloneLineHandler for FileLineHandler<'_> {
+    async fn handle_line(&mut self, line: &str) {
+        let Some((bytes, total, speed, eta_secs)) = parse_transfer_progress(
+            &self.progress_re,
+            line,
+            Some(self.file_path),
+            Some(self.file_size),
+        ) else {
+            return;
+        };
+        if bytes == self.last_bytes && total == self.last_total {
+            return;
+        }
+        metrics().record_bytes(bytes.saturating_sub(self.last_bytes));
+        quota::record(self.sa_email, bytes.saturating_sub(self.last_bytes));
+        self.last_bytes = bytes;
+        self.last_total = total;
+        emit_file_progress(
+            self.app,
+            self.item,
+            self.file_path,
+            bytes,
+            total,
+            self.sa_email.map(str::to_string),
+            speed,
+            eta_secs,
+        )
+        .await;
+        let (total_sent, total_size) = {
+            let mut guard = self.progress_tracker.lock().await;
+            guard.update(self.file_path, bytes)
+        };
+        if total_size > 0 {
+            emit_progress(
+                self.app, self.item, total_sent, total_size, None, None, false,
+            )
+            .await;
+        }
+    }
+}
 
-    let status = child
-        .wait()
-        .await
-        .map_err(|e| format!("Failed to wait for rclone: {e}"))?;
+// A batch of small files from one folder, matched back to their local paths by the
+// relative names rclone reports.
+struct BatchLineHandler<'a> {
+    app: &'a AppHandle,
+    item: &'a QueueItemInput,
+    sa_path: &'a Path,
+    sa_email: Option<&'a str>,
+    files_by_rel: &'a HashMap<String, &'a ScannedFile>,
+    progress_tracker: &'a Mutex<FolderProgressTracker>,
+    last_file_progress: HashMap<String, u64>,
+    failed_files: Vec<(String, String)>,
+}
 
+impl RcloneLineHandler for BatchLineHandler<'_> {
+    async fn handle_line(&mut self, line: &str) {
+        if let Some((object, message)) = parse_json_object_error(line) {
+            let file_path = self
+                .files_by_rel
+                .get(object.as_str())
+                .map(|entry| entry.path.to_string_lossy().to_string())
+                .unwrap_or(object);
+            log::warn!(
+                target: "rclone",
+                "upload.batch_file_failed id={} file={} error={}",
+                self.item.id,
+                file_path,
+                message
+            );
+            self.failed_files
+                .push((file_path, redact_sa_path(&message, self.sa_path)));
+        }
+        let Some(entries) = parse_json_file_progress(line) else {
+            return;
+        };
+        for (name, bytes, total, speed, eta_secs) in entries {
+            let Some(entry) = self.files_by_rel.get(name.as_str()) else {
+                continue;
+            };
+            let file_path = entry.path.to_string_lossy().to_string();
+            let last_bytes = self.last_file_progress.insert(file_path.clone(), bytes);
+            if last_bytes == Some(bytes) {
+                continue;
+            }
+            let sent = bytes.saturating_sub(last_bytes.unwrap_or(0));
+            metrics().record_bytes(sent);
+            quota::record(self.sa_email, sent);
+            emit_file_progress(
+                self.app,
+                self.item,
+                &file_path,
+                bytes,
+                total,
+                self.sa_email.map(str::to_string),
+                speed,
+                eta_secs,
+            )
+            .await;
+            let (total_sent, total_size) = {
+                let mut guard = self.progress_tracker.lock().await;
+                guard.update(&file_path, bytes)
+            };
+            if total_size > 0 {
+                emit_progress(
+                    self.app, self.item, total_sent, total_size, None, None, false,
+                )
+                .await;
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_rclone_command(
+    app: &AppHandle,
+    runner: &dyn RcloneRunner,
+    control: &UploadControlHandle,
+    prefs: &RclonePreferences,
+    sa_path: &Path,
+    sa_email: Option<String>,
+    destination_folder_id: &str,
+    item: &QueueItemInput,
+    known_total: Option<u64>,
+    item_file_count: usize,
+    unreadable_count: u32,
+) -> Result<(), String> {
     if control.is_canceled() {
         return Err("Upload canceled".to_string());
     }
+    if is_item_canceled(control, &item.id) {
+        return Err("Upload canceled".to_string());
+    }
+
+    log::debug!(
+        target: "rclone",
+        "upload.sa id={} sa={}",
+        item.id,
+        redact_path(&sa_path.to_string_lossy())
+    );
+    events::emit(
+        app,
+        "upload:item_status",
+        ItemStatusEvent {
+            item_id: item.id.clone(),
+            path: item.path.clone(),
+            kind: item.kind.clone(),
+            status: "uploading".to_string(),
+            message: None,
+            sa_email: sa_email.clone(),
+            skipped_count: None,
+            wall_time_ms: None,
+            active_time_ms: None,
+            timestamp_unix_ms: now_ms(),
+        },
+    );
+
+    let mut args = build_rclone_args(
+        prefs,
+        destination_folder_id,
+        item,
+        sa_path,
+        item_file_count,
+        None,
+    );
+    if prefs.create_empty_folders && item.kind == "folder" {
+        args.push("--create-empty-src-dirs".to_string());
+    }
+
+    // Local enumeration is authoritative; rclone's total grows as its checkers discover files.
+    if let Some(total) = known_total.filter(|total| *total > 0) {
+        emit_progress(app, item, 0, total, None, None, false).await;
+    }
 
-    if status.success() {
+    let mut handler = ItemLineHandler {
+        app,
+        item,
+        sa_email: sa_email.as_deref(),
+        known_total,
+        progress_re: progress_regex(),
+        last_bytes: 0,
+        last_total: 0,
+        last_file_progress: HashMap::new(),
+    };
+    let exit = drive_rclone_process(
+        app,
+        runner,
+        control,
+        prefs,
+        sa_path,
+        sa_email.as_deref(),
+        item,
+        &args,
+        None,
+        &mut handler,
+    )
+    .await?;
+
+    if exit.status.success() {
         log::info!(
             target: "rclone",
             "upload.done id={} status=ok",
             item.id
         );
-        let _ = app.emit(
+        let skipped = is_skipped_transfer(exit.last_counts);
+        if skipped {
+            let files = exit.last_counts.map_or(1, |(checks, _)| checks as u32);
+            emit_skipped(app, item, SkipReason::ExistsOnRemote, files);
+        }
+        let elapsed = timing::item_elapsed(&item.id);
+        events::emit(
+            app,
             "upload:item_status",
             ItemStatusEvent {
                 item_id: item.id.clone(),
                 path: item.path.clone(),
                 kind: item.kind.clone(),
                 status: "done".to_string(),
-                message: None,
+                message: skipped.then(|| "Skipped: destination is up to date".to_string()),
                 sa_email,
+                skipped_count: (unreadable_count > 0).then_some(unreadable_count),
+                wall_time_ms: elapsed.map(|e| e.wall_time_ms),
+                active_time_ms: elapsed.map(|e| e.active_time_ms),
+                timestamp_unix_ms: now_ms(),
             },
         );
         return Ok(());
@@ -701,16 +1900,15 @@ async fn run_rclone_command(
         target: "rclone",
         "upload.failed id={} status={}",
         item.id,
-        status
+        exit.status
     );
-
-    let message = last_error.unwrap_or_else(|| format!("Rclone failed with status: {status}"));
-    Err(message)
+    Err(exit.failure_message())
 }
 
 #[allow(clippy::too_many_arguments)]
 async fn run_rclone_for_file(
     app: &AppHandle,
+    runner: &dyn RcloneRunner,
     control: &UploadControlHandle,
     prefs: &RclonePreferences,
     sa_path: &Path,
@@ -720,8 +1918,9 @@ async fn run_rclone_for_file(
     file_path: &Path,
     file_size: u64,
     dest_dir: &str,
+    item_file_count: usize,
     progress_tracker: Arc<Mutex<FolderProgressTracker>>,
-) -> Result<(), String> {
+) -> Result<FileOutcome, String> {
     if control.is_canceled() {
         return Err("Upload canceled".to_string());
     }
@@ -729,7 +1928,8 @@ async fn run_rclone_for_file(
         return Err("Upload canceled".to_string());
     }
 
-    let _ = app.emit(
+    events::emit(
+        app,
         "upload:item_status",
         ItemStatusEvent {
             item_id: item.id.clone(),
@@ -738,6 +1938,10 @@ async fn run_rclone_for_file(
             status: "uploading".to_string(),
             message: None,
             sa_email: sa_email.clone(),
+            skipped_count: None,
+            wall_time_ms: None,
+            active_time_ms: None,
+            timestamp_unix_ms: now_ms(),
         },
     );
 
@@ -747,150 +1951,296 @@ async fn run_rclone_for_file(
         path: file_path_string.clone(),
         kind: "file".to_string(),
         dest_path: Some(dest_dir.to_string()),
+        sa_override_path: item.sa_override_path.clone(),
+        mode: item.mode,
+        notify_on_item_complete: item.notify_on_item_complete,
+        transfers: item.transfers,
+        bwlimit_kib: item.bwlimit_kib,
     };
-    let args = build_rclone_args(prefs, destination_folder_id, &file_item, sa_path);
-
-    #[cfg(windows)]
-    let mut command = {
-        use std::os::windows::process::CommandExt;
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
-        let mut std_command = std::process::Command::new(&prefs.rclone_path);
-        std_command
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .creation_flags(CREATE_NO_WINDOW);
-        Command::from(std_command)
-    };
-    #[cfg(not(windows))]
-    let mut command = {
-        let mut command = Command::new(&prefs.rclone_path);
-        command
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-        command
-    };
-
-    log::debug!(
-        target: "rclone",
-        "upload.exec id={} cmd={} args={:?}",
-        item.id,
-        prefs.rclone_path,
-        args
+    let args = build_rclone_args(
+        prefs,
+        destination_folder_id,
+        &file_item,
+        sa_path,
+        item_file_count,
+        None,
     );
-    let mut child = command
-        .spawn()
-        .map_err(|e| format!("Failed to start rclone: {e}"))?;
-
-    let pid = child
-        .id()
-        .ok_or_else(|| "Failed to get rclone process id".to_string())?;
-
-    let (done_tx, done_rx) = watch::channel(false);
-    let pause_task = tokio::spawn(monitor_pause_state(
-        app.clone(),
-        control.clone(),
-        item.clone(),
-        pid,
-        done_rx,
-    ));
-
-    let stdout = child
-        .stdout
-        .take()
-        .ok_or_else(|| "Missing stdout".to_string())?;
-    let stderr = child
-        .stderr
-        .take()
-        .ok_or_else(|| "Missing stderr".to_string())?;
-
-    let (line_tx, mut line_rx) = mpsc::channel::<String>(256);
-    let stdout_task = tokio::spawn(read_rclone_stream(stdout, line_tx.clone()));
-    let stderr_task = tokio::spawn(read_rclone_stream(stderr, line_tx.clone()));
-    drop(line_tx);
 
-    let progress_re = progress_regex();
-    let mut last_bytes = 0_u64;
-    let mut last_total = 0_u64;
-    let mut last_error: Option<String> = None;
+    emit_file_progress(
+        app,
+        item,
+        &file_path_string,
+        0,
+        file_size,
+        sa_email.clone(),
+        None,
+        None,
+    )
+    .await;
+    let (total_sent, total_size) = {
+        let mut guard = progress_tracker.lock().await;
+        guard.start(&file_path_string)
+    };
+    if total_size > 0 {
+        emit_progress(app, item, total_sent, total_size, None, None, false).await;
+    }
 
-    emit_file_progress(app, item, &file_path_string, 0, file_size, sa_email.clone()).await;
+    let mut handler = FileLineHandler {
+        app,
+        item,
+        sa_email: sa_email.as_deref(),
+        file_path: &file_path_string,
+        file_size,
+        progress_tracker: &progress_tracker,
+        progress_re: progress_regex(),
+        last_bytes: 0,
+        last_total: 0,
+    };
+    let exit = drive_rclone_process(
+        app,
+        runner,
+        control,
+        prefs,
+        sa_path,
+        sa_email.as_deref(),
+        item,
+        &args,
+        None,
+        &mut handler,
+    )
+    .await?;
+
+    if !exit.status.success() {
+        return Err(exit.failure_message());
+    }
+    emit_file_progress(
+        app,
+        item,
+        &file_path_string,
+        file_size,
+        file_size,
+        sa_email.clone(),
+        None,
+        None,
+    )
+    .await;
     let (total_sent, total_size) = {
         let mut guard = progress_tracker.lock().await;
-        guard.update(&file_path_string, 0)
+        guard.finish(&file_path_string, file_size)
     };
     if total_size > 0 {
-        emit_progress(app, item, total_sent, total_size).await;
+        emit_progress(app, item, total_sent, total_size, None, None, false).await;
+    }
+    if is_skipped_transfer(exit.last_counts) {
+        return Ok(FileOutcome::Skipped);
     }
+    Ok(FileOutcome::Uploaded)
+}
 
-    while let Some(line) = line_rx.recv().await {
-        log::debug!(target: "rclone", "{}", line);
-        if is_item_canceled(control, &item.id) {
-            return Err("Upload canceled".to_string());
-        }
-        if let Some(msg) = extract_error_message(&line) {
-            last_error = Some(msg);
-        }
-        if let Some((bytes, total)) = parse_json_progress(&line, &file_path_string)
-            .or_else(|| parse_progress_line(&progress_re, &line))
-        {
-            if bytes != last_bytes || total != last_total {
-                last_bytes = bytes;
-                last_total = total;
-                emit_file_progress(app, item, &file_path_string, bytes, total, sa_email.clone())
-                    .await;
-                let (total_sent, total_size) = {
-                    let mut guard = progress_tracker.lock().await;
-                    guard.update(&file_path_string, bytes)
-                };
-                if total_size > 0 {
-                    emit_progress(app, item, total_sent, total_size).await;
-                }
-            }
-        }
+#[allow(clippy::too_many_arguments)]
+async fn run_rclone_for_batch(
+    app: &AppHandle,
+    runner: &dyn RcloneRunner,
+    control: &UploadControlHandle,
+    prefs: &RclonePreferences,
+    sa_path: &Path,
+    sa_email: Option<String>,
+    destination_folder_id: &str,
+    item: &QueueItemInput,
+    dest_prefix: &str,
+    batch: &[ScannedFile],
+    item_file_count: usize,
+    progress_tracker: Arc<Mutex<FolderProgressTracker>>,
+) -> Result<u64, String> {
+    if control.is_canceled() {
+        return Err("Upload canceled".to_string());
+    }
+    if is_item_canceled(control, &item.id) {
+        return Err("Upload canceled".to_string());
     }
 
-    let _ = stdout_task.await;
-    let _ = stderr_task.await;
+    events::emit(
+        app,
+        "upload:item_status",
+        ItemStatusEvent {
+            item_id: item.id.clone(),
+            path: item.path.clone(),
+            kind: item.kind.clone(),
+            status: "uploading".to_string(),
+            message: None,
+            sa_email: sa_email.clone(),
+            skipped_count: None,
+            wall_time_ms: None,
+            active_time_ms: None,
+            timestamp_unix_ms: now_ms(),
+        },
+    );
 
-    let _ = done_tx.send(true);
-    let _ = pause_task.await;
+    {
+        // A retried batch starts every member's stall clock afresh once rclone reports it.
+        let mut guard = progress_tracker.lock().await;
+        for entry in batch {
+            guard
+                .file_started_at
+                .remove(entry.path.to_string_lossy().as_ref());
+        }
+    }
 
-    let status = child
-        .wait()
-        .await
-        .map_err(|e| format!("Failed to wait for rclone: {e}"))?;
+    // Both the list and rclone's reports use names as rclone reads them from disk.
+    let rel_paths: Vec<String> = batch
+        .iter()
+        .map(|entry| rclone_local_name(&entry.rel_path))
+        .collect();
+    // Held until this function returns so the list outlives the rclone process.
+    let filter = FilterSpec::files_from(rel_paths.iter().map(String::as_str))?;
+    // rclone reports batch members by their path relative to the folder root.
+    let files_by_rel: HashMap<String, &ScannedFile> = rel_paths.into_iter().zip(batch).collect();
+
+    let batch_item = QueueItemInput {
+        id: item.id.clone(),
+        path: item.path.clone(),
+        kind: "folder".to_string(),
+        dest_path: Some(dest_prefix.to_string()),
+        sa_override_path: item.sa_override_path.clone(),
+        mode: item.mode,
+        notify_on_item_complete: item.notify_on_item_complete,
+        transfers: item.transfers,
+        bwlimit_kib: item.bwlimit_kib,
+    };
+    let args = build_rclone_args(
+        prefs,
+        destination_folder_id,
+        &batch_item,
+        sa_path,
+        item_file_count,
+        Some(&filter),
+    );
 
-    if control.is_canceled() {
-        return Err("Upload canceled".to_string());
+    let mut handler = BatchLineHandler {
+        app,
+        item,
+        sa_path,
+        sa_email: sa_email.as_deref(),
+        files_by_rel: &files_by_rel,
+        progress_tracker: &progress_tracker,
+        last_file_progress: HashMap::new(),
+        failed_files: Vec::new(),
+    };
+    let exit = drive_rclone_process(
+        app,
+        runner,
+        control,
+        prefs,
+        sa_path,
+        sa_email.as_deref(),
+        item,
+        &args,
+        Some(batch.len()),
+        &mut handler,
+    )
+    .await?;
+
+    if exit.status.success() {
+        for entry in batch {
+            let file_path = entry.path.to_string_lossy().to_string();
+            emit_file_progress(
+                app,
+                item,
+                &file_path,
+                entry.size,
+                entry.size,
+                sa_email.clone(),
+                None,
+                None,
+            )
+            .await;
+            let (total_sent, total_size) = {
+                let mut guard = progress_tracker.lock().await;
+                guard.finish(&file_path, entry.size)
+            };
+            if total_size > 0 {
+                emit_progress(app, item, total_sent, total_size, None, None, false).await;
+            }
+        }
+        let uploaded = exit
+            .last_counts
+            .map_or(batch.len() as u64, |(_, transfers)| transfers);
+        return Ok((batch.len() as u64).saturating_sub(uploaded));
     }
 
-    if status.success() {
-        emit_file_progress(
-            app,
-            item,
-            &file_path_string,
-            file_size,
-            file_size,
-            sa_email.clone(),
-        )
-        .await;
-        let (total_sent, total_size) = {
-            let mut guard = progress_tracker.lock().await;
-            guard.update(&file_path_string, file_size)
-        };
-        if total_size > 0 {
-            emit_progress(app, item, total_sent, total_size).await;
+    match handler.failed_files.first() {
+        Some((file_path, file_error)) => Err(format!(
+            "{} of {} file(s) failed, first {}: {}",
+            handler.failed_files.len(),
+            batch.len(),
+            file_path,
+            file_error
+        )),
+        None => Err(exit.failure_message()),
+    }
+}
+
+enum FolderUnit {
+    File(ScannedFile),
+    Batch(Vec<ScannedFile>),
+}
+
+impl FolderUnit {
+    fn describe(&self) -> String {
+        match self {
+            FolderUnit::File(entry) => entry.path.to_string_lossy().to_string(),
+            FolderUnit::Batch(batch) => format!("batch of {} files", batch.len()),
         }
-        return Ok(());
     }
+}
+
+// Files at or above this size keep their own rclone process so SA rotation applies per file.
+const BATCH_MAX_FILE_BYTES: u64 = 8 * 1024 * 1024;
+const BATCH_MAX_FILES: usize = 200;
+const BATCH_MAX_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+// Groups small files into batches so a folder of thousands of tiny files does not pay
+// rclone startup and SA token costs per file. Large files stay on their own.
+fn plan_folder_units(entries: Vec<ScannedFile>) -> Vec<FolderUnit> {
+    let mut units = Vec::new();
+    let mut batch: Vec<ScannedFile> = Vec::new();
+    let mut batch_bytes = 0_u64;
+
+    for entry in entries {
+        if entry.size >= BATCH_MAX_FILE_BYTES {
+            units.push(FolderUnit::File(entry));
+            continue;
+        }
+        if batch.len() >= BATCH_MAX_FILES || batch_bytes + entry.size > BATCH_MAX_BYTES {
+            units.push(batch_unit(std::mem::take(&mut batch)));
+            batch_bytes = 0;
+        }
+        batch_bytes += entry.size;
+        batch.push(entry);
+    }
+    if !batch.is_empty() {
+        units.push(batch_unit(batch));
+    }
+    units
+}
 
-    let message = last_error.unwrap_or_else(|| format!("Rclone failed with status: {status}"));
-    Err(message)
+fn batch_unit(mut batch: Vec<ScannedFile>) -> FolderUnit {
+    if batch.len() == 1 {
+        FolderUnit::File(batch.remove(0))
+    } else {
+        FolderUnit::Batch(batch)
+    }
 }
 
-async fn emit_progress(app: &AppHandle, item: &QueueItemInput, bytes: u64, total: u64) {
+async fn emit_progress(
+    app: &AppHandle,
+    item: &QueueItemInput,
+    bytes: u64,
+    total: u64,
+    speed: Option<f64>,
+    eta_secs: Option<u64>,
+    estimated_total: bool,
+) {
     log::debug!(
         target: "rclone",
         "progress id={} bytes={} total={}",
@@ -898,17 +2248,23 @@ async fn emit_progress(app: &AppHandle, item: &QueueItemInput, bytes: u64, total
         bytes,
         total
     );
-    let _ = app.emit(
+    events::emit(
+        app,
         "upload:progress",
         ProgressEvent {
             item_id: item.id.clone(),
             path: item.path.clone(),
             bytes_sent: bytes,
             total_bytes: total,
+            speed,
+            eta_secs,
+            estimated_total,
+            timestamp_unix_ms: now_ms(),
         },
     );
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn emit_file_progress(
     app: &AppHandle,
     item: &QueueItemInput,
@@ -916,8 +2272,11 @@ async fn emit_file_progress(
     bytes: u64,
     total: u64,
     sa_email: Option<String>,
+    speed: Option<f64>,
+    eta_secs: Option<u64>,
 ) {
-    let _ = app.emit(
+    events::emit(
+        app,
         "upload:file_progress",
         FileProgressEvent {
             item_id: item.id.clone(),
@@ -925,35 +2284,118 @@ async fn emit_file_progress(
             bytes_sent: bytes,
             total_bytes: total,
             sa_email,
+            speed,
+            eta_secs,
+            timestamp_unix_ms: now_ms(),
         },
     );
 }
 
-fn extract_error_message(line: &str) -> Option<String> {
+const MAX_REPORTED_ERRORS: usize = 5;
+
+// Distinct error messages from one rclone run, in the order they first appeared. rclone
+// often logs the same failure several times, and one run can fail for unrelated reasons.
+#[derive(Debug, Default)]
+struct RcloneErrors {
+    messages: Vec<String>,
+    seen: HashSet<String>,
+}
+
+impl RcloneErrors {
+    fn push(&mut self, message: String) {
+        if self.messages.len() < MAX_REPORTED_ERRORS && self.seen.insert(message.clone()) {
+            self.messages.push(message);
+        }
+    }
+
+    fn combined(self) -> Option<String> {
+        (!self.messages.is_empty()).then(|| self.messages.join("; "))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogSeverity {
+    Error,
+    Warning,
+}
+
+// rclone's plain-text log prefix, e.g. "2024/01/02 15:04:05 ERROR : file.txt: message".
+fn plain_log_regex() -> &'static Regex {
+    static REGEX: std::sync::OnceLock<Regex> = std::sync::OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(
+            r"^\d{4}/\d{2}/\d{2} \d{2}:\d{2}:\d{2}(?:\.\d+)? (CRITICAL|ERROR|WARNING|NOTICE)\s*: (.*)$",
+        )
+        .expect("log line regex")
+    })
+}
+
+// Only rclone's own level decides whether a line is an error, never the word "error" in a file
+// name or a "0 errors" summary. Stats lines are logged at NOTICE but are progress, not notices.
+fn classify_log_line(line: &str) -> Option<(LogSeverity, String)> {
     if line.trim_start().starts_with('{') {
         if let Ok(value) = serde_json::from_str::<Value>(line) {
+            if value.get("stats").is_some() {
+                return None;
+            }
             let level = value.get("level").and_then(|v| v.as_str()).unwrap_or("");
-            if level.eq_ignore_ascii_case("error") {
-                if let Some(msg) = value.get("msg").and_then(|v| v.as_str()) {
-                    return Some(msg.to_string());
-                }
-                if let Some(err) = value.get("error").and_then(|v| v.as_str()) {
-                    return Some(err.to_string());
-                }
+            let severity = match level.to_ascii_lowercase().as_str() {
+                "error" | "critical" | "alert" | "emergency" => LogSeverity::Error,
+                "warning" | "notice" => LogSeverity::Warning,
+                _ => return None,
+            };
+            let message = value
+                .get("msg")
+                .and_then(|v| v.as_str())
+                .or_else(|| value.get("error").and_then(|v| v.as_str()))?
+                .trim();
+            if message.is_empty() {
+                return None;
             }
+            let message = match value.get("object").and_then(|v| v.as_str()) {
+                Some(object) if !object.is_empty() && !message.contains(object) => {
+                    format!("{object}: {message}")
+                }
+                _ => message.to_string(),
+            };
+            return Some((severity, message));
         }
     }
 
-    if line.contains("ERROR") || line.contains("error") {
-        return Some(line.to_string());
+    let trimmed = line.trim();
+    if let Some(caps) = plain_log_regex().captures(trimmed) {
+        let message = caps[2].trim();
+        if message.is_empty() {
+            return None;
+        }
+        let severity = match &caps[1] {
+            "CRITICAL" | "ERROR" => LogSeverity::Error,
+            _ => LogSeverity::Warning,
+        };
+        return Some((severity, message.to_string()));
+    }
+    // Argument errors are printed before logging is set up, e.g. "Error: unknown flag: --foo".
+    if let Some(message) = trimmed.strip_prefix("Error: ") {
+        return Some((LogSeverity::Error, message.to_string()));
     }
 
     None
 }
 
+fn collect_log_line(item_id: &str, line: &str, sa_path: &Path, errors: &mut RcloneErrors) {
+    match classify_log_line(line) {
+        Some((LogSeverity::Error, message)) => errors.push(redact_sa_path(&message, sa_path)),
+        Some((LogSeverity::Warning, message)) => {
+            warnings::record(item_id, redact_sa_path(&message, sa_path))
+        }
+        None => {}
+    }
+}
+
 fn is_retryable_error(message: &str) -> bool {
     let msg = message.to_ascii_lowercase();
-    msg.contains("ratelimit")
+    msg.starts_with(STALLED_ERROR_PREFIX)
+        || msg.contains("ratelimit")
         || msg.contains("rate limit")
         || msg.contains("userratelimitexceeded")
         || msg.contains("dailylimitexceeded")
@@ -963,6 +2405,362 @@ fn is_retryable_error(message: &str) -> bool {
         || msg.contains("too many requests")
         || msg.contains("http 429")
         || msg.contains("http 403")
+        // Transient network failures and server-side errors.
+        || msg.contains("connection reset")
+        || msg.contains("broken pipe")
+        || msg.contains("timed out")
+        || msg.contains("os error 104")
+        || msg.contains("os error 10054")
+        || msg.contains("http 500")
+        || msg.contains("http 502")
+        || msg.contains("http 503")
+        || msg.contains("http 504")
+        || is_token_server_error(&msg)
+}
+
+// rclone fetches the OAuth token itself; a 5xx from the token endpoint is worth another attempt.
+fn is_token_server_error(msg: &str) -> bool {
+    msg.contains("cannot fetch token")
+        && ["500 ", "502 ", "503 ", "504 "]
+            .iter()
+            .any(|code| msg.contains(code))
+}
+
+// invalid_grant means Google rejected the key itself (revoked key, disabled account or a
+// skewed clock), so retrying with the same account cannot succeed.
+fn is_sa_auth_failure(message: &str) -> bool {
+    message.to_ascii_lowercase().contains("invalid_grant")
+}
+
+// A skewed clock fails every account the same way, so it stops the item instead of draining
+// the pool. Otherwise the account is dropped for the rest of the job and the caller may retry
+// with another one.
+async fn handle_sa_auth_failure(
+    app: &AppHandle,
+    item: &QueueItemInput,
+    pool: &Arc<Mutex<ServiceAccountPool>>,
+    sa_path: &Path,
+    err: &str,
+) -> Result<(), String> {
+    if let Some(skew_secs) = detect_clock_skew_secs().await {
+        return Err(format!(
+            "Service account authentication failed because the system clock is off by \
+             {skew_secs}s from Google's servers. Sync the system clock and try again. ({err})"
+        ));
+    }
+    if item.sa_override_path.is_some() {
+        return Err(format!(
+            "Pinned service account was rejected by Google: {err}"
+        ));
+    }
+    disable_service_account(app, pool, sa_path, err).await;
+    Ok(())
+}
+
+fn start_sa_cooldown(app: &AppHandle, sa_path: &Path, sa_email: Option<&str>) {
+    cooldown::start(cooldown::key(sa_path, sa_email));
+    log::info!(
+        target: "rclone",
+        "sa.cooldown sa={} secs={}",
+        sa_email.unwrap_or("-"),
+        cooldown::RATE_LIMIT_COOLDOWN.as_secs()
+    );
+    events::emit(
+        app,
+        "upload:sa_cooldown",
+        ServiceAccountCooldownEvent {
+            path: sa_path.to_string_lossy().to_string(),
+            sa_email: sa_email.map(str::to_string),
+            remaining_secs: cooldown::RATE_LIMIT_COOLDOWN.as_secs(),
+            timestamp_unix_ms: now_ms(),
+        },
+    );
+}
+
+async fn disable_service_account(
+    app: &AppHandle,
+    pool: &Arc<Mutex<ServiceAccountPool>>,
+    sa_path: &Path,
+    reason: &str,
+) {
+    let mut guard = pool.lock().await;
+    guard.disabled.insert(sa_path.to_path_buf());
+    let Some(idx) = guard
+        .accounts
+        .iter()
+        .position(|entry| entry.path == sa_path)
+    else {
+        return;
+    };
+    let removed = guard.accounts.remove(idx);
+    log::warn!(
+        target: "rclone",
+        "sa.disabled path={} remaining={} reason={}",
+        removed.path.to_string_lossy(),
+        guard.accounts.len(),
+        reason
+    );
+    events::emit(
+        app,
+        "upload:sa_disabled",
+        ServiceAccountDisabledEvent {
+            path: removed.path.to_string_lossy().to_string(),
+            sa_email: removed.email,
+            reason: reason.to_string(),
+            remaining: guard.accounts.len() as u32,
+            timestamp_unix_ms: now_ms(),
+        },
+    );
+}
+
+const CLOCK_SKEW_TOLERANCE_SECS: i64 = 60;
+
+// Compares the local clock with the Date header from Google's token endpoint. Returns the
+// offset in seconds when it exceeds the tolerance, None when in sync or unreachable.
+async fn detect_clock_skew_secs() -> Option<i64> {
+    let response = rc_client()
+        .head("https://oauth2.googleapis.com/token")
+        .send()
+        .await
+        .ok()?;
+    let date = response
+        .headers()
+        .get(reqwest::header::DATE)?
+        .to_str()
+        .ok()?;
+    let server_secs = parse_http_date(date)?;
+    let local_secs = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs() as i64;
+    let skew = local_secs - server_secs;
+    (skew.abs() > CLOCK_SKEW_TOLERANCE_SECS).then_some(skew)
+}
+
+// Parses an IMF-fixdate such as "Sun, 06 Nov 1994 08:49:37 GMT" into unix seconds.
+fn parse_http_date(value: &str) -> Option<i64> {
+    let mut parts = value.split_whitespace().skip(1);
+    let day: i64 = parts.next()?.parse().ok()?;
+    let month = match parts.next()? {
+        "Jan" => 1,
+        "Feb" => 2,
+        "Mar" => 3,
+        "Apr" => 4,
+        "May" => 5,
+        "Jun" => 6,
+        "Jul" => 7,
+        "Aug" => 8,
+        "Sep" => 9,
+        "Oct" => 10,
+        "Nov" => 11,
+        "Dec" => 12,
+        _ => return None,
+    };
+    let year: i64 = parts.next()?.parse().ok()?;
+    let mut time = parts
+        .next()?
+        .split(':')
+        .map(|part| part.parse::<i64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+
+    // Days since the epoch for a proleptic Gregorian date (Howard Hinnant's days_from_civil).
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    Some(days * 86_400 + hour * 3_600 + minute * 60 + second)
+}
+
+const STALL_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const STALLED_ERROR_PREFIX: &str = "transfer stalled";
+
+// Tracks when rclone last reported any change in its stats. Time spent paused does not
+// count toward the stall timeout.
+struct StallWatch {
+    timeout: Option<Duration>,
+    last_progress: Instant,
+    last_stats: Option<(u64, u64, u64)>,
+}
+
+impl StallWatch {
+    fn new(timeout_minutes: u32) -> Self {
+        Self {
+            timeout: (timeout_minutes > 0)
+                .then(|| Duration::from_secs(u64::from(timeout_minutes) * 60)),
+            last_progress: Instant::now(),
+            last_stats: None,
+        }
+    }
+
+    fn observe(&mut self, line: &str) {
+        let Some(stats) = parse_json_stats_counters(line) else {
+            return;
+        };
+        if self.last_stats != Some(stats) {
+            self.last_stats = Some(stats);
+            self.last_progress = Instant::now();
+        }
+    }
+
+    fn is_stalled(&mut self, control: &UploadControlHandle, item_id: &str) -> bool {
+        let Some(timeout) = self.timeout else {
+            return false;
+        };
+        if *control.pause_rx.borrow() || control.paused_items_rx.borrow().contains(item_id) {
+            self.last_progress = Instant::now();
+            return false;
+        }
+        self.last_progress.elapsed() >= timeout
+    }
+}
+
+async fn abort_stalled(
+    app: &AppHandle,
+    item: &QueueItemInput,
+    child: &mut dyn RcloneChild,
+    sa_email: Option<String>,
+) -> String {
+    log::warn!(target: "rclone", "upload.stalled id={} path={}", item.id, item.path);
+    let _ = child.start_kill();
+    let message = "Transfer stalled: no progress from rclone, restarting".to_string();
+    events::emit(
+        app,
+        "upload:item_status",
+        ItemStatusEvent {
+            item_id: item.id.clone(),
+            path: item.path.clone(),
+            kind: item.kind.clone(),
+            status: "stalled".to_string(),
+            message: Some(message.clone()),
+            sa_email,
+            skipped_count: None,
+            wall_time_ms: None,
+            active_time_ms: None,
+            timestamp_unix_ms: now_ms(),
+        },
+    );
+    message
+}
+
+const RC_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const RC_MAX_POLL_FAILURES: u32 = 3;
+
+// rclone's remote control server, started on a random local port for every process. Once
+// its address appears in the log, stats are polled from it and cancel/pause go through it;
+// until then, or if it never comes up, the log lines remain the only source.
+#[derive(Clone, Default)]
+struct RcEndpoint(Arc<std::sync::OnceLock<String>>);
+
+impl RcEndpoint {
+    fn get(&self) -> Option<&str> {
+        self.0.get().map(String::as_str)
+    }
+
+    fn observe(&self, line: &str, stats_tx: &mpsc::WeakSender<String>) {
+        if self.0.get().is_some() {
+            return;
+        }
+        let Some(addr) = parse_rc_address(line) else {
+            return;
+        };
+        if self.0.set(addr.clone()).is_ok() {
+            log::debug!(target: "rclone", "rc.ready addr={}", addr);
+            tokio::spawn(poll_rc_stats(addr, stats_tx.clone()));
+        }
+    }
+}
+
+fn rc_client() -> &'static reqwest::Client {
+    static CLIENT: std::sync::OnceLock<reqwest::Client> = std::sync::OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .user_agent(crate::USER_AGENT)
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap_or_default()
+    })
+}
+
+async fn rc_call(addr: &str, method: &str, body: Value) -> Result<Value, String> {
+    rc_client()
+        .post(format!("{addr}{method}"))
+        .json(&body)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("rc {method} failed: {e}"))?
+        .json()
+        .await
+        .map_err(|e| format!("rc {method} returned invalid JSON: {e}"))
+}
+
+// Feeds core/stats into the line channel in the same {"stats": ...} shape as the JSON log,
+// so the existing parsers pick it up. Holds only a weak sender so the channel still closes
+// when rclone's output ends.
+async fn poll_rc_stats(addr: String, stats_tx: mpsc::WeakSender<String>) {
+    let mut failures = 0_u32;
+    loop {
+        tokio::time::sleep(RC_POLL_INTERVAL).await;
+        let Some(tx) = stats_tx.upgrade() else {
+            break;
+        };
+        match rc_call(&addr, "core/stats", serde_json::json!({})).await {
+            Ok(stats) => {
+                failures = 0;
+                let line = serde_json::json!({ "stats": stats }).to_string();
+                if tx.send(line).await.is_err() {
+                    break;
+                }
+            }
+            Err(e) => {
+                failures += 1;
+                log::debug!(target: "rclone", "rc.poll_failed addr={} error={}", addr, e);
+                if failures >= RC_MAX_POLL_FAILURES {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+// Asks rclone to quit through rc when it is up; otherwise signals the process, which is
+// not possible on Windows.
+async fn stop_rclone(rc: &RcEndpoint, pid: u32, item_id: &str) {
+    log::debug!(target: "rclone", "upload.cancel id={}", item_id);
+    if let Some(addr) = rc.get() {
+        match rc_call(addr, "core/quit", serde_json::json!({})).await {
+            Ok(_) => return,
+            Err(e) => log::debug!(target: "rclone", "rc.quit_failed id={} error={}", item_id, e),
+        }
+    }
+    #[cfg(unix)]
+    {
+        let _ = signal_process(pid, libc::SIGTERM);
+    }
+    #[cfg(windows)]
+    {
+        let _ = pid;
+        log::debug!(
+            target: "rclone",
+            "upload.cancel skipped on Windows id={}",
+            item_id
+        );
+    }
+}
+
+fn parse_rc_address(line: &str) -> Option<String> {
+    let start = line.find("http://127.0.0.1:")?;
+    let addr = line[start..]
+        .split(|c: char| c.is_whitespace() || c == '"')
+        .next()?;
+    if !line.contains("remote control") {
+        return None;
+    }
+    Some(if addr.ends_with('/') {
+        addr.to_string()
+    } else {
+        format!("{addr}/")
+    })
 }
 
 async fn monitor_pause_state(
@@ -970,10 +2768,12 @@ async fn monitor_pause_state(
     control: UploadControlHandle,
     item: QueueItemInput,
     pid: u32,
+    rc: RcEndpoint,
     mut done_rx: watch::Receiver<bool>,
 ) {
+    // Whether the process is currently suspended through ntdll rather than throttled.
     #[cfg(windows)]
-    let _pid = pid;
+    let mut suspended = false;
     let mut pause_all_rx = control.pause_rx.clone();
     let mut paused_items_rx = control.paused_items_rx.clone();
     let mut canceled_items_rx = control.canceled_items_rx.clone();
@@ -984,43 +2784,20 @@ async fn monitor_pause_state(
             break;
         }
 
-        if control.is_canceled() {
-            log::debug!(target: "rclone", "upload.cancel id={}", item.id);
-            #[cfg(unix)]
-            {
-                let _ = signal_process(pid, libc::SIGTERM);
-            }
-            #[cfg(windows)]
-            {
-                log::debug!(
-                    target: "rclone",
-                    "upload.cancel skipped on Windows id={}",
-                    item.id
-                );
-            }
-            break;
-        }
-
-        if canceled_items_rx.borrow().contains(&item.id) {
-            log::debug!(target: "rclone", "upload.cancel id={}", item.id);
-            #[cfg(unix)]
-            {
-                let _ = signal_process(pid, libc::SIGTERM);
-            }
+        if control.is_canceled() || canceled_items_rx.borrow().contains(&item.id) {
+            // A suspended rclone cannot answer rc's core/quit.
             #[cfg(windows)]
-            {
-                log::debug!(
-                    target: "rclone",
-                    "upload.cancel skipped on Windows id={}",
-                    item.id
-                );
+            if suspended {
+                let _ = set_process_suspended_windows(pid, false);
             }
+            stop_rclone(&rc, pid, &item.id).await;
             break;
         }
 
         let should_pause = *pause_all_rx.borrow() || paused_items_rx.borrow().contains(&item.id);
         if should_pause != is_paused {
             is_paused = should_pause;
+            timing::set_item_paused(&item.id, is_paused);
             log::debug!(
                 target: "rclone",
                 "upload.pause id={} paused={}",
@@ -1028,35 +2805,49 @@ async fn monitor_pause_state(
                 is_paused
             );
             #[cfg(unix)]
-            {
+            let (status, message) = {
                 let _ = if is_paused {
                     signal_process(pid, libc::SIGSTOP)
                 } else {
                     signal_process(pid, libc::SIGCONT)
                 };
-            }
+                (if is_paused { "paused" } else { "uploading" }, None)
+            };
             #[cfg(windows)]
-            {
-                log::debug!(
-                    target: "rclone",
-                    "upload.pause skipped on Windows id={} paused={}",
-                    item.id,
-                    is_paused
-                );
-            }
-            let _ = app.emit(
+            let (status, message) = if is_paused {
+                match set_process_suspended_windows(pid, true) {
+                    Ok(()) => {
+                        suspended = true;
+                        ("paused", None)
+                    }
+                    Err(e) => {
+                        log::debug!(target: "rclone", "upload.suspend_failed id={} error={}", item.id, e);
+                        soft_pause_windows(&rc, &item.id).await
+                    }
+                }
+            } else if std::mem::take(&mut suspended) {
+                if let Err(e) = set_process_suspended_windows(pid, false) {
+                    log::warn!(target: "rclone", "upload.resume_failed id={} error={}", item.id, e);
+                }
+                ("uploading", None)
+            } else {
+                resume_windows(&rc, &item.id, item.bwlimit_kib).await;
+                ("uploading", None)
+            };
+            events::emit(
+                &app,
                 "upload:item_status",
                 ItemStatusEvent {
                     item_id: item.id.clone(),
                     path: item.path.clone(),
                     kind: item.kind.clone(),
-                    status: if is_paused {
-                        "paused".to_string()
-                    } else {
-                        "uploading".to_string()
-                    },
-                    message: None,
+                    status: status.to_string(),
+                    message,
                     sa_email: None,
+                    skipped_count: None,
+                    wall_time_ms: None,
+                    active_time_ms: None,
+                    timestamp_unix_ms: now_ms(),
                 },
             );
         }
@@ -1071,30 +2862,200 @@ async fn monitor_pause_state(
     }
 }
 
+// Windows has no SIGSTOP; the undocumented but long-stable NtSuspendProcess/NtResumeProcess
+// pair from ntdll does the same. They are resolved at runtime so a missing export only
+// disables suspension.
+#[cfg(windows)]
+type NtProcessFn = unsafe extern "system" fn(windows::Win32::Foundation::HANDLE) -> i32;
+
+#[cfg(windows)]
+struct NtProcessControl {
+    _ntdll: libloading::Library,
+    suspend: NtProcessFn,
+    resume: NtProcessFn,
+}
+
+#[cfg(windows)]
+fn nt_process_control() -> Option<&'static NtProcessControl> {
+    static CONTROL: std::sync::OnceLock<Option<NtProcessControl>> = std::sync::OnceLock::new();
+    CONTROL
+        .get_or_init(|| {
+            // SAFETY: both exports take a process handle and return an NTSTATUS; the library
+            // is kept alive alongside the function pointers.
+            unsafe {
+                let ntdll = libloading::Library::new("ntdll.dll").ok()?;
+                let suspend = *ntdll.get::<NtProcessFn>(b"NtSuspendProcess\0").ok()?;
+                let resume = *ntdll.get::<NtProcessFn>(b"NtResumeProcess\0").ok()?;
+                Some(NtProcessControl {
+                    _ntdll: ntdll,
+                    suspend,
+                    resume,
+                })
+            }
+        })
+        .as_ref()
+}
+
+#[cfg(windows)]
+fn set_process_suspended_windows(pid: u32, suspend: bool) -> Result<(), String> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{OpenProcess, PROCESS_SUSPEND_RESUME};
+
+    let control =
+        nt_process_control().ok_or_else(|| "NtSuspendProcess is unavailable".to_string())?;
+    // SAFETY: the handle is only used for the NT call below and closed right after.
+    unsafe {
+        let handle = OpenProcess(PROCESS_SUSPEND_RESUME, false, pid)
+            .map_err(|e| format!("Failed to open rclone process: {e}"))?;
+        let status = if suspend {
+            (control.suspend)(handle)
+        } else {
+            (control.resume)(handle)
+        };
+        let _ = CloseHandle(handle);
+        if status < 0 {
+            return Err(format!("NTSTATUS {status:#010x}"));
+        }
+    }
+    Ok(())
+}
+
+// Fallback when the process cannot be suspended: strangle its bandwidth through rc. The
+// item reports "throttled" rather than "paused" because the process, its connections and
+// any in-flight chunk stay alive.
+#[cfg(windows)]
+const WINDOWS_PAUSE_BWLIMIT: &str = "1B";
+
+#[cfg(windows)]
+async fn soft_pause_windows(rc: &RcEndpoint, item_id: &str) -> (&'static str, Option<String>) {
+    let Some(addr) = rc.get() else {
+        log::debug!(target: "rclone", "upload.pause skipped on Windows id={} reason=rc_unavailable", item_id);
+        return (
+            "paused",
+            Some("Pause is not available yet; the transfer keeps running".to_string()),
+        );
+    };
+    match rc_call(
+        addr,
+        "core/bwlimit",
+        serde_json::json!({ "rate": WINDOWS_PAUSE_BWLIMIT }),
+    )
+    .await
+    {
+        Ok(_) => (
+            "throttled",
+            Some("Throttled to near zero; Windows cannot suspend the transfer".to_string()),
+        ),
+        Err(e) => {
+            log::debug!(target: "rclone", "rc.bwlimit_failed id={} error={}", item_id, e);
+            (
+                "paused",
+                Some("Pause could not be applied; the transfer keeps running".to_string()),
+            )
+        }
+    }
+}
+
+// Resuming restores the item's own bandwidth cap, or lifts the limit when it has none.
+#[cfg(windows)]
+async fn resume_windows(rc: &RcEndpoint, item_id: &str, bwlimit_kib: Option<u32>) {
+    let Some(addr) = rc.get() else {
+        return;
+    };
+    let rate = bwlimit_rate(bwlimit_kib);
+    if let Err(e) = rc_call(addr, "core/bwlimit", serde_json::json!({ "rate": rate })).await {
+        log::debug!(target: "rclone", "rc.bwlimit_failed id={} error={}", item_id, e);
+    }
+}
+
+// rclone reads a bare K suffix as KiB/s.
+fn bwlimit_rate(bwlimit_kib: Option<u32>) -> String {
+    bwlimit_kib.map_or_else(|| "off".to_string(), |kib| format!("{kib}K"))
+}
+
 fn is_item_canceled(control: &UploadControlHandle, item_id: &str) -> bool {
     control.canceled_items_rx.borrow().contains(item_id)
 }
 
+// Filter flags for a single rclone run. The files-from list lives in a private temp file
+// that is deleted when the spec is dropped, including on early return, cancel, or panic.
+#[derive(Debug, Default)]
+struct FilterSpec {
+    files_from: Option<tempfile::TempPath>,
+    excludes: Vec<String>,
+    includes: Vec<String>,
+}
+
+impl FilterSpec {
+    fn files_from<'a>(paths: impl IntoIterator<Item = &'a str>) -> Result<Self, String> {
+        Ok(Self {
+            files_from: Some(write_rclone_list(paths)?),
+            ..Self::default()
+        })
+    }
+
+    fn push_args(&self, args: &mut Vec<String>) {
+        if let Some(path) = self.files_from.as_ref() {
+            args.push("--files-from-raw".to_string());
+            args.push(path.to_string_lossy().to_string());
+        }
+        for pattern in &self.includes {
+            args.push("--include".to_string());
+            args.push(pattern.clone());
+        }
+        for pattern in &self.excludes {
+            args.push("--exclude".to_string());
+            args.push(pattern.clone());
+        }
+    }
+}
+
+// Writes one path per line with no quoting, which is what --files-from-raw expects.
+// A newline cannot be represented in that format, so such paths are rejected.
+fn write_rclone_list<'a>(
+    lines: impl IntoIterator<Item = &'a str>,
+) -> Result<tempfile::TempPath, String> {
+    let mut file = tempfile::Builder::new()
+        .prefix("gdexplorer-")
+        .suffix(".txt")
+        .tempfile()
+        .map_err(|e| format!("Failed to create rclone list file: {e}"))?;
+    for line in lines {
+        if line.contains(['\n', '\r']) {
+            return Err(format!(
+                "Path contains a line break and cannot be listed: {line:?}"
+            ));
+        }
+        writeln!(file, "{line}").map_err(|e| format!("Failed to write rclone list file: {e}"))?;
+    }
+    file.flush()
+        .map_err(|e| format!("Failed to write rclone list file: {e}"))?;
+    Ok(file.into_temp_path())
+}
+
 fn build_rclone_args(
     prefs: &RclonePreferences,
     destination_folder_id: &str,
     item: &QueueItemInput,
     sa_path: &Path,
+    item_file_count: usize,
+    filter: Option<&FilterSpec>,
 ) -> Vec<String> {
-    let args = vec![
+    let mut args = vec![
         "copy".to_string(),
         item.path.clone(),
         format!(
             "{}:{}",
             prefs.remote_name,
             if let Some(dest_path) = item.dest_path.as_ref() {
-                dest_path.clone()
+                remote_name(dest_path)
             } else if item.kind == "folder" {
-                Path::new(&item.path)
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("folder")
-                    .to_string()
+                remote_name(
+                    Path::new(&item.path)
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("folder"),
+                )
             } else {
                 "".to_string()
             }
@@ -1104,36 +3065,123 @@ fn build_rclone_args(
         "--drive-chunk-size".to_string(),
         format!("{}M", prefs.drive_chunk_size_mib),
         "--transfers".to_string(),
-        prefs.transfers.to_string(),
+        item.transfers.unwrap_or(prefs.transfers).to_string(),
         "--checkers".to_string(),
         prefs.checkers.to_string(),
         "--stats".to_string(),
         "1s".to_string(),
         "--stats-log-level".to_string(),
-        "INFO".to_string(),
+        prefs.stats_log_level().to_string(),
         "--log-level".to_string(),
-        "INFO".to_string(),
+        prefs.copy_log_level().to_string(),
         "--use-json-log".to_string(),
+        "--rc".to_string(),
+        "--rc-addr".to_string(),
+        "127.0.0.1:0".to_string(),
+        "--rc-no-auth".to_string(),
         "--drive-service-account-file".to_string(),
         sa_path.to_string_lossy().to_string(),
     ];
 
+    #[cfg(target_os = "macos")]
+    args.push("--local-unicode-normalization".to_string());
+    if item.mode == UploadMode::Incremental {
+        // Skip files whose destination copy is the same age or newer; never delete extras.
+        args.push("--update".to_string());
+    }
+    if prefs.no_traverse_for(item_file_count) {
+        args.push("--no-traverse".to_string());
+    }
+    if prefs.use_mmap {
+        args.push("--use-mmap".to_string());
+    }
+    args.push("--buffer-size".to_string());
+    args.push(format!("{}M", prefs.buffer_size_mib));
+    if let Some(bwlimit_kib) = item.bwlimit_kib {
+        args.push("--bwlimit".to_string());
+        args.push(bwlimit_rate(Some(bwlimit_kib)));
+    }
+    if let Some(filter) = filter {
+        filter.push_args(&mut args);
+    }
+
     args
 }
 
-fn build_rclone_mkdir_args(
-    prefs: &RclonePreferences,
-    destination_folder_id: &str,
-    dir: &str,
-    sa_path: &Path,
-) -> Vec<String> {
-    vec![
+// Flags whose value is a local path; only the file name is kept in logs.
+const PATH_ARGS: [&str; 1] = ["--drive-service-account-file"];
+// Flags whose value is a credential and is never logged.
+const SECRET_ARGS: [&str; 5] = [
+    "--drive-token",
+    "--drive-client-secret",
+    "--drive-service-account-credentials",
+    "--rc-pass",
+    "--rc-htpasswd",
+];
+
+fn redact_path(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "<redacted>".to_string())
+}
+
+fn redact_arg_value(flag: &str, value: &str) -> Option<String> {
+    if PATH_ARGS.contains(&flag) {
+        Some(redact_path(value))
+    } else if SECRET_ARGS.contains(&flag) {
+        Some("<redacted>".to_string())
+    } else {
+        None
+    }
+}
+
+// Masks sensitive flag values, in both `--flag value` and `--flag=value` form, so argv can
+// be logged and pasted into bug reports.
+fn redact_args(args: &[String]) -> Vec<String> {
+    let mut redacted = Vec::with_capacity(args.len());
+    let mut pending_flag: Option<&str> = None;
+    for arg in args {
+        if let Some(flag) = pending_flag.take() {
+            redacted.push(redact_arg_value(flag, arg).unwrap_or_else(|| arg.clone()));
+            continue;
+        }
+        if let Some((flag, value)) = arg.split_once('=') {
+            if let Some(value) = redact_arg_value(flag, value) {
+                redacted.push(format!("{flag}={value}"));
+                continue;
+            }
+        }
+        if PATH_ARGS.contains(&arg.as_str()) || SECRET_ARGS.contains(&arg.as_str()) {
+            pending_flag = Some(arg.as_str());
+        }
+        redacted.push(arg.clone());
+    }
+    redacted
+}
+
+// rclone echoes the service account path in some errors; keep only its file name.
+fn redact_sa_path(message: &str, sa_path: &Path) -> String {
+    let full = sa_path.to_string_lossy();
+    if full.is_empty() {
+        return message.to_string();
+    }
+    message.replace(full.as_ref(), &redact_path(&full))
+}
+
+fn build_rclone_mkdir_args(
+    prefs: &RclonePreferences,
+    destination_folder_id: &str,
+    dir: &str,
+    sa_path: &Path,
+) -> Vec<String> {
+    vec![
         "mkdir".to_string(),
         format!("{}:{}", prefs.remote_name, dir),
         "--drive-root-folder-id".to_string(),
         destination_folder_id.to_string(),
         "--log-level".to_string(),
-        "INFO".to_string(),
+        prefs.log_level.clone(),
         "--drive-service-account-file".to_string(),
         sa_path.to_string_lossy().to_string(),
     ]
@@ -1155,49 +3203,123 @@ fn build_rclone_lsf_args(
         "--drive-root-folder-id".to_string(),
         destination_folder_id.to_string(),
         "--log-level".to_string(),
-        "INFO".to_string(),
+        prefs.log_level.clone(),
+        "--drive-service-account-file".to_string(),
+        sa_path.to_string_lossy().to_string(),
+    ]
+}
+
+// Copies a Drive file by id to `dest`, a path under the destination root whose last segment
+// is the new name; Drive does the copy, so nothing passes through this machine.
+fn build_rclone_copyid_args(
+    prefs: &RclonePreferences,
+    destination_folder_id: &str,
+    doc_id: &str,
+    dest: &str,
+    sa_path: &Path,
+) -> Vec<String> {
+    vec![
+        "backend".to_string(),
+        "copyid".to_string(),
+        format!("{}:", prefs.remote_name),
+        doc_id.to_string(),
+        dest.to_string(),
+        "--drive-root-folder-id".to_string(),
+        destination_folder_id.to_string(),
+        "--log-level".to_string(),
+        prefs.log_level.clone(),
         "--drive-service-account-file".to_string(),
         sa_path.to_string_lossy().to_string(),
     ]
 }
 
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// Preparation commands have no monitor watching the cancel flag, so they are raced against
+// it here. They are spawned with kill_on_drop, which stops rclone when the race is lost.
+async fn until_canceled<F: std::future::Future>(
+    control: &UploadControlHandle,
+    item_id: &str,
+    future: F,
+) -> Result<F::Output, String> {
+    tokio::pin!(future);
+    let mut poll = tokio::time::interval(CANCEL_POLL_INTERVAL);
+    loop {
+        if control.is_canceled() || is_item_canceled(control, item_id) {
+            return Err("Upload canceled".to_string());
+        }
+        tokio::select! {
+            output = &mut future => return Ok(output),
+            _ = poll.tick() => {}
+        }
+    }
+}
+
 async fn get_or_create_folder_id(
+    runner: &dyn RcloneRunner,
+    control: &UploadControlHandle,
+    item_id: &str,
     prefs: &RclonePreferences,
     sa_path: &Path,
     destination_folder_id: &str,
     folder_name: &str,
 ) -> Result<String, String> {
-    let mut id = lookup_folder_id(prefs, sa_path, destination_folder_id, folder_name).await?;
+    let mut id = lookup_folder_id(
+        runner,
+        control,
+        item_id,
+        prefs,
+        sa_path,
+        destination_folder_id,
+        folder_name,
+    )
+    .await?;
     if id.is_none() {
         let args = build_rclone_mkdir_args(prefs, destination_folder_id, folder_name, sa_path);
-        let status = Command::new(&prefs.rclone_path)
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .status()
-            .await
-            .map_err(|e| format!("Failed to run rclone mkdir: {e}"))?;
-        if !status.success() {
+        metrics().record_process_spawned();
+        let output = until_canceled(
+            control,
+            item_id,
+            runner::run_to_completion(runner, &prefs.rclone_path, &args),
+        )
+        .await?
+        .map_err(|e| format!("Failed to run rclone mkdir: {e}"))?;
+        if !output.status.success() {
             return Err(format!("Failed to create folder {folder_name}"));
         }
-        id = lookup_folder_id(prefs, sa_path, destination_folder_id, folder_name).await?;
+        id = lookup_folder_id(
+            runner,
+            control,
+            item_id,
+            prefs,
+            sa_path,
+            destination_folder_id,
+            folder_name,
+        )
+        .await?;
     }
 
     id.ok_or_else(|| format!("Failed to locate folder id for {folder_name}"))
 }
 
 async fn lookup_folder_id(
+    runner: &dyn RcloneRunner,
+    control: &UploadControlHandle,
+    item_id: &str,
     prefs: &RclonePreferences,
     sa_path: &Path,
     destination_folder_id: &str,
     folder_name: &str,
 ) -> Result<Option<String>, String> {
     let args = build_rclone_lsf_args(prefs, destination_folder_id, sa_path);
-    let output = Command::new(&prefs.rclone_path)
-        .args(&args)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run rclone lsf: {e}"))?;
+    metrics().record_process_spawned();
+    let output = until_canceled(
+        control,
+        item_id,
+        runner::run_to_completion(runner, &prefs.rclone_path, &args),
+    )
+    .await?
+    .map_err(|e| format!("Failed to run rclone lsf: {e}"))?;
     if !output.status.success() {
         return Err("Failed to list remote folders".to_string());
     }
@@ -1207,7 +3329,7 @@ async fn lookup_folder_id(
         let id = parts.next().unwrap_or("").trim();
         let path = parts.next().unwrap_or("").trim();
         let name = path.trim_end_matches('/');
-        if !id.is_empty() && name == folder_name {
+        if !id.is_empty() && remote_name(name) == remote_name(folder_name) {
             return Ok(Some(id.to_string()));
         }
     }
@@ -1215,6 +3337,7 @@ async fn lookup_folder_id(
 }
 
 async fn ensure_remote_dirs(
+    runner: &dyn RcloneRunner,
     control: &UploadControlHandle,
     prefs: &RclonePreferences,
     sa_path: &Path,
@@ -1227,48 +3350,99 @@ async fn ensure_remote_dirs(
             return Err("Upload canceled".to_string());
         }
         let args = build_rclone_mkdir_args(prefs, destination_folder_id, dir, sa_path);
-        #[cfg(windows)]
-        let mut command = {
-            use std::os::windows::process::CommandExt;
-            const CREATE_NO_WINDOW: u32 = 0x08000000;
-            let mut std_command = std::process::Command::new(&prefs.rclone_path);
-            std_command
-                .args(&args)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .creation_flags(CREATE_NO_WINDOW);
-            Command::from(std_command)
-        };
-        #[cfg(not(windows))]
-        let mut command = {
-            let mut command = Command::new(&prefs.rclone_path);
-            command
-                .args(&args)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped());
-            command
-        };
-
         log::debug!(
             target: "rclone",
             "upload.mkdir dir={} cmd={} args={:?}",
             dir,
             prefs.rclone_path,
-            args
+            redact_args(&args)
         );
 
-        let status = command
-            .status()
-            .await
-            .map_err(|e| format!("Failed to run rclone mkdir: {e}"))?;
-        if !status.success() {
+        metrics().record_process_spawned();
+        let output = until_canceled(
+            control,
+            item_id,
+            runner::run_to_completion(runner, &prefs.rclone_path, &args),
+        )
+        .await?
+        .map_err(|e| format!("Failed to run rclone mkdir: {e}"))?;
+        if !output.status.success() {
             return Err(format!("Failed to create folder {dir}"));
         }
     }
     Ok(())
 }
 
-fn build_rel_folder_dir_list(entries: &[FolderFileEntry]) -> Vec<String> {
+fn emit_empty_folders_created(app: &AppHandle, item: &QueueItemInput, folder_paths: Vec<String>) {
+    log::info!(
+        target: "rclone",
+        "upload.empty_folders id={} count={}",
+        item.id,
+        folder_paths.len()
+    );
+    events::emit(
+        app,
+        "upload:empty_folders_created",
+        EmptyFoldersCreatedEvent {
+            item_id: item.id.clone(),
+            count: folder_paths.len() as u32,
+            folder_paths,
+            timestamp_unix_ms: now_ms(),
+        },
+    );
+}
+
+// Folder skips are reported once this many have piled up, and the rest when the item ends.
+const SKIP_BATCH_SIZE: u32 = 10;
+
+fn emit_skipped(app: &AppHandle, item: &QueueItemInput, reason: SkipReason, count: u32) {
+    log::info!(
+        target: "rclone",
+        "upload.skip id={} reason={:?} count={}",
+        item.id,
+        reason,
+        count
+    );
+    events::emit(
+        app,
+        "upload:skipped",
+        SkippedEvent {
+            item_id: item.id.clone(),
+            path: item.path.clone(),
+            reason,
+            skipped_count: count,
+            timestamp_unix_ms: now_ms(),
+        },
+    );
+}
+
+// Collects one kind of skip for an item so a large folder does not send an event per file.
+struct SkipBatch {
+    reason: SkipReason,
+    pending: u32,
+}
+
+impl SkipBatch {
+    fn new(reason: SkipReason) -> Self {
+        Self { reason, pending: 0 }
+    }
+
+    fn add(&mut self, app: &AppHandle, item: &QueueItemInput, count: u32) {
+        self.pending = self.pending.saturating_add(count);
+        if self.pending >= SKIP_BATCH_SIZE {
+            self.flush(app, item);
+        }
+    }
+
+    fn flush(&mut self, app: &AppHandle, item: &QueueItemInput) {
+        if self.pending > 0 {
+            emit_skipped(app, item, self.reason, self.pending);
+            self.pending = 0;
+        }
+    }
+}
+
+fn build_rel_folder_dir_list(entries: &[ScannedFile]) -> Vec<String> {
     let mut dirs = HashSet::new();
     for entry in entries {
         let rel_path = Path::new(&entry.rel_path);
@@ -1278,7 +3452,7 @@ fn build_rel_folder_dir_list(entries: &[FolderFileEntry]) -> Vec<String> {
                 current.push(component);
                 let dir = current.to_string_lossy().to_string();
                 if !dir.is_empty() {
-                    dirs.insert(dir.replace('\\', "/"));
+                    dirs.insert(remote_name(&dir.replace('\\', "/")));
                 }
             }
         }
@@ -1288,16 +3462,32 @@ fn build_rel_folder_dir_list(entries: &[FolderFileEntry]) -> Vec<String> {
     list
 }
 
-fn load_service_account_files(folder: &str) -> Result<Vec<ServiceAccountFile>, String> {
+// One bad file must not block the rest of the folder: files that cannot be read or parsed
+// come back as warnings, and only a folder without a single usable key is an error.
+fn load_service_account_files(
+    folder: &str,
+) -> Result<(Vec<ServiceAccountFile>, Vec<String>), String> {
     let entries = std::fs::read_dir(folder)
         .map_err(|e| format!("Failed to read service account folder: {e}"))?;
 
     let mut accounts = Vec::new();
+    let mut warnings = Vec::new();
     for entry in entries {
-        let entry = entry.map_err(|e| format!("Failed to read folder entry: {e}"))?;
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                warnings.push(format!("Failed to read folder entry: {e}"));
+                continue;
+            }
+        };
         let path = entry.path();
-        let metadata = std::fs::metadata(&path)
-            .map_err(|e| format!("Failed to read metadata for {path:?}: {e}"))?;
+        let metadata = match std::fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                warnings.push(format!("Failed to read metadata for {path:?}: {e}"));
+                continue;
+            }
+        };
         if !metadata.is_file() {
             continue;
         }
@@ -1310,7 +3500,10 @@ fn load_service_account_files(folder: &str) -> Result<Vec<ServiceAccountFile>, S
 
         let email = match read_service_account_email(&path) {
             Ok(email) => email,
-            Err(_) => continue,
+            Err(e) => {
+                warnings.push(format!("{}: {e}", path.to_string_lossy()));
+                continue;
+            }
         };
         accounts.push(ServiceAccountFile {
             path,
@@ -1319,7 +3512,18 @@ fn load_service_account_files(folder: &str) -> Result<Vec<ServiceAccountFile>, S
         });
     }
 
-    Ok(accounts)
+    if accounts.is_empty() {
+        return Err(
+            "No valid service account JSON files found in the selected folder.".to_string(),
+        );
+    }
+    Ok((accounts, warnings))
+}
+
+fn log_service_account_warnings(warnings: &[String]) {
+    for warning in warnings {
+        log::warn!(target: "rclone", "sa.load_skipped {}", warning);
+    }
 }
 
 fn read_service_account_email(path: &Path) -> Result<Option<String>, String> {
@@ -1336,91 +3540,348 @@ fn read_service_account_email(path: &Path) -> Result<Option<String>, String> {
     Ok(parsed.client_email)
 }
 
+async fn select_service_account_for_item(
+    app: &AppHandle,
+    item: &QueueItemInput,
+    pool: &Arc<Mutex<ServiceAccountPool>>,
+    tick: &Arc<AtomicU64>,
+    exclude: &HashSet<PathBuf>,
+    strategy: SaSelectionStrategy,
+) -> Result<(PathBuf, Option<String>), String> {
+    // A pinned service account bypasses rotation; retries reuse the same file.
+    if let Some(override_path) = item.sa_override_path.as_ref() {
+        let path = PathBuf::from(override_path);
+        if !path.is_file() {
+            return Err(format!(
+                "Service account override not found: {}",
+                path.to_string_lossy()
+            ));
+        }
+        let email = read_service_account_email(&path)?;
+        return Ok((path, email));
+    }
+
+    select_service_account_excluding(app, pool, tick, exclude, strategy).await
+}
+
 async fn select_service_account_excluding(
-    pool: &Arc<Mutex<Vec<ServiceAccountFile>>>,
+    app: &AppHandle,
+    pool: &Arc<Mutex<ServiceAccountPool>>,
     tick: &Arc<AtomicU64>,
     exclude: &HashSet<PathBuf>,
+    strategy: SaSelectionStrategy,
 ) -> Result<(PathBuf, Option<String>), String> {
     let mut guard = pool.lock().await;
-    if guard.is_empty() {
-        return Err("No service account JSON files available.".to_string());
-    }
+    let accounts = &mut guard.accounts;
 
-    let mut best_idx: Option<usize> = None;
-    let mut best_used = u64::MAX;
-    for (idx, entry) in guard.iter().enumerate() {
-        if exclude.contains(&entry.path) {
+    loop {
+        if accounts.is_empty() {
+            return Err(NO_SERVICE_ACCOUNTS_ERROR.to_string());
+        }
+
+        let mut candidates: Vec<usize> = Vec::new();
+        // Only used when every candidate is cooling down: the one that is free again soonest.
+        let mut soonest_cooled: Option<(usize, Instant)> = None;
+        for (idx, entry) in accounts.iter().enumerate() {
+            if exclude.contains(&entry.path) {
+                continue;
+            }
+            let key = cooldown::key(&entry.path, entry.email.as_deref());
+            if let Some(until) = cooldown::until(&key) {
+                if soonest_cooled.is_none_or(|(_, soonest)| until < soonest) {
+                    soonest_cooled = Some((idx, until));
+                }
+                continue;
+            }
+            candidates.push(idx);
+        }
+        let best_idx = pick_service_account(accounts, &candidates, tick, strategy);
+
+        let Some(best_idx) = best_idx.or(soonest_cooled.map(|(idx, _)| idx)) else {
+            return Err("No unused service account JSON files available.".to_string());
+        };
+
+        // The folder may have been edited while the job runs; drop keys that no longer exist.
+        if !accounts[best_idx].path.is_file() {
+            let removed = accounts.remove(best_idx);
+            log::warn!(
+                target: "rclone",
+                "sa.removed path={} remaining={}",
+                removed.path.to_string_lossy(),
+                accounts.len()
+            );
+            events::emit(
+                app,
+                "upload:sa_removed",
+                ServiceAccountRemovedEvent {
+                    path: removed.path.to_string_lossy().to_string(),
+                    sa_email: removed.email,
+                    remaining: accounts.len() as u32,
+                    timestamp_unix_ms: now_ms(),
+                },
+            );
             continue;
         }
-        if entry.last_used < best_used {
-            best_idx = Some(idx);
-            best_used = entry.last_used;
+
+        let next = tick.fetch_add(1, Ordering::Relaxed) + 1;
+        accounts[best_idx].last_used = next;
+
+        let entry = &accounts[best_idx];
+        return Ok((entry.path.clone(), entry.email.clone()));
+    }
+}
+
+// Accounts never drop below this weight, so even a failing one is tried again every so often
+// and can earn its way back once it recovers.
+const MIN_SA_WEIGHT: f64 = 0.1;
+
+fn pick_service_account(
+    pool: &[ServiceAccountFile],
+    candidates: &[usize],
+    tick: &AtomicU64,
+    strategy: SaSelectionStrategy,
+) -> Option<usize> {
+    match strategy {
+        // Continues after whichever account was handed out last.
+        SaSelectionStrategy::RoundRobin => {
+            let start = pool
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| entry.last_used > 0)
+                .max_by_key(|(_, entry)| entry.last_used)
+                .map_or(0, |(idx, _)| idx + 1);
+            candidates
+                .iter()
+                .copied()
+                .find(|idx| *idx >= start)
+                .or_else(|| candidates.first().copied())
+        }
+        SaSelectionStrategy::LeastRecentlyUsed => candidates
+            .iter()
+            .copied()
+            .min_by_key(|idx| pool[*idx].last_used),
+        // An account's claim grows with the picks since it last ran, scaled by how healthy it
+        // has been; a 90% failure rate makes it wait ten times as long as a clean one.
+        SaSelectionStrategy::Weighted => {
+            let now = tick.load(Ordering::Relaxed) + 1;
+            candidates
+                .iter()
+                .copied()
+                .map(|idx| {
+                    let entry = &pool[idx];
+                    let weight =
+                        (1.0 - quota::failure_rate(entry.email.as_deref())).max(MIN_SA_WEIGHT);
+                    let waited = now.saturating_sub(entry.last_used) as f64;
+                    (idx, waited * weight)
+                })
+                .fold(
+                    None,
+                    |best: Option<(usize, f64)>, (idx, score)| match best {
+                        Some((_, best_score)) if best_score >= score => best,
+                        _ => Some((idx, score)),
+                    },
+                )
+                .map(|(idx, _)| idx)
         }
     }
+}
 
-    let Some(best_idx) = best_idx else {
-        return Err("No unused service account JSON files available.".to_string());
-    };
+async fn watch_service_account_reloads(
+    mut reload_rx: watch::Receiver<u64>,
+    pool: Arc<Mutex<ServiceAccountPool>>,
+    folder: String,
+) {
+    while reload_rx.changed().await.is_ok() {
+        let fresh = match load_service_account_files(&folder) {
+            Ok((fresh, warnings)) => {
+                log_service_account_warnings(&warnings);
+                fresh
+            }
+            Err(err) => {
+                log::warn!(target: "rclone", "sa.reload_failed error={}", err);
+                continue;
+            }
+        };
 
-    let next = tick.fetch_add(1, Ordering::Relaxed) + 1;
-    guard[best_idx].last_used = next;
+        pool.lock().await.replace_accounts(fresh);
+    }
+}
 
-    let entry = &guard[best_idx];
-    Ok((entry.path.clone(), entry.email.clone()))
+impl ServiceAccountPool {
+    // Keeps each account's rotation position across the reload.
+    fn replace_accounts(&mut self, fresh: Vec<ServiceAccountFile>) {
+        let merged = fresh
+            .into_iter()
+            .filter(|entry| !self.disabled.contains(&entry.path))
+            .map(|mut entry| {
+                if let Some(existing) = self.accounts.iter().find(|e| e.path == entry.path) {
+                    entry.last_used = existing.last_used;
+                }
+                entry
+            })
+            .collect::<Vec<_>>();
+        log::info!(
+            target: "rclone",
+            "sa.reloaded before={} after={}",
+            self.accounts.len(),
+            merged.len()
+        );
+        self.accounts = merged;
+    }
 }
 
+// Matches rclone's text stats, e.g.
+// "Transferred: 1.234 GiB / 4.567 GiB, 27%, 12.3 MiB/s, ETA 3m45s". The percentage, speed
+// and ETA are optional; rclone drops them early in a transfer and prints "ETA -" when unknown.
 fn progress_regex() -> Regex {
-    Regex::new(r"([0-9.]+)\s*([A-Za-z]+)\s*/\s*([0-9.]+)\s*([A-Za-z]+)").expect("progress regex")
+    Regex::new(
+        r"([0-9.]+)\s*([A-Za-z]+)\s*/\s*([0-9.]+)\s*([A-Za-z]+)(?:,\s*\d+%)?(?:,\s*([0-9.]+)\s*([A-Za-z]+)/s)?(?:,\s*ETA\s+(\S+))?",
+    )
+    .expect("progress regex")
+}
+
+struct TextProgress {
+    sent: u64,
+    total: u64,
+    speed: Option<f64>,
+    eta_secs: Option<u64>,
 }
 
-fn parse_progress_line(regex: &Regex, line: &str) -> Option<(u64, u64)> {
+fn parse_progress_line(regex: &Regex, line: &str) -> Option<TextProgress> {
     let caps = regex.captures(line)?;
     let sent = parse_size(&caps[1], &caps[2])?;
     let total = parse_size(&caps[3], &caps[4])?;
-    Some((sent, total))
+    let speed = caps
+        .get(5)
+        .zip(caps.get(6))
+        .and_then(|(value, unit)| parse_size(value.as_str(), unit.as_str()))
+        .map(|bytes| bytes as f64);
+    let eta_secs = caps
+        .get(7)
+        .and_then(|eta| parse_eta(eta.as_str()))
+        .map(|eta| eta.as_secs());
+    Some(TextProgress {
+        sent,
+        total,
+        speed,
+        eta_secs,
+    })
 }
 
-fn parse_json_progress(line: &str, path: &str) -> Option<(u64, u64)> {
+// rclone prints durations like "45s", "3m45s" or "1d2h3m4s".
+fn parse_eta(value: &str) -> Option<Duration> {
+    let value = value.trim_end_matches(',');
+    if value.is_empty() || value == "-" {
+        return None;
+    }
+    let mut secs = 0_f64;
+    let mut number = String::new();
+    for c in value.chars() {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            'y' => 365.0 * 86_400.0,
+            'w' => 7.0 * 86_400.0,
+            'd' => 86_400.0,
+            'h' => 3_600.0,
+            'm' => 60.0,
+            's' => 1.0,
+            _ => return None,
+        };
+        secs += number.parse::<f64>().ok()? * unit;
+        number.clear();
+    }
+    number.is_empty().then(|| Duration::from_secs_f64(secs))
+}
+
+// The path of the single file a copy is for, or None for a folder. A folder's transferring
+// entries are files inside it, so their bytes must never stand in for the folder's total.
+fn single_file_path(item: &QueueItemInput) -> Option<&str> {
+    (item.kind != "folder").then_some(item.path.as_str())
+}
+
+// With `path` set, the matching transferring entry is preferred over the aggregate counters;
+// without it only the aggregate `bytes`/`totalBytes` are used.
+fn parse_json_progress(
+    line: &str,
+    path: Option<&str>,
+    known_total: Option<u64>,
+) -> Option<(u64, u64, Option<f64>)> {
     if !line.trim_start().starts_with('{') {
         return None;
     }
     let value: Value = serde_json::from_str(line).ok()?;
     let stats = value.get("stats")?;
-    let file_name = Path::new(path)
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or(path);
-
-    if let Some(transferring) = stats.get("transferring").and_then(|v| v.as_array()) {
-        for entry in transferring {
+    let speed = stats.get("speed").and_then(|v| v.as_f64());
+
+    // Checksum passes report their files under "checking" while nothing is transferring.
+    let active = ["transferring", "checking"]
+        .into_iter()
+        .filter_map(|key| stats.get(key).and_then(|v| v.as_array()))
+        .find(|entries| !entries.is_empty());
+    if let (Some(active), Some(path)) = (active, path) {
+        let file_name = Path::new(path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(path);
+        for entry in active {
             let name = entry
-                .get("name")
-                .and_then(|v| v.as_str())
+                .as_str()
+                .or_else(|| entry.get("name").and_then(|v| v.as_str()))
                 .or_else(|| entry.get("path").and_then(|v| v.as_str()))
                 .or_else(|| entry.get("object").and_then(|v| v.as_str()));
             if let Some(name) = name {
                 if name == file_name || name.ends_with(file_name) {
-                    let bytes = entry.get("bytes").and_then(|v| v.as_u64())?;
-                    let total = entry.get("size").and_then(|v| v.as_u64())?;
-                    return Some((bytes, total));
+                    if let Some(progress) = json_entry_progress(entry, known_total, speed) {
+                        return Some(progress);
+                    }
                 }
             }
         }
 
-        if transferring.len() == 1 {
-            let entry = &transferring[0];
-            let bytes = entry.get("bytes").and_then(|v| v.as_u64())?;
-            let total = entry.get("size").and_then(|v| v.as_u64())?;
-            return Some((bytes, total));
+        if active.len() == 1 {
+            if let Some(progress) = json_entry_progress(&active[0], known_total, speed) {
+                return Some(progress);
+            }
         }
     }
 
-    let bytes = stats.get("bytes").and_then(|v| v.as_u64())?;
-    let total = stats.get("totalBytes").and_then(|v| v.as_u64())?;
-    Some((bytes, total))
+    let bytes = json_counter(stats.get("bytes"))?;
+    let total = json_counter(stats.get("totalBytes"))?;
+    Some((bytes, clamp_total(total, known_total), speed))
+}
+
+// Entries in "checking" may be bare names without byte counts; those fall through to
+// the overall stats.
+fn json_entry_progress(
+    entry: &Value,
+    known_total: Option<u64>,
+    speed: Option<f64>,
+) -> Option<(u64, u64, Option<f64>)> {
+    let bytes = json_counter(entry.get("bytes"))?;
+    let total = json_counter(entry.get("size"))?;
+    Some((bytes, clamp_total(total, known_total), speed))
+}
+
+// rclone reports some counters as negative numbers on error paths; treat those as 0.
+fn json_counter(value: Option<&Value>) -> Option<u64> {
+    let value = value?;
+    value
+        .as_u64()
+        .or_else(|| value.as_i64().map(|n| n.max(0) as u64))
+}
+
+// Ignores rclone totals smaller than the locally enumerated size so progress never goes backwards.
+fn clamp_total(total: u64, known_total: Option<u64>) -> u64 {
+    known_total.map_or(total, |known| total.max(known))
 }
 
-fn parse_json_file_progress(line: &str) -> Option<Vec<(String, u64, u64)>> {
+// (name, bytes, size, speed, eta_secs); speed and eta are only present in newer rclone releases.
+type JsonFileProgress = (String, u64, u64, Option<f64>, Option<u64>);
+
+fn parse_json_file_progress(line: &str) -> Option<Vec<JsonFileProgress>> {
     if !line.trim_start().starts_with('{') {
         return None;
     }
@@ -1434,10 +3895,14 @@ fn parse_json_file_progress(line: &str) -> Option<Vec<(String, u64, u64)>> {
             .and_then(|v| v.as_str())
             .or_else(|| entry.get("path").and_then(|v| v.as_str()))
             .or_else(|| entry.get("object").and_then(|v| v.as_str()));
-        let bytes = entry.get("bytes").and_then(|v| v.as_u64());
-        let total = entry.get("size").and_then(|v| v.as_u64());
+        let bytes = json_counter(entry.get("bytes"));
+        let total = json_counter(entry.get("size"));
+        let speed = entry.get("speed").and_then(|v| v.as_f64());
+        let eta_secs = entry
+            .get("eta")
+            .and_then(|v| v.as_u64().or_else(|| v.as_f64().map(|f| f.max(0.0) as u64)));
         if let (Some(name), Some(bytes), Some(total)) = (name, bytes, total) {
-            entries.push((name.to_string(), bytes, total));
+            entries.push((name.to_string(), bytes, total, speed, eta_secs));
         }
     }
     if entries.is_empty() {
@@ -1447,103 +3912,364 @@ fn parse_json_file_progress(line: &str) -> Option<Vec<(String, u64, u64)>> {
     }
 }
 
-fn collect_file_list(item: &QueueItemInput) -> Option<Vec<FileListEntry>> {
-    let path = PathBuf::from(&item.path);
-    let mut files = Vec::new();
+// Returns the (bytes, checks, transfers) counters from an rclone JSON stats line.
+fn parse_json_stats_counters(line: &str) -> Option<(u64, u64, u64)> {
+    if !line.trim_start().starts_with('{') {
+        return None;
+    }
+    let value: Value = serde_json::from_str(line).ok()?;
+    let stats = value.get("stats")?;
+    let counter = |key: &str| stats.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+    Some((counter("bytes"), counter("checks"), counter("transfers")))
+}
 
-    if item.kind == "file" {
-        if let Ok(metadata) = std::fs::metadata(&path) {
-            files.push(FileListEntry {
-                file_path: path.to_string_lossy().to_string(),
-                total_bytes: metadata.len(),
-            });
-        }
-        return Some(files);
+// Returns the (checks, transfers) counters from an rclone JSON stats line.
+fn parse_json_transfer_counts(line: &str) -> Option<(u64, u64)> {
+    if !line.trim_start().starts_with('{') {
+        return None;
     }
+    let value: Value = serde_json::from_str(line).ok()?;
+    let stats = value.get("stats")?;
+    let checks = stats.get("checks").and_then(|v| v.as_u64())?;
+    let transfers = stats.get("transfers").and_then(|v| v.as_u64())?;
+    Some((checks, transfers))
+}
 
-    if item.kind != "folder" {
+// Per-file failures carry the object path relative to the source root.
+fn parse_json_object_error(line: &str) -> Option<(String, String)> {
+    if !line.trim_start().starts_with('{') {
         return None;
     }
+    let value: Value = serde_json::from_str(line).ok()?;
+    let level = value.get("level").and_then(|v| v.as_str())?;
+    if !level.eq_ignore_ascii_case("error") {
+        return None;
+    }
+    let object = value.get("object").and_then(|v| v.as_str())?;
+    let message = value
+        .get("msg")
+        .and_then(|v| v.as_str())
+        .unwrap_or("upload failed");
+    Some((object.to_string(), message.to_string()))
+}
 
-    for entry in WalkDir::new(&path).into_iter().filter_map(Result::ok) {
-        if !entry.file_type().is_file() {
-            continue;
+fn is_skipped_transfer(counts: Option<(u64, u64)>) -> bool {
+    matches!(counts, Some((checks, 0)) if checks > 0)
+}
+
+// Returns whether files were dropped, in which case the folder must be uploaded file by file.
+fn apply_name_collision_policy(
+    app: &AppHandle,
+    item: &QueueItemInput,
+    scan: &mut ScanResult,
+    policy: NameCollisionPolicy,
+) -> Result<bool, String> {
+    let collisions =
+        fs_scan::find_name_collisions(scan.files.iter().map(|entry| entry.rel_path.as_str()));
+    if collisions.is_empty() {
+        return Ok(false);
+    }
+    for collision in &collisions {
+        log::warn!(
+            target: "rclone",
+            "scan.name_collision id={} parent={} names={:?} policy={:?}",
+            item.id,
+            collision.parent,
+            collision.names,
+            policy
+        );
+    }
+
+    match policy {
+        NameCollisionPolicy::KeepBoth => Ok(false),
+        NameCollisionPolicy::Fail => {
+            let first = &collisions[0];
+            Err(format!(
+                "{} name collision(s) that differ only by case, first in {:?}: {}",
+                collisions.len(),
+                first.parent,
+                first.names.join(", ")
+            ))
         }
-        let file_path = entry.path().to_path_buf();
-        if let Ok(metadata) = std::fs::metadata(&file_path) {
-            files.push(FileListEntry {
-                file_path: file_path.to_string_lossy().to_string(),
-                total_bytes: metadata.len(),
+        NameCollisionPolicy::FirstWins => {
+            let dropped_prefixes: Vec<(String, &str)> = collisions
+                .iter()
+                .flat_map(|collision| {
+                    collision.names[1..].iter().map(|name| {
+                        let prefix = if collision.parent.is_empty() {
+                            name.clone()
+                        } else {
+                            format!("{}/{}", collision.parent, name)
+                        };
+                        (prefix, collision.names[0].as_str())
+                    })
+                })
+                .collect();
+            let mut skipped = Vec::new();
+            scan.files.retain(|entry| {
+                let kept_instead = dropped_prefixes.iter().find(|(prefix, _)| {
+                    entry.rel_path == *prefix
+                        || entry
+                            .rel_path
+                            .strip_prefix(prefix.as_str())
+                            .is_some_and(|rest| rest.starts_with('/'))
+                });
+                let Some((_, kept)) = kept_instead else {
+                    return true;
+                };
+                skipped.push(fs_scan::ScanWarning {
+                    path: entry.path.to_string_lossy().to_string(),
+                    message: format!("Skipped: name collides with {kept:?}"),
+                });
+                false
             });
+            events::emit(
+                app,
+                "upload:skipped_files",
+                SkippedFilesEvent {
+                    item_id: item.id.clone(),
+                    files: skipped,
+                    timestamp_unix_ms: now_ms(),
+                },
+            );
+            Ok(true)
         }
     }
+}
 
-    if files.is_empty() {
-        None
-    } else {
-        Some(files)
+// Takes the Google Docs shortcuts out of the scan, and out of its file list too unless the
+// stubs are to be uploaded as they are.
+fn take_doc_shortcuts(scan: &mut ScanResult, policy: DocShortcutPolicy) -> Vec<DocShortcut> {
+    let shortcuts = std::mem::take(&mut scan.doc_shortcuts);
+    if policy != DocShortcutPolicy::UploadRaw && !shortcuts.is_empty() {
+        let paths: HashSet<&Path> = shortcuts.iter().map(|s| s.path.as_path()).collect();
+        scan.files
+            .retain(|entry| !paths.contains(entry.path.as_path()));
     }
+    shortcuts
 }
 
-fn collect_folder_file_entries(item: &QueueItemInput) -> Option<Vec<FolderFileEntry>> {
-    if item.kind != "folder" {
-        return None;
+// Where the document behind a shortcut goes: next to where the stub would have been uploaded,
+// named after the stub without its extension.
+fn doc_shortcut_dest(item: &QueueItemInput, shortcut: &DocShortcut) -> String {
+    let dir = if item.kind == "folder" {
+        build_folder_dest_dir(&resolve_folder_dest_base(item), &shortcut.rel_path)
+    } else {
+        item.dest_path
+            .as_deref()
+            .map(remote_name)
+            .unwrap_or_default()
+    };
+    let name = shortcut
+        .path
+        .file_stem()
+        .and_then(|n| n.to_str())
+        .unwrap_or(&shortcut.doc_id);
+    join_remote_path(&dir, name)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn copy_doc_shortcut(
+    runner: &dyn RcloneRunner,
+    control: &UploadControlHandle,
+    item_id: &str,
+    prefs: &RclonePreferences,
+    sa_path: &Path,
+    destination_folder_id: &str,
+    doc_id: &str,
+    dest: &str,
+) -> Result<(), String> {
+    let args = build_rclone_copyid_args(prefs, destination_folder_id, doc_id, dest, sa_path);
+    metrics().record_process_spawned();
+    let output = until_canceled(
+        control,
+        item_id,
+        runner::run_to_completion(runner, &prefs.rclone_path, &args),
+    )
+    .await?
+    .map_err(|e| format!("Failed to run rclone backend copyid: {e}"))?;
+    if output.status.success() {
+        return Ok(());
     }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message = stderr
+        .lines()
+        .filter_map(classify_log_line)
+        .find(|(severity, _)| *severity == LogSeverity::Error)
+        .map(|(_, message)| message)
+        .unwrap_or_else(|| format!("rclone backend copyid exited with {}", output.status));
+    Err(redact_sa_path(&message, sa_path))
+}
 
-    let base = PathBuf::from(&item.path);
-    let mut entries = Vec::new();
+// Applies the doc shortcut policy to every shortcut the scan found and reports the result.
+// Only cancellation is an error; a failed copy falls back to skipping the stub.
+#[allow(clippy::too_many_arguments)]
+async fn handle_doc_shortcuts(
+    app: &AppHandle,
+    runner: &dyn RcloneRunner,
+    control: &UploadControlHandle,
+    prefs: &RclonePreferences,
+    sa_pool: &Arc<Mutex<ServiceAccountPool>>,
+    sa_tick: &Arc<AtomicU64>,
+    destination_folder_id: &str,
+    item: &QueueItemInput,
+    shortcuts: &[DocShortcut],
+    policy: DocShortcutPolicy,
+) -> Result<Vec<DocShortcutOutcome>, String> {
+    let sa_path = if policy == DocShortcutPolicy::CopyDocument {
+        let (sa_path, _sa_email) = select_service_account_for_item(
+            app,
+            item,
+            sa_pool,
+            sa_tick,
+            &HashSet::new(),
+            prefs.sa_selection_strategy,
+        )
+        .await?;
+        Some(sa_path)
+    } else {
+        None
+    };
 
-    for entry in WalkDir::new(&base).into_iter().filter_map(Result::ok) {
-        if !entry.file_type().is_file() {
-            continue;
-        }
-        let path = entry.path().to_path_buf();
-        let rel_path = path
-            .strip_prefix(&base)
-            .ok()
-            .and_then(|p| p.to_str())
-            .map(|p| p.replace('\\', "/"))
-            .unwrap_or_else(|| path.to_string_lossy().to_string());
-        if let Ok(metadata) = std::fs::metadata(&path) {
-            entries.push(FolderFileEntry {
-                path,
-                rel_path,
-                size: metadata.len(),
-            });
+    let mut outcomes = Vec::with_capacity(shortcuts.len());
+    for shortcut in shortcuts {
+        let (handling, message) = match (policy, sa_path.as_ref()) {
+            (DocShortcutPolicy::UploadRaw, _) => (DocShortcutHandling::UploadedRaw, None),
+            (DocShortcutPolicy::CopyDocument, Some(sa_path)) => {
+                let dest = doc_shortcut_dest(item, shortcut);
+                match copy_doc_shortcut(
+                    runner,
+                    control,
+                    &item.id,
+                    prefs,
+                    sa_path,
+                    destination_folder_id,
+                    &shortcut.doc_id,
+                    &dest,
+                )
+                .await
+                {
+                    Ok(()) => (DocShortcutHandling::Copied, None),
+                    Err(err) if is_item_canceled(control, &item.id) || control.is_canceled() => {
+                        return Err(err)
+                    }
+                    Err(err) => (DocShortcutHandling::CopyFailed, Some(err)),
+                }
+            }
+            _ => (
+                DocShortcutHandling::Skipped,
+                Some(
+                    "Google Docs shortcut; the document itself is not on this computer".to_string(),
+                ),
+            ),
+        };
+        let path = shortcut.path.to_string_lossy().to_string();
+        log::info!(
+            target: "rclone",
+            "upload.doc_shortcut id={} path={} doc_id={} handling={:?}",
+            item.id,
+            path,
+            shortcut.doc_id,
+            handling
+        );
+        if matches!(
+            handling,
+            DocShortcutHandling::Skipped | DocShortcutHandling::CopyFailed
+        ) {
+            warnings::record(
+                &item.id,
+                format!(
+                    "Skipped Google Docs shortcut {}: {}",
+                    shortcut.rel_path,
+                    message.as_deref().unwrap_or_default()
+                ),
+            );
         }
+        outcomes.push(DocShortcutOutcome {
+            path,
+            doc_id: shortcut.doc_id.clone(),
+            handling,
+            message,
+        });
     }
 
-    if entries.is_empty() {
-        None
+    let skipped = outcomes
+        .iter()
+        .filter(|outcome| {
+            matches!(
+                outcome.handling,
+                DocShortcutHandling::Skipped | DocShortcutHandling::CopyFailed
+            )
+        })
+        .count() as u32;
+    if skipped > 0 {
+        emit_skipped(app, item, SkipReason::DocShortcut, skipped);
+    }
+    events::emit(
+        app,
+        "upload:doc_shortcuts",
+        DocShortcutsEvent {
+            item_id: item.id.clone(),
+            files: outcomes.clone(),
+            timestamp_unix_ms: now_ms(),
+        },
+    );
+    Ok(outcomes)
+}
+
+fn scan_item(item: &QueueItemInput) -> Option<ScanResult> {
+    if item.kind != "file" && item.kind != "folder" {
+        return None;
+    }
+    Some(fs_scan::scan(
+        Path::new(&item.path),
+        &ScanOptions::default(),
+    ))
+}
+
+// Drive keeps names as sent, and macOS hands out decomposed (NFD) names, so "Résumé" from a
+// Mac would never equal the same name typed elsewhere. Everything named on the remote side
+// is composed (NFC) first; local paths used for reading are left untouched.
+fn remote_name(name: &str) -> String {
+    name.nfc().collect()
+}
+
+// How rclone reports a local name: composed on macOS, where --local-unicode-normalization is
+// passed, and byte-for-byte elsewhere since those filesystems do not fold normalization forms.
+fn rclone_local_name(name: &str) -> String {
+    if cfg!(target_os = "macos") {
+        remote_name(name)
     } else {
-        Some(entries)
+        name.to_string()
     }
 }
 
 fn resolve_folder_dest_base(item: &QueueItemInput) -> String {
     if let Some(dest_path) = item.dest_path.as_ref() {
-        return dest_path.clone();
+        return remote_name(dest_path);
     }
-    Path::new(&item.path)
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("folder")
-        .to_string()
+    remote_name(
+        Path::new(&item.path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("folder"),
+    )
 }
 
+// Split as text rather than through Path so '\\' separates segments on every platform.
 fn build_folder_dest_dir(base: &str, rel_path: &str) -> String {
-    let rel_dir = Path::new(rel_path)
-        .parent()
-        .and_then(|p| p.to_str())
-        .unwrap_or("")
-        .replace('\\', "/");
-    if rel_dir.is_empty() {
-        base.to_string()
-    } else if base.is_empty() {
-        rel_dir
-    } else {
-        format!("{}/{}", base, rel_dir)
-    }
+    let rel_path = rel_path.replace('\\', "/");
+    let rel_dir = rel_path.rsplit_once('/').map_or("", |(dir, _)| dir);
+    join_remote_path(base, rel_dir)
+}
+
+fn join_remote_path(base: &str, rel_dir: &str) -> String {
+    let rel_dir = remote_name(rel_dir);
+    base.split('/')
+        .chain(rel_dir.split('/'))
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
 }
 
 fn parse_size(value: &str, unit: &str) -> Option<u64> {
@@ -1574,46 +4300,905 @@ fn signal_process(pid: u32, signal: i32) -> Result<(), String> {
     }
 }
 
-async fn read_rclone_stream<R: tokio::io::AsyncRead + Unpin>(
-    mut reader: R,
-    tx: mpsc::Sender<String>,
-) {
-    let mut buf = [0_u8; 4096];
-    let mut pending = Vec::new();
+// rclone lines are short, but one pathological line must not grow the buffer without bound.
+const MAX_STREAM_LINE_BYTES: usize = 1024 * 1024;
+
+#[cfg(not(windows))]
+fn decode_line(line: &[u8]) -> std::borrow::Cow<'_, str> {
+    String::from_utf8_lossy(line)
+}
 
+// On East Asian Windows systems rclone can print paths in the OEM code page rather than UTF-8.
+#[cfg(windows)]
+fn decode_line(line: &[u8]) -> std::borrow::Cow<'_, str> {
+    if let Ok(line) = std::str::from_utf8(line) {
+        return std::borrow::Cow::Borrowed(line);
+    }
+    log::debug!(target: "rclone", "rclone.stream_non_utf8 bytes={}", line.len());
+    for encoding in [encoding_rs::SHIFT_JIS, encoding_rs::EUC_KR] {
+        let (decoded, _, had_errors) = encoding.decode(line);
+        if !had_errors {
+            return decoded;
+        }
+    }
+    String::from_utf8_lossy(line)
+}
+
+fn forward_line(line: &[u8]) -> Option<String> {
+    let line = decode_line(line);
+    let line = line.trim();
+    (!line.is_empty()).then(|| line.to_string())
+}
+
+async fn read_rclone_stream<R: tokio::io::AsyncRead + Unpin>(reader: R, tx: mpsc::Sender<String>) {
+    let mut reader = BufReader::new(reader);
+    let mut line = Vec::new();
+    let mut truncated = false;
     loop {
-        let read = match reader.read(&mut buf).await {
-            Ok(0) => break,
-            Ok(n) => n,
-            Err(_) => break,
+        let available = match reader.fill_buf().await {
+            Ok(available) => available,
+            Err(e) => {
+                log::debug!(target: "rclone", "rclone.stream_read_failed error={}", e);
+                break;
+            }
         };
-        pending.extend_from_slice(&buf[..read]);
-
-        let mut start = 0;
-        for i in 0..pending.len() {
-            let b = pending[i];
-            if b == b'\n' || b == b'\r' {
-                if i > start {
-                    let line = String::from_utf8_lossy(&pending[start..i])
-                        .trim()
-                        .to_string();
-                    if !line.is_empty() {
-                        let _ = tx.send(line).await;
+        if available.is_empty() {
+            // The final line may have no trailing newline.
+            if let Some(line) = forward_line(&line) {
+                let _ = tx.send(line).await;
+            }
+            break;
+        }
+
+        let newline = available.iter().position(|&b| b == b'\n');
+        let chunk = &available[..newline.unwrap_or(available.len())];
+        let room = MAX_STREAM_LINE_BYTES.saturating_sub(line.len());
+        if chunk.len() > room {
+            truncated = true;
+        }
+        line.extend_from_slice(&chunk[..chunk.len().min(room)]);
+        let consumed = newline.map_or(available.len(), |i| i + 1);
+        reader.consume(consumed);
+
+        if newline.is_some() {
+            if truncated {
+                log::warn!(
+                    target: "rclone",
+                    "rclone.stream_line_truncated max_bytes={}",
+                    MAX_STREAM_LINE_BYTES
+                );
+                truncated = false;
+            }
+            if let Some(line) = forward_line(&line) {
+                let _ = tx.send(line).await;
+            }
+            line.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn stream_lines(input: &[u8]) -> Vec<String> {
+        let (tx, mut rx) = mpsc::channel(64);
+        read_rclone_stream(input, tx).await;
+        let mut lines = Vec::new();
+        while let Some(line) = rx.recv().await {
+            lines.push(line);
+        }
+        lines
+    }
+
+    #[tokio::test]
+    async fn read_rclone_stream_splits_crlf_lines() {
+        let lines = stream_lines(b"first\r\nsecond\r\n\r\nthird").await;
+        assert_eq!(lines, ["first", "second", "third"]);
+    }
+
+    #[tokio::test]
+    async fn read_rclone_stream_keeps_final_line_without_newline() {
+        let lines = stream_lines(b"{\"level\":\"info\"}\n{\"level\":\"error\"}").await;
+        assert_eq!(lines, [r#"{"level":"info"}"#, r#"{"level":"error"}"#]);
+    }
+
+    #[tokio::test]
+    async fn read_rclone_stream_survives_invalid_utf8() {
+        let lines = stream_lines(b"bad \xff byte\nafter\n").await;
+        assert_eq!(lines, ["bad \u{fffd} byte", "after"]);
+    }
+
+    #[test]
+    fn parse_json_progress_uses_aggregate_counters_for_folders() {
+        let line = r#"{"stats":{"bytes":300,"totalBytes":1000,"speed":10.0,
+            "transferring":[{"name":"sub/a.bin","bytes":50,"size":100}]}}"#
+            .replace('\n', "");
+        assert_eq!(
+            parse_json_progress(&line, None, Some(1000)),
+            Some((300, 1000, Some(10.0)))
+        );
+        // A file item still follows its own entry.
+        assert_eq!(
+            parse_json_progress(&line, Some("/local/a.bin"), Some(100)),
+            Some((50, 100, Some(10.0)))
+        );
+    }
+
+    #[test]
+    fn build_folder_dest_dir_collapses_redundant_separators() {
+        assert_eq!(
+            build_folder_dest_dir("base/", "sub//dir/file.txt"),
+            "base/sub/dir"
+        );
+        assert_eq!(build_folder_dest_dir("", "file.txt"), "");
+        assert_eq!(build_folder_dest_dir("a//b", "c\\d\\e.txt"), "a/b/c/d");
+    }
+
+    #[test]
+    fn files_from_lists_one_unquoted_path_per_line() {
+        let paths = [
+            "plain.txt",
+            "with space/file name.mkv",
+            "ünïcødé/日本語.txt",
+            "quote\"d 'name'",
+        ];
+        let filter = FilterSpec::files_from(paths).expect("list file");
+        let mut args = Vec::new();
+        filter.push_args(&mut args);
+        assert_eq!(args[0], "--files-from-raw");
+        let contents = std::fs::read_to_string(&args[1]).expect("read list file");
+        assert_eq!(contents.lines().collect::<Vec<_>>(), paths);
+        assert!(contents.ends_with('\n'));
+    }
+
+    #[test]
+    fn files_from_rejects_paths_with_line_breaks() {
+        assert!(FilterSpec::files_from(["ok.txt", "bad\nname.txt"]).is_err());
+        assert!(FilterSpec::files_from(["bad\rname.txt"]).is_err());
+    }
+
+    #[test]
+    fn files_from_list_is_removed_with_the_filter() {
+        let filter = FilterSpec::files_from(["a.txt"]).expect("list file");
+        let mut args = Vec::new();
+        filter.push_args(&mut args);
+        let path = PathBuf::from(&args[1]);
+        assert!(path.exists());
+        drop(filter);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn parse_json_progress_falls_back_to_checking_entries() {
+        let line = r#"{"stats":{"bytes":0,"totalBytes":100,"transferring":[],
+            "checking":[{"name":"a.bin","bytes":40,"size":100}]}}"#
+            .replace('\n', "");
+        assert_eq!(
+            parse_json_progress(&line, Some("/local/a.bin"), Some(100)),
+            Some((40, 100, None))
+        );
+        // Bare names carry no counters, so the aggregate stats are used.
+        let line = r#"{"stats":{"bytes":10,"totalBytes":100,"checking":["a.bin"]}}"#;
+        assert_eq!(
+            parse_json_progress(line, Some("/local/a.bin"), Some(100)),
+            Some((10, 100, None))
+        );
+    }
+
+    #[test]
+    fn parse_json_progress_clamps_negative_counters() {
+        let line = r#"{"stats":{"bytes":-1,"totalBytes":500}}"#;
+        assert_eq!(parse_json_progress(line, None, None), Some((0, 500, None)));
+        let line = r#"{"stats":{"bytes":-1,"totalBytes":-1}}"#;
+        assert_eq!(
+            parse_json_progress(line, None, Some(800)),
+            Some((0, 800, None))
+        );
+    }
+
+    #[test]
+    fn classify_log_line_trusts_only_rclone_levels() {
+        let cases: &[(&str, Option<(LogSeverity, &str)>)] = &[
+            (
+                "2024/01/02 03:04:05 ERROR : a.txt: Failed to copy: googleapi: Error 403",
+                Some((
+                    LogSeverity::Error,
+                    "a.txt: Failed to copy: googleapi: Error 403",
+                )),
+            ),
+            (
+                "2024/01/02 03:04:05.123 CRITICAL : Failed to create file system",
+                Some((LogSeverity::Error, "Failed to create file system")),
+            ),
+            (
+                r#"{"level":"error","msg":"Failed to copy: quota exceeded","object":"a.txt"}"#,
+                Some((LogSeverity::Error, "a.txt: Failed to copy: quota exceeded")),
+            ),
+            (
+                "Error: unknown flag: --bogus",
+                Some((LogSeverity::Error, "unknown flag: --bogus")),
+            ),
+            // The word "error" in a file name or an info message is not an error.
+            (
+                "2024/01/02 03:04:05 INFO  : error-log.txt: Copied (new)",
+                None,
+            ),
+            (
+                r#"{"level":"info","msg":"Copied (new)","object":"errors/error.txt"}"#,
+                None,
+            ),
+            ("Transferred: 1 / 1, 100%, error.bin", None),
+            (
+                r#"{"level":"error","stats":{"errors":1,"lastError":"boom"}}"#,
+                None,
+            ),
+            (r#"{"level":"error","msg":"   "}"#, None),
+        ];
+        for (line, expected) in cases {
+            let expected = expected.map(|(severity, message)| (severity, message.to_string()));
+            assert_eq!(classify_log_line(line), expected, "{line}");
+        }
+    }
+
+    #[test]
+    fn classify_log_line_separates_warnings_from_errors() {
+        let cases: &[(&str, Option<(LogSeverity, &str)>)] = &[
+            (
+                "2024/01/02 03:04:05 NOTICE: b.txt: Duplicate object found in destination",
+                Some((
+                    LogSeverity::Warning,
+                    "b.txt: Duplicate object found in destination",
+                )),
+            ),
+            (
+                "2024/01/02 03:04:05 WARNING : Can't follow symlink",
+                Some((LogSeverity::Warning, "Can't follow symlink")),
+            ),
+            (
+                r#"{"level":"notice","msg":"Skipping undecodable name","object":"c.txt"}"#,
+                Some((LogSeverity::Warning, "c.txt: Skipping undecodable name")),
+            ),
+            (
+                r#"{"level":"WARNING","msg":"c.txt: already prefixed","object":"c.txt"}"#,
+                Some((LogSeverity::Warning, "c.txt: already prefixed")),
+            ),
+            (
+                r#"{"level":"alert","error":"disk full"}"#,
+                Some((LogSeverity::Error, "disk full")),
+            ),
+            (r#"{"level":"debug","msg":"Sizes identical"}"#, None),
+            ("2024/01/02 03:04:05 DEBUG : a.txt: Sizes identical", None),
+        ];
+        for (line, expected) in cases {
+            let expected = expected.map(|(severity, message)| (severity, message.to_string()));
+            assert_eq!(classify_log_line(line), expected, "{line}");
+        }
+    }
+
+    #[test]
+    fn is_retryable_error_matches_transient_failures() {
+        for message in [
+            "transfer stalled: no progress",
+            "googleapi: Error 403: Rate Limit Exceeded, rateLimitExceeded",
+            "backend rate limit hit",
+            "userRateLimitExceeded",
+            "dailyLimitExceeded",
+            "quotaExceeded",
+            "storageQuotaExceeded",
+            "HTTP 429 Too Many Requests",
+            "http 403 forbidden",
+            "read tcp: connection reset by peer",
+            "write: broken pipe",
+            "dial tcp: i/o timed out",
+            "read: os error 104",
+            "An existing connection was forcibly closed (os error 10054)",
+            "HTTP 500 Internal Server Error",
+            "http 502 bad gateway",
+            "http 503 service unavailable",
+            "http 504 gateway timeout",
+            "couldn't fetch token: cannot fetch token: 503 Service Unavailable",
+        ] {
+            assert!(is_retryable_error(message), "{message} should be retried");
+        }
+    }
+
+    #[test]
+    fn is_retryable_error_rejects_permanent_failures() {
+        for message in [
+            "file not found",
+            "directory not found",
+            "cannot fetch token: 400 Bad Request: invalid_grant",
+            "permission denied",
+            "",
+        ] {
+            assert!(
+                !is_retryable_error(message),
+                "{message} should not be retried"
+            );
+        }
+    }
+
+    // A suspended process must stop producing output and pick up again once resumed; a
+    // successful NtSuspendProcess call alone does not show that.
+    #[cfg(windows)]
+    #[test]
+    fn set_process_suspended_windows_suspends_and_resumes() {
+        use std::io::Read;
+        use std::os::windows::process::CommandExt;
+        use std::sync::atomic::AtomicUsize;
+
+        let mut child = std::process::Command::new("cmd")
+            .arg("/C")
+            .raw_arg("for /L %i in (0,0,1) do @echo tick")
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .expect("spawn writer");
+        let mut stdout = child.stdout.take().expect("stdout");
+        let received = Arc::new(AtomicUsize::new(0));
+        let reader = {
+            let received = received.clone();
+            std::thread::spawn(move || {
+                let mut buf = [0_u8; 4096];
+                while let Ok(n) = stdout.read(&mut buf) {
+                    if n == 0 {
+                        break;
                     }
+                    received.fetch_add(n, Ordering::SeqCst);
                 }
-                start = i + 1;
+            })
+        };
+        let wait_for_more_than = |floor: usize| {
+            let deadline = Instant::now() + Duration::from_secs(10);
+            while received.load(Ordering::SeqCst) <= floor {
+                assert!(Instant::now() < deadline, "no output past {floor} bytes");
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        };
+
+        let pid = child.id();
+        wait_for_more_than(0);
+        set_process_suspended_windows(pid, true).expect("suspend");
+        // Let whatever was already in the pipe drain before taking the baseline.
+        std::thread::sleep(Duration::from_millis(300));
+        let while_suspended = received.load(Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(700));
+        let still_suspended = received.load(Ordering::SeqCst);
+
+        set_process_suspended_windows(pid, false).expect("resume");
+        wait_for_more_than(still_suspended);
+
+        let _ = child.kill();
+        let _ = child.wait();
+        let _ = reader.join();
+        assert_eq!(still_suspended, while_suspended, "output while suspended");
+        assert!(set_process_suspended_windows(u32::MAX, true).is_err());
+    }
+
+    // Well above any real pid_max, so signalling it can only ever fail.
+    const FAKE_PID: u32 = 999_999_999;
+
+    // What one scripted rclone run prints and how it exits.
+    struct Transcript {
+        stdout: &'static [u8],
+        stderr: &'static [u8],
+        exit_code: i32,
+    }
+
+    // Replays transcripts in order instead of starting rclone, recording every command line.
+    #[derive(Default)]
+    struct ScriptedRunner {
+        transcripts: std::sync::Mutex<std::collections::VecDeque<Transcript>>,
+        calls: std::sync::Mutex<Vec<Vec<String>>>,
+    }
+
+    impl ScriptedRunner {
+        fn new(transcripts: impl IntoIterator<Item = Transcript>) -> Self {
+            Self {
+                transcripts: std::sync::Mutex::new(transcripts.into_iter().collect()),
+                ..Self::default()
             }
         }
+    }
+
+    impl RcloneRunner for ScriptedRunner {
+        fn spawn(&self, _rclone_path: &str, args: &[String]) -> Result<RcloneProcess, String> {
+            self.calls.lock().unwrap().push(args.to_vec());
+            let transcript = self
+                .transcripts
+                .lock()
+                .unwrap()
+                .pop_front()
+                .ok_or_else(|| "Failed to start rclone: no transcript left".to_string())?;
+            Ok(RcloneProcess {
+                pid: FAKE_PID,
+                stdout: Box::pin(transcript.stdout),
+                stderr: Box::pin(transcript.stderr),
+                child: Box::new(ScriptedChild(exit_status(transcript.exit_code))),
+            })
+        }
+    }
+
+    struct ScriptedChild(std::process::ExitStatus);
+
+    impl RcloneChild for ScriptedChild {
+        fn start_kill(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+
+        fn wait(&mut self) -> runner::WaitFuture<'_> {
+            let status = self.0;
+            Box::pin(async move { Ok(status) })
+        }
+    }
+
+    #[cfg(unix)]
+    fn exit_status(code: i32) -> std::process::ExitStatus {
+        std::os::unix::process::ExitStatusExt::from_raw(code << 8)
+    }
+
+    #[cfg(windows)]
+    fn exit_status(code: i32) -> std::process::ExitStatus {
+        std::os::windows::process::ExitStatusExt::from_raw(code as u32)
+    }
+
+    fn test_control() -> UploadControlHandle {
+        UploadControlHandle {
+            cancel: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            pause_rx: watch::channel(false).1,
+            paused_items_rx: watch::channel(HashSet::new()).1,
+            canceled_items_rx: watch::channel(HashSet::new()).1,
+            reload_sa_rx: watch::channel(0).1,
+        }
+    }
 
-        if start > 0 {
-            pending.drain(0..start);
+    #[derive(Default)]
+    struct RecordingHandler {
+        lines: Vec<String>,
+    }
+
+    impl RcloneLineHandler for RecordingHandler {
+        async fn handle_line(&mut self, line: &str) {
+            self.lines.push(line.to_string());
         }
     }
 
-    if !pending.is_empty() {
-        let line = String::from_utf8_lossy(&pending).trim().to_string();
-        if !line.is_empty() {
-            let _ = tx.send(line).await;
+    async fn replay(transcript: Transcript) -> (RcloneExit, Vec<String>) {
+        let runner = ScriptedRunner::new([transcript]);
+        let RcloneProcess {
+            stdout,
+            stderr,
+            mut child,
+            ..
+        } = runner.spawn("rclone", &[]).expect("spawn");
+        let mut handler = RecordingHandler::default();
+        let pumped = pump_rclone_output(
+            &test_control(),
+            "item-1",
+            Path::new("/keys/sa.json"),
+            StallWatch::new(0),
+            &RcEndpoint::default(),
+            (stdout, stderr),
+            child.as_mut(),
+            &mut handler,
+        )
+        .await;
+        match pumped {
+            Ok(Pumped::Exited(exit)) => (exit, handler.lines),
+            Ok(Pumped::Stalled) => panic!("transcript stalled"),
+            Err(e) => panic!("transcript failed: {e}"),
         }
     }
+
+    #[tokio::test]
+    async fn transcript_reports_every_transferring_entry() {
+        let (exit, lines) = replay(Transcript {
+            stdout: b"",
+            stderr: br#"{"level":"info","msg":"Starting","stats":{"bytes":0,"checks":0,"transfers":0,"transferring":[{"name":"a.bin","bytes":10,"size":100},{"name":"sub/b.bin","bytes":20,"size":200}]}}
+{"level":"info","msg":"Done","stats":{"bytes":300,"checks":0,"transfers":2,"transferring":[]}}
+"#,
+            exit_code: 0,
+        })
+        .await;
+        assert!(exit.status.success());
+        assert_eq!(exit.last_counts, Some((0, 2)));
+        let entries = parse_json_file_progress(&lines[0]).expect("entries");
+        let names: Vec<_> = entries.iter().map(|e| (e.0.as_str(), e.1, e.2)).collect();
+        assert_eq!(names, [("a.bin", 10, 100), ("sub/b.bin", 20, 200)]);
+    }
+
+    #[tokio::test]
+    async fn transcript_keeps_reading_after_an_error_mid_stream() {
+        let (exit, lines) = replay(Transcript {
+            stdout: b"",
+            stderr: b"2024/01/02 03:04:05 INFO  : a.bin: Copied (new)\n\
+                2024/01/02 03:04:05 ERROR : b.bin: Failed to copy: googleapi: Error 500\n\
+                2024/01/02 03:04:06 INFO  : c.bin: Copied (new)\n",
+            exit_code: 1,
+        })
+        .await;
+        assert_eq!(lines.len(), 3);
+        assert!(!exit.status.success());
+        assert_eq!(
+            exit.failure_message(),
+            "b.bin: Failed to copy: googleapi: Error 500"
+        );
+    }
+
+    #[tokio::test]
+    async fn transcript_handles_crlf_output() {
+        let (exit, lines) = replay(Transcript {
+            stdout: b"",
+            stderr: b"{\"level\":\"info\",\"stats\":{\"checks\":1,\"transfers\":0}}\r\n\
+                2024/01/02 03:04:05 ERROR : x.bin: boom\r\n",
+            exit_code: 1,
+        })
+        .await;
+        assert!(lines.iter().all(|line| !line.ends_with('\r')));
+        assert_eq!(exit.last_counts, Some((1, 0)));
+        assert_eq!(exit.failure_message(), "x.bin: boom");
+    }
+
+    #[tokio::test]
+    async fn transcript_ignores_noise_lines() {
+        let (exit, lines) = replay(Transcript {
+            stdout: b"\n   \nrclone v1.66.0\n",
+            stderr: b"Transferred: 0 B / 0 B, -, 0 B/s, ETA -\n\
+                {\"level\":\"error\",\"stats\":{\"errors\":1}}\n\
+                {not json\n\
+                2024/01/02 03:04:05 DEBUG : error.log: Sizes identical\n",
+            exit_code: 3,
+        })
+        .await;
+        assert_eq!(lines.len(), 5);
+        assert!(!exit.status.success());
+        assert!(exit
+            .failure_message()
+            .starts_with("Rclone failed with status:"));
+    }
+
+    #[tokio::test]
+    async fn folder_lookups_go_through_the_runner() {
+        let runner = ScriptedRunner::new([Transcript {
+            stdout: b"id-1\tOther/\nid-2\tTarget/\n",
+            stderr: b"",
+            exit_code: 0,
+        }]);
+        let prefs = crate::rclone_preferences_from(&crate::AppPreferences::default());
+        let id = lookup_folder_id(
+            &runner,
+            &test_control(),
+            "item-1",
+            &prefs,
+            Path::new("/keys/sa.json"),
+            "root-id",
+            "Target",
+        )
+        .await
+        .expect("lookup");
+        assert_eq!(id.as_deref(), Some("id-2"));
+        let calls = runner.calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].iter().any(|arg| arg == "lsf"));
+    }
+
+    // Hands out one byte per read, so every line and multi-byte character spans many reads.
+    struct OneByteReader(&'static [u8]);
+
+    impl tokio::io::AsyncRead for OneByteReader {
+        fn poll_read(
+            mut self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+            buf: &mut tokio::io::ReadBuf<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            if let Some((first, rest)) = self.0.split_first() {
+                buf.put_slice(&[*first]);
+                self.0 = rest;
+            }
+            std::task::Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn read_rclone_stream_reassembles_byte_sized_reads() {
+        let (tx, mut rx) = mpsc::channel(64);
+        read_rclone_stream(
+            OneByteReader("日本語.txt: Copied\r\n{\"level\":\"error\"}\nlast ü".as_bytes()),
+            tx,
+        )
+        .await;
+        let mut lines = Vec::new();
+        while let Some(line) = rx.recv().await {
+            lines.push(line);
+        }
+        assert_eq!(
+            lines,
+            ["日本語.txt: Copied", r#"{"level":"error"}"#, "last ü"]
+        );
+    }
+
+    #[tokio::test]
+    async fn read_rclone_stream_truncates_overlong_lines() {
+        let mut input = vec![b'x'; MAX_STREAM_LINE_BYTES + 10];
+        input.extend_from_slice(b"\nnext\n");
+        let lines = stream_lines(&input).await;
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].len(), MAX_STREAM_LINE_BYTES);
+        assert_eq!(lines[1], "next");
+    }
+
+    #[test]
+    fn remote_paths_are_composed_to_nfc() {
+        assert_eq!(
+            build_folder_dest_dir("Caf\u{e9}", "Re\u{301}sume\u{301}/a.txt"),
+            "Caf\u{e9}/R\u{e9}sum\u{e9}"
+        );
+        let expected_local = if cfg!(target_os = "macos") {
+            "R\u{e9}sum\u{e9}.txt"
+        } else {
+            "Re\u{301}sume\u{301}.txt"
+        };
+        assert_eq!(
+            rclone_local_name("Re\u{301}sume\u{301}.txt"),
+            expected_local
+        );
+    }
+
+    #[tokio::test]
+    async fn lookup_folder_id_matches_a_decomposed_local_name() {
+        let runner = ScriptedRunner::new([Transcript {
+            stdout: "id-1\tR\u{e9}sum\u{e9}/\n".as_bytes(),
+            stderr: b"",
+            exit_code: 0,
+        }]);
+        let prefs = crate::rclone_preferences_from(&crate::AppPreferences::default());
+        let id = lookup_folder_id(
+            &runner,
+            &test_control(),
+            "item-1",
+            &prefs,
+            Path::new("/keys/sa.json"),
+            "root-id",
+            "Re\u{301}sume\u{301}",
+        )
+        .await
+        .expect("lookup");
+        assert_eq!(id.as_deref(), Some("id-1"));
+    }
+
+    #[tokio::test]
+    async fn transcript_reports_every_distinct_error_once() {
+        let (exit, _) = replay(Transcript {
+            stdout: b"",
+            stderr:
+                br#"{"level":"error","msg":"Failed to copy: googleapi: Error 500","object":"a.bin"}
+{"level":"error","msg":"Failed to copy: file name too long","object":"b.bin"}
+{"level":"error","msg":"Failed to copy: googleapi: Error 500","object":"a.bin"}
+{"level":"error","msg":"Failed to copy: permission denied","object":"c.bin"}
+"#,
+            exit_code: 1,
+        })
+        .await;
+        assert_eq!(
+            exit.failure_message(),
+            "a.bin: Failed to copy: googleapi: Error 500; \
+             b.bin: Failed to copy: file name too long; \
+             c.bin: Failed to copy: permission denied"
+        );
+    }
+
+    #[test]
+    fn rclone_errors_are_capped() {
+        let mut errors = RcloneErrors::default();
+        for index in 0..MAX_REPORTED_ERRORS + 3 {
+            errors.push(format!("error {index}"));
+        }
+        let combined = errors.combined().expect("errors");
+        assert_eq!(combined.split("; ").count(), MAX_REPORTED_ERRORS);
+        assert!(combined.starts_with("error 0; error 1"));
+        assert!(RcloneErrors::default().combined().is_none());
+    }
+
+    #[test]
+    fn parse_eta_reads_rclone_durations() {
+        let secs = |value: &str| parse_eta(value).map(|eta| eta.as_secs());
+        assert_eq!(secs("45s"), Some(45));
+        assert_eq!(secs("3m45s"), Some(225));
+        assert_eq!(secs("1d2h3m4s"), Some(93_784));
+        assert_eq!(secs("1.5s"), Some(1));
+        assert_eq!(secs("45s,"), Some(45));
+        assert_eq!(secs("-"), None);
+        assert_eq!(secs(""), None);
+        assert_eq!(secs("3x"), None);
+        assert_eq!(secs("45"), None);
+        assert_eq!(secs("soon"), None);
+    }
+
+    #[test]
+    fn parse_progress_line_reads_speed_and_eta() {
+        let regex = progress_regex();
+        let progress = parse_progress_line(
+            &regex,
+            "Transferred:   \t  1 GiB / 4 GiB, 25%, 12.5 MiB/s, ETA 3m45s",
+        )
+        .expect("progress");
+        assert_eq!(progress.sent, 1024 * 1024 * 1024);
+        assert_eq!(progress.total, 4 * 1024 * 1024 * 1024);
+        assert_eq!(progress.speed, Some(12.5 * 1024.0 * 1024.0));
+        assert_eq!(progress.eta_secs, Some(225));
+
+        // Early in a transfer rclone leaves out the speed and prints "ETA -".
+        let progress =
+            parse_progress_line(&regex, "Transferred: 0 B / 10 MB, 0%, ETA -").expect("progress");
+        assert_eq!((progress.sent, progress.total), (0, 10_000_000));
+        assert_eq!(progress.speed, None);
+        assert_eq!(progress.eta_secs, None);
+
+        assert!(parse_progress_line(&regex, "Transferred: 1 / 4, 25%").is_none());
+        assert!(parse_progress_line(&regex, "Elapsed time: 1m2.3s").is_none());
+    }
+
+    #[test]
+    fn load_service_account_files_skips_bad_files_with_a_warning() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let write = |name: &str, contents: &str| {
+            std::fs::write(dir.path().join(name), contents).expect("write file");
+        };
+        write(
+            "good.json",
+            r#"{"client_email":"a@x.iam.gserviceaccount.com"}"#,
+        );
+        write("no-email.JSON", "{}");
+        write("truncated.json", r#"{"client_email":"#);
+        write("notes.txt", "not a key");
+        std::fs::create_dir(dir.path().join("nested.json")).expect("create dir");
+
+        let folder = dir.path().to_string_lossy().to_string();
+        let (mut accounts, warnings) = load_service_account_files(&folder).expect("load");
+        accounts.sort_by(|a, b| a.path.cmp(&b.path));
+        let emails: Vec<_> = accounts.iter().map(|a| a.email.as_deref()).collect();
+        assert_eq!(emails, [Some("a@x.iam.gserviceaccount.com"), None]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("truncated.json"), "{}", warnings[0]);
+
+        std::fs::remove_file(dir.path().join("good.json")).expect("remove");
+        std::fs::remove_file(dir.path().join("no-email.JSON")).expect("remove");
+        assert!(load_service_account_files(&folder).is_err());
+    }
+
+    #[test]
+    fn pick_service_account_only_hands_out_candidates() {
+        let pool: Vec<_> = [3, 0, 1, 2]
+            .into_iter()
+            .enumerate()
+            .map(|(idx, last_used)| ServiceAccountFile {
+                path: PathBuf::from(format!("/keys/{idx}.json")),
+                email: None,
+                last_used,
+            })
+            .collect();
+        let tick = AtomicU64::new(3);
+        let pick = |candidates: &[usize], strategy| {
+            pick_service_account(&pool, candidates, &tick, strategy)
+        };
+
+        // Account 1 was never used, but it is excluded here.
+        assert_eq!(
+            pick(&[0, 2, 3], SaSelectionStrategy::LeastRecentlyUsed),
+            Some(2)
+        );
+        // Account 0 ran last, so round robin moves on to the first candidate after it.
+        assert_eq!(pick(&[1, 3], SaSelectionStrategy::RoundRobin), Some(1));
+        assert_eq!(pick(&[0], SaSelectionStrategy::RoundRobin), Some(0));
+        assert_eq!(pick(&[], SaSelectionStrategy::LeastRecentlyUsed), None);
+        assert_eq!(pick(&[], SaSelectionStrategy::RoundRobin), None);
+    }
+
+    #[tokio::test]
+    async fn a_finished_job_only_clears_its_own_pool() {
+        let state = DrivePoolState::default();
+        *state.0.lock().await = Some(ActivePool {
+            job_id: "job-2".to_string(),
+            pool: Arc::new(Mutex::new(ServiceAccountPool::default())),
+            tick: Arc::new(AtomicU64::new(4)),
+        });
+        state.clear("job-1").await;
+        let stats = service_account_pool_stats(&state).await.expect("pool kept");
+        assert_eq!(stats.selections, 4);
+        state.clear("job-2").await;
+        assert!(service_account_pool_stats(&state).await.is_none());
+    }
+
+    #[test]
+    fn redact_args_masks_credentials_in_both_flag_forms() {
+        let args: Vec<String> = [
+            "copy",
+            "/home/me/secret plans/a.txt",
+            "--drive-service-account-file",
+            "/home/me/keys/sa-1.json",
+            "--drive-token={\"access_token\":\"abc\"}",
+            "--rc-pass",
+            "hunter2",
+            "--drive-service-account-file=/home/me/keys/sa-2.json",
+            "--transfers=4",
+        ]
+        .map(String::from)
+        .to_vec();
+        assert_eq!(
+            redact_args(&args),
+            [
+                "copy",
+                "/home/me/secret plans/a.txt",
+                "--drive-service-account-file",
+                "sa-1.json",
+                "--drive-token=<redacted>",
+                "--rc-pass",
+                "<redacted>",
+                "--drive-service-account-file=sa-2.json",
+                "--transfers=4",
+            ]
+        );
+        // A trailing flag with no value is left as is.
+        let trailing = vec!["--rc-pass".to_string()];
+        assert_eq!(redact_args(&trailing), trailing);
+    }
+
+    #[test]
+    fn reloads_keep_disabled_accounts_out() {
+        let account = |name: &str, last_used| ServiceAccountFile {
+            path: PathBuf::from(format!("/keys/{name}.json")),
+            email: None,
+            last_used,
+        };
+        let mut pool = ServiceAccountPool {
+            accounts: vec![account("a", 3)],
+            disabled: HashSet::from([PathBuf::from("/keys/b.json")]),
+        };
+        pool.replace_accounts(vec![account("a", 0), account("b", 0), account("c", 0)]);
+        let kept: Vec<_> = pool
+            .accounts
+            .iter()
+            .map(|entry| (entry.path.to_string_lossy().into_owned(), entry.last_used))
+            .collect();
+        assert_eq!(
+            kept,
+            [
+                ("/keys/a.json".to_string(), 3),
+                ("/keys/c.json".to_string(), 0)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn items_take_the_scan_queued_at_job_start() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        std::fs::write(dir.path().join("a.bin"), [0_u8; 3]).expect("write file");
+        let item: QueueItemInput = serde_json::from_value(serde_json::json!({
+            "id": "scan-item",
+            "path": dir.path().to_string_lossy(),
+            "kind": "folder",
+        }))
+        .expect("item");
+
+        let (tx, rx) = oneshot::channel();
+        let scans: PendingScans = std::sync::Mutex::new(HashMap::from([(item.id.clone(), rx)]));
+        // A queued scan is used as is, even if the folder changed since.
+        tx.send(Some(ScanResult::default())).expect("send");
+        let queued = take_item_scan(&scans, &item).await.expect("scan");
+        assert!(queued.files.is_empty());
+
+        // A second attempt at the same item scans the folder itself.
+        let fresh = take_item_scan(&scans, &item).await.expect("scan");
+        assert_eq!(fresh.total_bytes(), 3);
+    }
+
+    #[test]
+    fn file_progress_keeps_entries_of_unknown_size() {
+        let line = r#"{"level":"info","stats":{"transferring":[{"name":"stream.bin","bytes":5,"size":-1}]}}"#;
+        let entries = parse_json_file_progress(line).expect("entries");
+        assert_eq!(
+            (entries[0].0.as_str(), entries[0].1, entries[0].2),
+            ("stream.bin", 5, 0)
+        );
+    }
 }
@@ -0,0 +1,133 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::process::{ExitStatus, Stdio};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+pub type RcloneStream = Pin<Box<dyn AsyncRead + Send>>;
+pub type WaitFuture<'a> = Pin<Box<dyn Future<Output = std::io::Result<ExitStatus>> + Send + 'a>>;
+
+// Starts rclone processes for the upload engine. The engine only relies on the output
+// streams, the pid and the exit status, so a scripted stand-in can replace the real binary.
+pub trait RcloneRunner: Send + Sync {
+    fn spawn(&self, rclone_path: &str, args: &[String]) -> Result<RcloneProcess, String>;
+}
+
+pub trait RcloneChild: Send {
+    fn start_kill(&mut self) -> std::io::Result<()>;
+    fn wait(&mut self) -> WaitFuture<'_>;
+}
+
+pub struct RcloneProcess {
+    pub pid: u32,
+    pub stdout: RcloneStream,
+    pub stderr: RcloneStream,
+    pub child: Box<dyn RcloneChild>,
+}
+
+pub struct RcloneOutput {
+    pub status: ExitStatus,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+// Kills the process if the owning future is dropped before it exits, e.g. on cancellation.
+struct KillOnDrop(Box<dyn RcloneChild>);
+
+impl Drop for KillOnDrop {
+    fn drop(&mut self) {
+        let _ = self.0.start_kill();
+    }
+}
+
+// Runs a short rclone command (lsf, mkdir, backend copyid) to completion and collects its
+// output, going through `runner` like the copies do.
+pub async fn run_to_completion(
+    runner: &dyn RcloneRunner,
+    rclone_path: &str,
+    args: &[String],
+) -> Result<RcloneOutput, String> {
+    let RcloneProcess {
+        mut stdout,
+        mut stderr,
+        child,
+        ..
+    } = runner.spawn(rclone_path, args)?;
+    let mut child = KillOnDrop(child);
+    let mut out = Vec::new();
+    let mut err = Vec::new();
+    let (out_result, err_result) =
+        tokio::join!(stdout.read_to_end(&mut out), stderr.read_to_end(&mut err));
+    out_result
+        .and(err_result)
+        .map_err(|e| format!("Failed to read rclone output: {e}"))?;
+    let status = child
+        .0
+        .wait()
+        .await
+        .map_err(|e| format!("Failed to wait for rclone: {e}"))?;
+    Ok(RcloneOutput {
+        status,
+        stdout: out,
+        stderr: err,
+    })
+}
+
+// Runs the rclone binary found at the configured path.
+pub struct SystemRcloneRunner;
+
+impl RcloneChild for tokio::process::Child {
+    fn start_kill(&mut self) -> std::io::Result<()> {
+        tokio::process::Child::start_kill(self)
+    }
+
+    fn wait(&mut self) -> WaitFuture<'_> {
+        Box::pin(tokio::process::Child::wait(self))
+    }
+}
+
+impl RcloneRunner for SystemRcloneRunner {
+    fn spawn(&self, rclone_path: &str, args: &[String]) -> Result<RcloneProcess, String> {
+        #[cfg(windows)]
+        let mut command = {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            let mut std_command = std::process::Command::new(rclone_path);
+            std_command
+                .args(args)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .creation_flags(CREATE_NO_WINDOW);
+            tokio::process::Command::from(std_command)
+        };
+        #[cfg(not(windows))]
+        let mut command = {
+            let mut command = tokio::process::Command::new(rclone_path);
+            command
+                .args(args)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+            command
+        };
+
+        let mut child = command
+            .spawn()
+            .map_err(|e| format!("Failed to start rclone: {e}"))?;
+        let pid = child
+            .id()
+            .ok_or_else(|| "Failed to get rclone process id".to_string())?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| "Missing stdout".to_string())?;
+        let stderr = child
+            .stderr
+            .take()
+            .ok_or_else(|| "Missing stderr".to_string())?;
+        Ok(RcloneProcess {
+            pid,
+            stdout: Box::pin(stdout),
+            stderr: Box::pin(stderr),
+            child: Box::new(child),
+        })
+    }
+}
@@ -8,6 +8,7 @@ pub struct UploadControlHandle {
     pub pause_rx: watch::Receiver<bool>,
     pub paused_items_rx: watch::Receiver<HashSet<String>>,
     pub canceled_items_rx: watch::Receiver<HashSet<String>>,
+    pub reload_sa_rx: watch::Receiver<u64>,
 }
 
 impl UploadControlHandle {
@@ -16,6 +17,15 @@ impl UploadControlHandle {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UploadMode {
+    #[default]
+    Copy,
+    // Only uploads files that are new or newer than the destination copy; never deletes.
+    Incremental,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueueItemInput {
@@ -23,6 +33,54 @@ pub struct QueueItemInput {
     pub path: String,
     pub kind: String,
     pub dest_path: Option<String>,
+    pub sa_override_path: Option<String>,
+    #[serde(default)]
+    pub mode: UploadMode,
+    #[serde(default)]
+    pub notify_on_item_complete: bool,
+    // Per-item overrides of the rclone transfers preference and a bandwidth cap in KiB/s.
+    #[serde(default)]
+    pub transfers: Option<u16>,
+    #[serde(default)]
+    pub bwlimit_kib: Option<u32>,
+}
+
+const MAX_DEST_SEGMENT_LEN: usize = 255;
+
+// Normalizes a remote destination path relative to the destination folder and rejects
+// anything that could escape it (parent segments, absolute paths, drive prefixes).
+pub fn normalize_dest_path(dest_path: &str) -> Result<String, String> {
+    if dest_path.chars().any(char::is_control) {
+        return Err("must not contain control characters".to_string());
+    }
+
+    let unified = dest_path.replace('\\', "/");
+    if unified.starts_with('/') {
+        return Err("must be relative to the destination folder".to_string());
+    }
+
+    let mut segments = Vec::new();
+    for segment in unified.split('/') {
+        if segment.is_empty() {
+            continue;
+        }
+        if segment == "." || segment == ".." {
+            return Err("must not contain '.' or '..' segments".to_string());
+        }
+        if segment.len() > MAX_DEST_SEGMENT_LEN {
+            return Err(format!("segment exceeds {MAX_DEST_SEGMENT_LEN} bytes"));
+        }
+        segments.push(segment);
+    }
+
+    if let Some(first) = segments.first() {
+        let bytes = first.as_bytes();
+        if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+            return Err("must not start with a drive letter".to_string());
+        }
+    }
+
+    Ok(segments.join("/"))
 }
 
 pub async fn wait_if_paused(control: &UploadControlHandle, item_id: &str) -> Result<(), String> {
@@ -65,3 +123,43 @@ pub async fn wait_if_paused(control: &UploadControlHandle, item_id: &str) -> Res
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_dest_path_rejects_hostile_inputs() {
+        for hostile in [
+            "../escape",
+            "a/../../b",
+            "./a",
+            "a\\..\\b",
+            "/absolute",
+            "\\\\server\\share",
+            "C:\\Users",
+            "c:/users",
+            "tab\there",
+            "line\nbreak",
+            "nul\0byte",
+        ] {
+            assert!(
+                normalize_dest_path(hostile).is_err(),
+                "{hostile:?} should be rejected"
+            );
+        }
+        assert!(normalize_dest_path(&"x".repeat(MAX_DEST_SEGMENT_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn normalize_dest_path_cleans_separators() {
+        assert_eq!(normalize_dest_path("a//b/").unwrap(), "a/b");
+        assert_eq!(normalize_dest_path("a\\b\\c").unwrap(), "a/b/c");
+        assert_eq!(normalize_dest_path("").unwrap(), "");
+        assert_eq!(
+            normalize_dest_path("..hidden/x..y").unwrap(),
+            "..hidden/x..y"
+        );
+        assert!(normalize_dest_path(&"x".repeat(MAX_DEST_SEGMENT_LEN)).is_ok());
+    }
+}
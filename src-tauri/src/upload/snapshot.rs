@@ -0,0 +1,276 @@
+use crate::upload::events::{
+    FileProgressEvent, ItemStatusEvent, ProgressEvent, SkippedEvent, Summary,
+};
+use crate::upload::scheduler::QueueItemInput;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use tauri::{AppHandle, Listener};
+
+// Finished jobs stay queryable by id for a while so their final state is not lost.
+const MAX_RETAINED_JOBS: usize = 8;
+// Per-file detail is only kept for files that are mid-transfer, and at most this many per item.
+const MAX_ACTIVE_FILES_PER_ITEM: usize = 32;
+
+static JOBS: Mutex<Vec<JobState>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobSnapshot {
+    pub job_id: String,
+    pub started_unix_ms: u64,
+    pub destination_folder_id: String,
+    pub summary: Option<Summary>,
+    pub items: Vec<ItemSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemSnapshot {
+    pub item_id: String,
+    pub path: String,
+    pub kind: String,
+    pub status: String,
+    pub message: Option<String>,
+    pub sa_email: Option<String>,
+    pub bytes_sent: u64,
+    pub total_bytes: u64,
+    pub skipped_count: Option<u32>,
+    pub active_files: Vec<FileSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileSnapshot {
+    pub file_path: String,
+    pub bytes_sent: u64,
+    pub total_bytes: u64,
+    pub sa_email: Option<String>,
+}
+
+struct JobState {
+    job_id: String,
+    started_unix_ms: u64,
+    destination_folder_id: String,
+    summary: Option<Summary>,
+    items: Vec<ItemState>,
+    index: HashMap<String, usize>,
+    skipped: u32,
+}
+
+struct ItemState {
+    snapshot: ItemSnapshot,
+    files: BTreeMap<String, FileSnapshot>,
+}
+
+impl JobState {
+    fn item_mut(&mut self, item_id: &str) -> Option<&mut ItemState> {
+        let idx = *self.index.get(item_id)?;
+        self.items.get_mut(idx)
+    }
+
+    fn snapshot(&self) -> JobSnapshot {
+        JobSnapshot {
+            job_id: self.job_id.clone(),
+            started_unix_ms: self.started_unix_ms,
+            destination_folder_id: self.destination_folder_id.clone(),
+            summary: self.summary.clone(),
+            items: self
+                .items
+                .iter()
+                .map(|item| ItemSnapshot {
+                    active_files: item.files.values().cloned().collect(),
+                    ..item.snapshot.clone()
+                })
+                .collect(),
+        }
+    }
+}
+
+pub fn start_job(
+    job_id: &str,
+    started_unix_ms: u64,
+    destination_folder_id: &str,
+    queue: &[QueueItemInput],
+) {
+    let items: Vec<ItemState> = queue
+        .iter()
+        .map(|item| ItemState {
+            snapshot: ItemSnapshot {
+                item_id: item.id.clone(),
+                path: item.path.clone(),
+                kind: item.kind.clone(),
+                status: "queued".to_string(),
+                message: None,
+                sa_email: None,
+                bytes_sent: 0,
+                total_bytes: 0,
+                skipped_count: None,
+                active_files: Vec::new(),
+            },
+            files: BTreeMap::new(),
+        })
+        .collect();
+    let index = items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| (item.snapshot.item_id.clone(), idx))
+        .collect();
+
+    let mut jobs = JOBS.lock().unwrap_or_else(|e| e.into_inner());
+    jobs.push(JobState {
+        job_id: job_id.to_string(),
+        started_unix_ms,
+        destination_folder_id: destination_folder_id.to_string(),
+        summary: None,
+        items,
+        index,
+        skipped: 0,
+    });
+    // Drop the oldest finished jobs first; running jobs are never evicted.
+    while jobs.len() > MAX_RETAINED_JOBS {
+        let Some(idx) = jobs.iter().position(|job| job.summary.is_some()) else {
+            break;
+        };
+        jobs.remove(idx);
+    }
+}
+
+pub fn finish_job(job_id: &str, summary: &Summary) {
+    let mut jobs = JOBS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(job) = jobs.iter_mut().find(|job| job.job_id == job_id) {
+        job.summary = Some(summary.clone());
+        for item in &mut job.items {
+            item.files.clear();
+        }
+    }
+}
+
+// Returns the requested job, or the most recently started one when no id is given.
+pub fn job_snapshot(job_id: Option<&str>) -> Option<JobSnapshot> {
+    let jobs = JOBS.lock().unwrap_or_else(|e| e.into_inner());
+    match job_id {
+        Some(job_id) => jobs.iter().find(|job| job.job_id == job_id),
+        None => jobs.last(),
+    }
+    .map(JobState::snapshot)
+}
+
+// The newest job whose queue holds the item, so per-item log lines reach that job's log.
+pub fn job_for_item(item_id: &str) -> Option<String> {
+    let jobs = JOBS.lock().unwrap_or_else(|e| e.into_inner());
+    jobs.iter()
+        .rev()
+        .find(|job| job.index.contains_key(item_id))
+        .map(|job| job.job_id.clone())
+}
+
+// Everything reported through upload:skipped for the job so far.
+pub fn skipped_total(job_id: &str) -> u32 {
+    let jobs = JOBS.lock().unwrap_or_else(|e| e.into_inner());
+    jobs.iter()
+        .find(|job| job.job_id == job_id)
+        .map_or(0, |job| job.skipped)
+}
+
+// (bytes sent, total bytes) summed over the items of every job that is still running.
+pub fn aggregate_progress() -> Option<(u64, u64)> {
+    let jobs = JOBS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut running = jobs.iter().filter(|job| job.summary.is_none()).peekable();
+    running.peek()?;
+    Some(
+        running
+            .flat_map(|job| &job.items)
+            .fold((0, 0), |(sent, total), item| {
+                (
+                    sent + item.snapshot.bytes_sent.min(item.snapshot.total_bytes),
+                    total + item.snapshot.total_bytes,
+                )
+            }),
+    )
+}
+
+// Item ids are only unique within the frontend session, so the newest job that knows the
+// item wins.
+fn with_item(item_id: &str, update: impl FnOnce(&mut ItemState)) {
+    let mut jobs = JOBS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(item) = jobs.iter_mut().rev().find_map(|job| job.item_mut(item_id)) {
+        update(item);
+    }
+}
+
+fn record_item_status(event: ItemStatusEvent) {
+    with_item(&event.item_id, |item| {
+        if event.status != "uploading" {
+            item.files.clear();
+        }
+        item.snapshot.status = event.status;
+        item.snapshot.message = event.message;
+        if event.sa_email.is_some() {
+            item.snapshot.sa_email = event.sa_email;
+        }
+        if event.skipped_count.is_some() {
+            item.snapshot.skipped_count = event.skipped_count;
+        }
+    });
+}
+
+fn record_skipped(event: SkippedEvent) {
+    let mut jobs = JOBS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(job) = jobs
+        .iter_mut()
+        .rev()
+        .find(|job| job.index.contains_key(&event.item_id))
+    {
+        job.skipped = job.skipped.saturating_add(event.skipped_count);
+    }
+}
+
+fn record_progress(event: ProgressEvent) {
+    with_item(&event.item_id, |item| {
+        item.snapshot.bytes_sent = event.bytes_sent;
+        item.snapshot.total_bytes = event.total_bytes;
+    });
+}
+
+fn record_file_progress(event: FileProgressEvent) {
+    with_item(&event.item_id, |item| {
+        if event.total_bytes > 0 && event.bytes_sent >= event.total_bytes {
+            item.files.remove(&event.file_path);
+            return;
+        }
+        if !item.files.contains_key(&event.file_path)
+            && item.files.len() >= MAX_ACTIVE_FILES_PER_ITEM
+        {
+            return;
+        }
+        item.files.insert(
+            event.file_path.clone(),
+            FileSnapshot {
+                file_path: event.file_path,
+                bytes_sent: event.bytes_sent,
+                total_bytes: event.total_bytes,
+                sa_email: event.sa_email,
+            },
+        );
+    });
+}
+
+fn record<T: serde::de::DeserializeOwned>(payload: &str, apply: fn(T)) {
+    match serde_json::from_str(payload) {
+        Ok(event) => apply(event),
+        Err(e) => log::debug!(target: "rclone", "snapshot.invalid_event error={}", e),
+    }
+}
+
+// Keeps the snapshots in step with every upload event, whichever code path emitted it.
+pub fn install(app: &AppHandle) {
+    app.listen_any("upload:item_status", |e| {
+        record(e.payload(), record_item_status)
+    });
+    app.listen_any("upload:progress", |e| record(e.payload(), record_progress));
+    app.listen_any("upload:skipped", |e| record(e.payload(), record_skipped));
+    app.listen_any("upload:file_progress", |e| {
+        record(e.payload(), record_file_progress)
+    });
+}
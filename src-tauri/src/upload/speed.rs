@@ -0,0 +1,74 @@
+use crate::upload::events::{self, SpeedEvent};
+use crate::upload::scheduler::UploadControlHandle;
+use crate::upload::snapshot;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+const SPEED_INTERVAL: Duration = Duration::from_secs(1);
+const SPEED_WINDOW: Duration = Duration::from_secs(5);
+
+// Samples the job snapshot once a second and emits upload:speed for every uploading item, so
+// the progress hot path never does rate math. Stops once the job has a summary or is canceled.
+pub fn spawn(app: &AppHandle, job_id: &str, control: UploadControlHandle) {
+    let app = app.clone();
+    let job_id = job_id.to_string();
+    tokio::spawn(async move {
+        let mut windows: HashMap<String, VecDeque<(Instant, u64)>> = HashMap::new();
+        let mut interval = tokio::time::interval(SPEED_INTERVAL);
+        loop {
+            interval.tick().await;
+            if control.is_canceled() {
+                break;
+            }
+            let Some(job) = snapshot::job_snapshot(Some(&job_id)) else {
+                break;
+            };
+            if job.summary.is_some() {
+                break;
+            }
+
+            let now = Instant::now();
+            windows.retain(|item_id, _| {
+                job.items
+                    .iter()
+                    .any(|item| &item.item_id == item_id && item.status == "uploading")
+            });
+            for item in job.items.iter().filter(|item| item.status == "uploading") {
+                let window = windows.entry(item.item_id.clone()).or_default();
+                window.push_back((now, item.bytes_sent));
+                while window
+                    .front()
+                    .is_some_and(|(at, _)| now.duration_since(*at) > SPEED_WINDOW)
+                {
+                    window.pop_front();
+                }
+                let (Some(&(first_at, first_bytes)), Some(&(last_at, last_bytes))) =
+                    (window.front(), window.back())
+                else {
+                    continue;
+                };
+                let elapsed = last_at.duration_since(first_at).as_secs_f64();
+                if elapsed <= 0.0 {
+                    continue;
+                }
+                let bytes_per_sec = last_bytes.saturating_sub(first_bytes) as f64 / elapsed;
+                let eta_secs = (bytes_per_sec > 0.0 && item.total_bytes > 0).then(|| {
+                    (item.total_bytes.saturating_sub(item.bytes_sent) as f64 / bytes_per_sec).ceil()
+                        as u64
+                });
+                events::emit(
+                    &app,
+                    "upload:speed",
+                    SpeedEvent {
+                        item_id: item.item_id.clone(),
+                        path: item.path.clone(),
+                        bytes_per_sec,
+                        eta_secs,
+                        window_secs: elapsed.round() as u32,
+                    },
+                );
+            }
+        }
+    });
+}
@@ -0,0 +1,164 @@
+use crate::upload::events::ItemStatusEvent;
+use crate::upload::snapshot;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::window::{ProgressBarState, ProgressBarStatus};
+use tauri::{AppHandle, Listener, Manager, WindowEvent};
+
+// Progress events arrive far more often than the taskbar needs redrawing.
+const UPDATE_INTERVAL: Duration = Duration::from_millis(500);
+
+struct TaskbarState {
+    last_update: Option<Instant>,
+    last_percent: u64,
+    // Set when an item fails; the bar stays red until the window is focused or
+    // acknowledge_error is called.
+    errored: bool,
+}
+
+static STATE: Mutex<TaskbarState> = Mutex::new(TaskbarState {
+    last_update: None,
+    last_percent: 0,
+    errored: false,
+});
+
+fn enabled(app: &AppHandle) -> bool {
+    app.state::<crate::PreferencesState>()
+        .0
+        .borrow()
+        .show_taskbar_progress
+}
+
+fn set_progress_bar(app: &AppHandle, status: ProgressBarStatus, progress: Option<u64>) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    if let Err(e) = window.set_progress_bar(ProgressBarState {
+        status: Some(status),
+        progress,
+    }) {
+        log::debug!("Failed to update taskbar progress: {e}");
+    }
+}
+
+fn current_status(state: &TaskbarState) -> ProgressBarStatus {
+    if state.errored {
+        ProgressBarStatus::Error
+    } else {
+        ProgressBarStatus::Normal
+    }
+}
+
+// The bar is redrawn after releasing the lock: a focus event on the main thread takes the
+// same lock.
+fn on_progress(app: &AppHandle) {
+    if !enabled(app) {
+        return;
+    }
+    let (status, percent) = {
+        let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+        if state
+            .last_update
+            .is_some_and(|at| at.elapsed() < UPDATE_INTERVAL)
+        {
+            return;
+        }
+        let Some((sent, total)) = snapshot::aggregate_progress() else {
+            return;
+        };
+        state.last_update = Some(Instant::now());
+        state.last_percent = (sent * 100).checked_div(total).unwrap_or(0);
+        (current_status(&state), state.last_percent)
+    };
+    set_progress_bar(app, status, Some(percent));
+}
+
+fn on_item_status(app: &AppHandle, payload: &str) {
+    let Ok(event) = serde_json::from_str::<ItemStatusEvent>(payload) else {
+        return;
+    };
+    if event.status != "failed" {
+        return;
+    }
+    let percent = {
+        let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+        state.errored = true;
+        state.last_percent
+    };
+    if enabled(app) {
+        set_progress_bar(app, ProgressBarStatus::Error, Some(percent));
+    }
+}
+
+fn on_completed(app: &AppHandle) {
+    let errored = {
+        let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+        state.last_update = None;
+        if snapshot::aggregate_progress().is_some() {
+            // Another job is still running and will keep the bar updated.
+            return;
+        }
+        state.last_percent = 0;
+        state.errored
+    };
+    if !enabled(app) {
+        return;
+    }
+    if errored {
+        set_progress_bar(app, ProgressBarStatus::Error, Some(100));
+    } else {
+        set_progress_bar(app, ProgressBarStatus::None, None);
+    }
+}
+
+pub fn acknowledge_error(app: &AppHandle) {
+    let percent = {
+        let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+        if !state.errored {
+            return;
+        }
+        state.errored = false;
+        state.last_percent
+    };
+    if !enabled(app) {
+        return;
+    }
+    if snapshot::aggregate_progress().is_some() {
+        set_progress_bar(app, ProgressBarStatus::Normal, Some(percent));
+    } else {
+        set_progress_bar(app, ProgressBarStatus::None, None);
+    }
+}
+
+// Mirrors aggregate upload progress onto the taskbar button (Windows) or dock tile (macOS).
+pub fn install(app: &AppHandle) {
+    let handle = app.clone();
+    app.listen_any("upload:progress", move |_| on_progress(&handle));
+    let handle = app.clone();
+    app.listen_any("upload:item_status", move |e| {
+        on_item_status(&handle, e.payload())
+    });
+    let handle = app.clone();
+    app.listen_any("upload:completed", move |_| on_completed(&handle));
+
+    if let Some(window) = app.get_webview_window("main") {
+        let handle = app.clone();
+        window.on_window_event(move |event| {
+            if let WindowEvent::Focused(true) = event {
+                acknowledge_error(&handle);
+            }
+        });
+    }
+
+    // Clear the bar as soon as the preference is switched off.
+    let mut prefs_rx = app.state::<crate::PreferencesState>().0.subscribe();
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        while prefs_rx.changed().await.is_ok() {
+            let show = prefs_rx.borrow_and_update().show_taskbar_progress;
+            if !show {
+                set_progress_bar(&handle, ProgressBarStatus::None, None);
+            }
+        }
+    });
+}
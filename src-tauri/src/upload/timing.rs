@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+// Wall time since start, with the time spent paused tracked separately so throughput can be
+// worked out over the time rclone was actually allowed to send.
+#[derive(Debug, Clone, Copy)]
+pub struct PauseClock {
+    started: Instant,
+    paused_since: Option<Instant>,
+    paused_total: Duration,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Elapsed {
+    pub wall_time_ms: u64,
+    pub active_time_ms: u64,
+}
+
+impl PauseClock {
+    pub fn start() -> Self {
+        Self {
+            started: Instant::now(),
+            paused_since: None,
+            paused_total: Duration::ZERO,
+        }
+    }
+
+    // Repeated calls with the same state are ignored, so several observers may report it.
+    pub fn set_paused(&mut self, paused: bool) {
+        match (paused, self.paused_since) {
+            (true, None) => self.paused_since = Some(Instant::now()),
+            (false, Some(since)) => {
+                self.paused_total += since.elapsed();
+                self.paused_since = None;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn elapsed(&self) -> Elapsed {
+        let wall = self.started.elapsed();
+        let paused = self.paused_total + self.paused_since.map_or(Duration::ZERO, |s| s.elapsed());
+        Elapsed {
+            wall_time_ms: wall.as_millis() as u64,
+            active_time_ms: wall.saturating_sub(paused).as_millis() as u64,
+        }
+    }
+}
+
+impl Elapsed {
+    pub fn average_bytes_per_sec(&self, bytes: u64) -> Option<f64> {
+        (self.active_time_ms > 0).then(|| bytes as f64 * 1000.0 / self.active_time_ms as f64)
+    }
+}
+
+// Keyed by item id, from the moment a worker picks the item up until it finishes.
+static ITEMS: Mutex<Option<HashMap<String, PauseClock>>> = Mutex::new(None);
+
+pub fn start_item(item_id: &str) {
+    let mut items = ITEMS.lock().unwrap_or_else(|e| e.into_inner());
+    items
+        .get_or_insert_with(HashMap::new)
+        .insert(item_id.to_string(), PauseClock::start());
+}
+
+pub fn set_item_paused(item_id: &str, paused: bool) {
+    let mut items = ITEMS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(clock) = items.as_mut().and_then(|items| items.get_mut(item_id)) {
+        clock.set_paused(paused);
+    }
+}
+
+pub fn item_elapsed(item_id: &str) -> Option<Elapsed> {
+    let items = ITEMS.lock().unwrap_or_else(|e| e.into_inner());
+    items.as_ref()?.get(item_id).map(PauseClock::elapsed)
+}
+
+pub fn finish_item(item_id: &str) -> Option<Elapsed> {
+    let mut items = ITEMS.lock().unwrap_or_else(|e| e.into_inner());
+    items.as_mut()?.remove(item_id).map(|clock| clock.elapsed())
+}
+
+// Follows the job-wide pause switch until the returned handle is aborted.
+pub fn track_job_pause(
+    clock: Arc<Mutex<PauseClock>>,
+    mut pause_rx: watch::Receiver<bool>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let paused = *pause_rx.borrow_and_update();
+            clock
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .set_paused(paused);
+            if pause_rx.changed().await.is_err() {
+                break;
+            }
+        }
+    })
+}
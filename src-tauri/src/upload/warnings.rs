@@ -0,0 +1,28 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+// Enough to show what went on without keeping every duplicate-object notice of a huge folder.
+const MAX_WARNINGS_PER_ITEM: usize = 20;
+
+// rclone NOTICE and WARNING lines per item id, gathered across every process the item ran and
+// handed out once when it finishes.
+static ITEMS: Mutex<Option<HashMap<String, Vec<String>>>> = Mutex::new(None);
+
+pub fn record(item_id: &str, message: String) {
+    let mut items = ITEMS.lock().unwrap_or_else(|e| e.into_inner());
+    let warnings = items
+        .get_or_insert_with(HashMap::new)
+        .entry(item_id.to_string())
+        .or_default();
+    if warnings.len() < MAX_WARNINGS_PER_ITEM && !warnings.contains(&message) {
+        warnings.push(message);
+    }
+}
+
+pub fn take(item_id: &str) -> Vec<String> {
+    let mut items = ITEMS.lock().unwrap_or_else(|e| e.into_inner());
+    items
+        .as_mut()
+        .and_then(|items| items.remove(item_id))
+        .unwrap_or_default()
+}
@@ -1,4 +1,4 @@
-import React, { useCallback, useMemo, useState } from 'react'
+import React, { useCallback, useEffect, useMemo, useState } from 'react'
 import { open } from '@tauri-apps/plugin-dialog'
 import { invoke } from '@tauri-apps/api/core'
 import { toast } from 'sonner'
@@ -6,6 +6,13 @@ import { Label } from '@/components/ui/label'
 import { Separator } from '@/components/ui/separator'
 import { Input } from '@/components/ui/input'
 import { Button } from '@/components/ui/button'
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select'
 import { usePreferences, useSavePreferences } from '@/services/preferences'
 import type { DestinationPreset } from '@/types/preferences'
 import { extractDriveFolderId } from '@/lib/drive-url'
@@ -116,6 +123,23 @@ const GeneralPaneForm: React.FC<{
   const [isInstallingRclone, setIsInstallingRclone] = useState(false)
   const [isConfiguringRclone, setIsConfiguringRclone] = useState(false)
 
+  // Suggests an installed rclone while the path is still the bare default; saving is up to
+  // the user.
+  useEffect(() => {
+    if (lastSavedRclonePath !== 'rclone') return
+    invoke<{ found: boolean; path: string | null; version: string | null }>(
+      'detect_rclone_installation'
+    )
+      .then(installation => {
+        if (installation.found && installation.path) {
+          setRclonePathInput(current =>
+            current === 'rclone' ? (installation.path ?? current) : current
+          )
+        }
+      })
+      .catch(() => undefined)
+  }, [lastSavedRclonePath])
+
   const [destinationPresetsDraft, setDestinationPresetsDraft] = useState<
     DestinationPreset[]
   >(() => preferences.destinationPresets ?? [])
@@ -359,8 +383,15 @@ const GeneralPaneForm: React.FC<{
       deduped.push({ ...p, name: p.name.trim(), url })
     }
 
+    // Removing the default preset also clears the default, which would otherwise fail
+    // validation.
+    const defaultId = preferences?.defaultDestinationPresetId ?? null
+    const keepDefault = deduped.some(p => p.id === defaultId)
     try {
-      await savePreferences.mutateAsync({ destinationPresets: deduped })
+      await savePreferences.mutateAsync({
+        destinationPresets: deduped,
+        defaultDestinationPresetId: keepDefault ? defaultId : null,
+      })
       setDestinationPresetsDraft(deduped)
     } catch {
       // Reset to last saved value (from query cache)
@@ -517,8 +548,8 @@ const GeneralPaneForm: React.FC<{
         </SettingsField>
 
         <SettingsField
-          label="Rclone setup (Windows)"
-          description="Install rclone and configure the remote automatically."
+          label="Rclone setup"
+          description="Configure the remote automatically. Installing rclone is only available on Windows."
         >
           <div className="flex flex-wrap gap-2">
             <Button
@@ -630,6 +661,36 @@ const GeneralPaneForm: React.FC<{
             </div>
           </div>
         </SettingsField>
+
+        <SettingsField
+          label="Default destination"
+          description="Preset selected as the upload destination when the app starts."
+        >
+          <Select
+            value={preferences?.defaultDestinationPresetId ?? 'none'}
+            onValueChange={value =>
+              savePreferences.mutate({
+                defaultDestinationPresetId: value === 'none' ? null : value,
+              })
+            }
+            disabled={
+              !preferences?.destinationPresets.length ||
+              savePreferences.isPending
+            }
+          >
+            <SelectTrigger size="sm">
+              <SelectValue placeholder="None" />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value="none">None</SelectItem>
+              {(preferences?.destinationPresets ?? []).map(p => (
+                <SelectItem key={p.id} value={p.id}>
+                  {p.name}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </SettingsField>
       </SettingsSection>
     </div>
   )
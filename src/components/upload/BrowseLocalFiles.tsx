@@ -3,8 +3,12 @@ import { invoke } from '@tauri-apps/api/core'
 import { open } from '@tauri-apps/plugin-dialog'
 import { getCurrentWindow } from '@tauri-apps/api/window'
 import { listen } from '@tauri-apps/api/event'
-import { useLocalUploadQueue } from '@/store/local-upload-queue-store'
+import {
+  useLocalUploadQueue,
+  type LocalUploadItem,
+} from '@/store/local-upload-queue-store'
 import { useUploadDestinationStore } from '@/store/upload-destination-store'
+import { usePreferences } from '@/services/preferences'
 import { useTransferUiStore } from '@/store/transfer-ui-store'
 import { TransferTable } from '@/components/transfers/TransferTable'
 import { toast } from 'sonner'
@@ -17,6 +21,91 @@ function normalizeSelection(
   return Array.isArray(selection) ? selection : [selection]
 }
 
+interface OrphanedUpload {
+  pid: number
+  itemId: string
+  fingerprint: string
+  startedUnixMs: number
+}
+
+// rclone uploads left running by a crashed session block new jobs until they are
+// stopped or explicitly kept.
+function offerToStopOrphans(processes: OrphanedUpload[]) {
+  if (processes.length === 0) return
+  toast.warning('Uploads from a previous session are still running', {
+    id: 'orphaned-uploads',
+    description: `${processes.length} rclone process(es) may upload the same files again. New uploads wait until they are handled.`,
+    duration: Infinity,
+    action: {
+      label: 'Stop them',
+      onClick: () => {
+        invoke<number>('kill_orphaned_uploads')
+          .then(killed => {
+            toast.success(`Stopped ${killed} rclone process(es)`)
+          })
+          .catch(error => {
+            logger.error('Failed to stop orphaned uploads', {
+              error: String(error),
+            })
+          })
+      },
+    },
+    cancel: {
+      label: 'Keep running',
+      onClick: () => {
+        invoke('dismiss_orphaned_uploads').catch(error => {
+          logger.error('Failed to keep orphaned uploads', {
+            error: String(error),
+          })
+        })
+      },
+    },
+  })
+}
+
+interface JobSnapshot {
+  jobId: string
+  startedUnixMs: number
+  summary: {
+    total: number
+    succeeded: number
+    failed: number
+    skipped: number
+  } | null
+  items: {
+    itemId: string
+    path: string
+    kind: string
+    status: string
+    message: string | null
+    saEmail: string | null
+    bytesSent: number
+    totalBytes: number
+    activeFiles: {
+      filePath: string
+      bytesSent: number
+      totalBytes: number
+      saEmail: string | null
+    }[]
+  }[]
+}
+
+function restoredStatus(status: string): LocalUploadItem['status'] {
+  switch (status) {
+    case 'queued':
+    case 'preparing':
+    case 'uploading':
+    case 'paused':
+    case 'done':
+    case 'failed':
+      return status
+    case 'throttled':
+      return 'paused'
+    default:
+      return 'uploading'
+  }
+}
+
 export function BrowseLocalFiles() {
   const {
     items,
@@ -25,11 +114,14 @@ export function BrowseLocalFiles() {
     setItemProgress,
     setItemStatus,
     resetItemsUploadState,
+    restoreItems,
   } = useLocalUploadQueue()
   const recordFileProgress = useTransferUiStore(s => s.recordFileProgress)
   const recordFileList = useTransferUiStore(s => s.recordFileList)
   const clearFileProgress = useTransferUiStore(s => s.clearFileProgress)
-  const { destinationError, destinationFolderId } = useUploadDestinationStore()
+  const { destinationError, destinationFolderId, destinationUrl } =
+    useUploadDestinationStore()
+  const { data: preferences } = usePreferences()
   const [isBrowsing, setIsBrowsing] = useState(false)
   const [isDropActive, setIsDropActive] = useState(false)
   const isUploading = items.some(item =>
@@ -68,18 +160,32 @@ export function BrowseLocalFiles() {
     let unlistenCompleted: (() => void) | null = null
     let unlistenErrorBanner: (() => void) | null = null
     let unlistenNotice: (() => void) | null = null
+    let unlistenOrphans: (() => void) | null = null
 
     const setup = async () => {
       unlistenStatus = await listen<{
         itemId: string
         path: string
         kind: string
-        status: 'queued' | 'preparing' | 'uploading' | 'done' | 'failed'
+        status:
+          | 'queued'
+          | 'preparing'
+          | 'uploading'
+          | 'paused'
+          | 'throttled'
+          | 'done'
+          | 'failed'
         message?: string | null
         saEmail?: string | null
       }>('upload:item_status', event => {
         const { itemId, status, message, saEmail } = event.payload
-        setItemStatus(itemId, status, message ?? null, saEmail ?? null)
+        // Windows soft-pause: shown as paused, with the message explaining the throttle.
+        setItemStatus(
+          itemId,
+          status === 'throttled' ? 'paused' : status,
+          message ?? null,
+          saEmail ?? null
+        )
       })
 
       unlistenProgress = await listen<{
@@ -120,11 +226,17 @@ export function BrowseLocalFiles() {
       })
 
       unlistenCompleted = await listen<{
-        summary: { total: number; succeeded: number; failed: number }
+        summary: {
+          total: number
+          succeeded: number
+          failed: number
+          skipped: number
+        }
       }>('upload:completed', event => {
-        const { total, succeeded, failed } = event.payload.summary
+        const { total, succeeded, failed, skipped } = event.payload.summary
+        const skippedNote = skipped > 0 ? `, ${skipped} skipped` : ''
         toast.success('Upload completed', {
-          description: `${succeeded}/${total} succeeded, ${failed} failed`,
+          description: `${succeeded}/${total} succeeded, ${failed} failed${skippedNote}`,
         })
       })
 
@@ -142,6 +254,49 @@ export function BrowseLocalFiles() {
           toast.message('Upload notice', { description: event.payload.message })
         }
       )
+
+      unlistenOrphans = await listen<{ processes: OrphanedUpload[] }>(
+        'orphaned_uploads_detected',
+        event => {
+          offerToStopOrphans(event.payload.processes)
+        }
+      )
+      // Found at startup, before this listener existed.
+      offerToStopOrphans(
+        await invoke<OrphanedUpload[]>('list_orphaned_uploads')
+      )
+
+      // After a webview reload the queue is empty while the backend keeps uploading;
+      // rebuild it from the backend's snapshot of the running job.
+      const snapshot = await invoke<JobSnapshot | null>('get_job_snapshot', {
+        jobId: null,
+      })
+      if (snapshot && !snapshot.summary) {
+        restoreItems(
+          snapshot.items.map(item => ({
+            id: item.itemId,
+            path: item.path,
+            kind: item.kind === 'folder' ? 'folder' : 'file',
+            addedAt: snapshot.startedUnixMs,
+            status: restoredStatus(item.status),
+            message: item.message,
+            bytesSent: item.bytesSent,
+            totalBytes: item.totalBytes,
+            saEmail: item.saEmail,
+          }))
+        )
+        for (const item of snapshot.items) {
+          for (const file of item.activeFiles) {
+            recordFileProgress(
+              item.itemId,
+              file.filePath,
+              file.bytesSent,
+              file.totalBytes,
+              file.saEmail
+            )
+          }
+        }
+      }
     }
 
     setup().catch(error => {
@@ -158,8 +313,15 @@ export function BrowseLocalFiles() {
       if (unlistenCompleted) unlistenCompleted()
       if (unlistenErrorBanner) unlistenErrorBanner()
       if (unlistenNotice) unlistenNotice()
+      if (unlistenOrphans) unlistenOrphans()
     }
-  }, [recordFileList, recordFileProgress, setItemProgress, setItemStatus])
+  }, [
+    recordFileList,
+    recordFileProgress,
+    restoreItems,
+    setItemProgress,
+    setItemStatus,
+  ])
 
   useEffect(() => {
     let unlisten: (() => void) | null = null
@@ -298,6 +460,10 @@ export function BrowseLocalFiles() {
             }
           }),
           destinationFolderId,
+          destinationPresetId:
+            preferences?.destinationPresets.find(
+              p => p.url.trim() === destinationUrl.trim()
+            )?.id ?? null,
         },
       })
     } catch (error) {
@@ -35,10 +35,18 @@ export function DestinationPicker() {
   useEffect(() => {
     if (!destinationPresets.length) return
     if (destinationUrl.trim()) return
-    const firstPreset = destinationPresets[0]
-    if (!firstPreset) return
-    setDestinationUrl(firstPreset.url)
-  }, [destinationPresets, destinationUrl, setDestinationUrl])
+    const defaultPreset = destinationPresets.find(
+      p => p.id === preferences?.defaultDestinationPresetId
+    )
+    const initialPreset = defaultPreset ?? destinationPresets[0]
+    if (!initialPreset) return
+    setDestinationUrl(initialPreset.url)
+  }, [
+    destinationPresets,
+    destinationUrl,
+    preferences?.defaultDestinationPresetId,
+    setDestinationUrl,
+  ])
 
   return (
     <section className="space-y-2">
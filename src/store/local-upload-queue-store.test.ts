@@ -52,6 +52,35 @@ describe('localUploadQueue', () => {
     ])
   })
 
+  it('restores items from a snapshot, updating known ids in place', () => {
+    useLocalUploadQueue.getState().addFiles(['/tmp/a.txt'])
+    const existing = useLocalUploadQueue.getState().items[0]
+    if (!existing) throw new Error('Missing first item')
+
+    useLocalUploadQueue.getState().restoreItems([
+      {
+        id: existing.id,
+        path: '/tmp/a.txt',
+        kind: 'file',
+        addedAt: 1,
+        status: 'uploading',
+        bytesSent: 5,
+        totalBytes: 10,
+      },
+      { id: 'restored', path: '/tmp/b', kind: 'folder', addedAt: 1 },
+    ])
+
+    const items = useLocalUploadQueue.getState().items
+    expect(items).toHaveLength(2)
+    expect(items[0]).toMatchObject({
+      id: existing.id,
+      addedAt: existing.addedAt,
+      status: 'uploading',
+      bytesSent: 5,
+    })
+    expect(items[1]).toMatchObject({ id: 'restored', path: '/tmp/b' })
+  })
+
   it('clears the queue', () => {
     useLocalUploadQueue.getState().addFolders(['/tmp/folder'])
     useLocalUploadQueue.getState().clear()
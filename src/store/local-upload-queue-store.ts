@@ -32,6 +32,7 @@ interface LocalUploadQueueState {
     bytesSent: number,
     totalBytes: number
   ) => void
+  restoreItems: (items: LocalUploadItem[]) => void
   resetUploadState: () => void
   resetItemsUploadState: (itemIds: string[]) => void
   remove: (path: string) => void
@@ -136,6 +137,23 @@ export const useLocalUploadQueue = create<LocalUploadQueueState>()(
           'setItemProgress'
         ),
 
+      restoreItems: restored =>
+        set(
+          state => {
+            if (restored.length === 0) return state
+            const byId = new Map(restored.map(item => [item.id, item]))
+            const items = state.items.map(item => {
+              const match = byId.get(item.id)
+              if (!match) return item
+              byId.delete(item.id)
+              return { ...item, ...match, addedAt: item.addedAt }
+            })
+            return { items: items.concat(Array.from(byId.values())) }
+          },
+          undefined,
+          'restoreItems'
+        ),
+
       resetUploadState: () =>
         set(
           state => ({
@@ -3,6 +3,7 @@
 export interface AppPreferences {
   theme: string
   autoCheckUpdates: boolean
+  updateChannel: UpdateChannel
   serviceAccountFolderPath: string | null
   maxConcurrentUploads: number
   uploadChunkSizeMib: number
@@ -11,17 +12,52 @@ export interface AppPreferences {
   rcloneTransfers: number
   rcloneCheckers: number
   destinationPresets: DestinationPreset[]
+  extendedPresets: boolean
+  defaultDestinationPresetId: string | null
+  notifyPerItemCompletion: boolean
+  failOnUnreadable: boolean
+  rcloneNoTraverse: boolean
+  rcloneAutoNoTraverseThresholdFiles: number | null
+  rcloneUseMmap: boolean
+  rcloneBufferSizeMib: number
+  folderUploadStrategy: FolderUploadStrategy
+  nameCollisionPolicy: NameCollisionPolicy
+  saSelectionStrategy: SaSelectionStrategy
+  docShortcutPolicy: DocShortcutPolicy
+  createEmptyFolders: boolean
+  stallTimeoutMinutes: number
+  rcloneLogLevel: RcloneLogLevel
+  jobLogRetentionDays: number
+  showTaskbarProgress: boolean
 }
 
+export type RcloneLogLevel = 'ERROR' | 'NOTICE' | 'INFO' | 'DEBUG'
+
+export type UpdateChannel = 'stable' | 'beta'
+
+export type FolderUploadStrategy = 'per-file' | 'single-process' | 'auto'
+
+export type NameCollisionPolicy = 'keep-both' | 'fail' | 'first-wins'
+
+export type SaSelectionStrategy =
+  | 'round-robin'
+  | 'least-recently-used'
+  | 'weighted'
+
+export type DocShortcutPolicy = 'skip' | 'upload-raw' | 'copy-document'
+
 export interface DestinationPreset {
   id: string
   name: string
   url: string
+  // rclone remote for this destination; falls back to rcloneRemoteName.
+  rcloneRemoteName?: string | null
 }
 
 export const defaultPreferences: AppPreferences = {
   theme: 'system',
   autoCheckUpdates: true,
+  updateChannel: 'stable',
   serviceAccountFolderPath: null,
   maxConcurrentUploads: 3,
   uploadChunkSizeMib: 128,
@@ -30,4 +66,21 @@ export const defaultPreferences: AppPreferences = {
   rcloneTransfers: 4,
   rcloneCheckers: 8,
   destinationPresets: [],
+  extendedPresets: false,
+  defaultDestinationPresetId: null,
+  notifyPerItemCompletion: false,
+  failOnUnreadable: false,
+  rcloneNoTraverse: false,
+  rcloneAutoNoTraverseThresholdFiles: null,
+  rcloneUseMmap: false,
+  rcloneBufferSizeMib: 16,
+  folderUploadStrategy: 'auto',
+  nameCollisionPolicy: 'keep-both',
+  saSelectionStrategy: 'least-recently-used',
+  docShortcutPolicy: 'skip',
+  createEmptyFolders: false,
+  stallTimeoutMinutes: 10,
+  rcloneLogLevel: 'INFO',
+  jobLogRetentionDays: 14,
+  showTaskbarProgress: true,
 }